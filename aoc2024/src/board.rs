@@ -0,0 +1,297 @@
+//! Drives a board running `embedded_aoc::run` over a serial port, in the text
+//! mode: `START INPUT DAY: NN` … `END INPUT` and the `[NN] …` replies.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+use std::time::{Duration, Instant};
+
+use crate::days::Day;
+
+/// The answers of a board.
+#[derive(Debug, PartialEq)]
+pub struct Reply {
+    pub part_1: String,
+    pub part_2: String,
+    pub elapsed: Duration,
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Io(io::Error),
+    /// No complete reply before the timeout.
+    Timeout,
+    UnsupportedDay,
+    InvalidInput,
+    /// The board failed to solve a part.
+    Part(u32, String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "{err}"),
+            Error::Timeout => write!(f, "timeout"),
+            Error::UnsupportedDay => write!(f, "unsupported day"),
+            Error::InvalidInput => write!(f, "invalid input"),
+            Error::Part(part, err) => write!(f, "part {part}: {err}"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+/// A line of a reply, the lines of the other days and the log lines of the
+/// board are [`Line::Other`].
+#[derive(Debug, PartialEq)]
+enum Line<'a> {
+    Part(u32, &'a str),
+    PartError(u32, &'a str),
+    Elapsed(Duration),
+    UnsupportedDay,
+    InvalidInput,
+    Other,
+}
+
+fn parse_line(line: &str, day: u32) -> Line<'_> {
+    match line {
+        "unsupported day" => return Line::UnsupportedDay,
+        "invalid input" => return Line::InvalidInput,
+        _ => {}
+    }
+
+    let Some(line) = line.strip_prefix(&format!("[{day:02}] ")) else {
+        return Line::Other;
+    };
+
+    if let Some(elapsed) = line
+        .strip_prefix("elapsed: ")
+        .and_then(|elapsed| elapsed.split_once(" ("))
+        .and_then(|(_, micros)| micros.strip_suffix("us)")?.parse().ok())
+    {
+        return Line::Elapsed(Duration::from_micros(elapsed));
+    }
+
+    let Some((part, answer)) = line
+        .strip_prefix("part ")
+        .and_then(|line| line.split_once(": "))
+        .and_then(|(part, answer)| Some((part.parse().ok()?, answer)))
+    else {
+        return Line::Other;
+    };
+
+    match answer.strip_prefix("error: ") {
+        Some(err) => Line::PartError(part, err),
+        None => Line::Part(part, answer),
+    }
+}
+
+/// Reads a line without the line ending, the port must have a read timeout.
+fn read_line(port: &mut impl Read, deadline: Instant) -> Result<String, Error> {
+    let mut line = Vec::new();
+    let mut byte = [0];
+    loop {
+        if Instant::now() > deadline {
+            return Err(Error::Timeout);
+        }
+
+        match port.read(&mut byte) {
+            Ok(0) => {}
+            Ok(_) if byte[0] == b'\n' => {
+                return Ok(String::from_utf8_lossy(&line).trim_end().to_string());
+            }
+            Ok(_) => line.push(byte[0]),
+            Err(err) if err.kind() == io::ErrorKind::TimedOut => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+}
+
+/// Sends `input` to the board and waits its answers of `day`.
+///
+/// # Errors
+/// See [`Error`].
+pub fn solve(
+    port: &mut (impl Read + Write),
+    day: u32,
+    input: &str,
+    timeout: Duration,
+) -> Result<Reply, Error> {
+    write!(
+        port,
+        "START INPUT DAY: {day:02}\n{}\nEND INPUT\n",
+        input.trim()
+    )?;
+    port.flush()?;
+
+    let deadline = Instant::now() + timeout;
+
+    let (mut part_1, mut part_2) = (None, None);
+    loop {
+        let line = read_line(port, deadline)?;
+        match parse_line(&line, day) {
+            Line::Part(1, answer) => part_1 = Some(answer.to_string()),
+            Line::Part(2, answer) => part_2 = Some(answer.to_string()),
+            Line::Elapsed(elapsed) => {
+                return Ok(Reply {
+                    part_1: part_1.unwrap_or_default(),
+                    part_2: part_2.unwrap_or_default(),
+                    elapsed,
+                });
+            }
+            Line::PartError(number, err) => {
+                return Err(Error::Part(number, err.to_string()));
+            }
+            Line::UnsupportedDay => return Err(Error::UnsupportedDay),
+            Line::InvalidInput => return Err(Error::InvalidInput),
+            Line::Part(..) | Line::Other => {}
+        }
+    }
+}
+
+/// The board and the host answers of a day.
+pub struct Row {
+    pub day: u32,
+    pub board: Result<Reply, Error>,
+    /// [`Error::Part`] if the host fails to solve a part.
+    pub host: Result<Reply, Error>,
+}
+
+impl Row {
+    /// Solves `input` on the board and on the host.
+    pub fn new(port: &mut (impl Read + Write), day: &Day, input: &str, timeout: Duration) -> Self {
+        let board = solve(port, day.number, input, timeout);
+
+        let start = Instant::now();
+        let host = (day.try_solve_1)(input)
+            .map_err(|err| Error::Part(1, err.to_string()))
+            .and_then(|part_1| {
+                let part_2 =
+                    (day.try_solve_2)(input).map_err(|err| Error::Part(2, err.to_string()))?;
+                Ok(Reply {
+                    part_1,
+                    part_2,
+                    elapsed: start.elapsed(),
+                })
+            });
+
+        Self {
+            day: day.number,
+            board,
+            host,
+        }
+    }
+
+    /// Whether the board answers match the host ones.
+    pub fn verdict(&self) -> Result<(), String> {
+        let board = self.board.as_ref().map_err(ToString::to_string)?;
+        let host = self.host.as_ref().map_err(|err| format!("host {err}"))?;
+
+        for (part, board, host) in [
+            (1, &board.part_1, &host.part_1),
+            (2, &board.part_2, &host.part_2),
+        ] {
+            if board != host {
+                return Err(format!("part {part}: {board}, expected {host}"));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl fmt::Display for Row {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let elapsed = |reply: &Result<Reply, Error>| {
+            reply.as_ref().map_or_else(
+                |_| "-".to_string(),
+                |reply| format!("{}us", reply.elapsed.as_micros()),
+            )
+        };
+        let verdict = self.verdict().err().unwrap_or_else(|| "ok".to_string());
+
+        write!(
+            f,
+            "[{:02}]  {:>14}  {:>14}  {verdict}",
+            self.day,
+            elapsed(&self.board),
+            elapsed(&self.host)
+        )
+    }
+}
+
+pub const HEADER: &str = "day            board            host  result";
+
+#[cfg(test)]
+mod tests {
+    use std::thread;
+
+    use embedded_aoc::host::{StdIo, StdTimer};
+
+    use serialport::TTYPort;
+
+    use crate::days::DAYS;
+
+    use super::*;
+
+    /// A board on the other end of a PTY pair.
+    fn board() -> TTYPort {
+        let (host, board) = TTYPort::pair().unwrap();
+        let rx = board.try_clone_native().unwrap();
+
+        thread::spawn(move || {
+            embedded_aoc::run(
+                (StdIo(rx), StdIo(board)),
+                &StdTimer::new(),
+                embedded_aoc::DummyHandler::default(),
+            )
+        });
+
+        host
+    }
+
+    #[test]
+    fn lines() {
+        assert_eq!(parse_line("[07] part 1: 3749", 7), Line::Part(1, "3749"));
+        assert_eq!(
+            parse_line("[07] part 2: error: parse error", 7),
+            Line::PartError(2, "parse error")
+        );
+        assert_eq!(
+            parse_line("[07] elapsed: 12ms (12345us)", 7),
+            Line::Elapsed(Duration::from_micros(12_345))
+        );
+        assert_eq!(parse_line("[08] part 1: 3749", 7), Line::Other);
+        assert_eq!(parse_line("INFO - [07] start", 7), Line::Other);
+        assert_eq!(parse_line("unsupported day", 7), Line::UnsupportedDay);
+    }
+
+    #[test]
+    fn pty() {
+        let mut port = board();
+        let timeout = Duration::from_secs(10);
+
+        let input = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+        let row = Row::new(&mut port, &DAYS[0], input, timeout);
+        assert_eq!(row.verdict(), Ok(()));
+        let board = row.board.unwrap();
+        assert_eq!((board.part_1.as_str(), board.part_2.as_str()), ("11", "31"));
+
+        assert!(matches!(
+            solve(&mut port, 1, "3   x", timeout),
+            Err(Error::Part(1, _))
+        ));
+
+        // the host errors are shown too, instead of panicking
+        let row = Row::new(&mut port, &DAYS[0], "3   x", timeout);
+        assert!(matches!(row.host, Err(Error::Part(1, _))));
+        assert!(row.to_string().ends_with("-  part 1: parse error at 1:5"));
+        assert!(matches!(
+            solve(&mut port, 2, "7 6 4 2 1", timeout),
+            Err(Error::UnsupportedDay)
+        ));
+    }
+}
@@ -0,0 +1,55 @@
+use solution::{Error, Solution};
+
+pub struct Day {
+    pub number: u32,
+    pub input: &'static str,
+    pub try_solve_1: fn(&str) -> Result<String, Error>,
+    pub try_solve_2: fn(&str) -> Result<String, Error>,
+}
+
+impl Day {
+    const fn new<S: Solution>(input: &'static str) -> Self {
+        Self {
+            number: S::DAY,
+            input,
+            try_solve_1: try_solve_1::<S>,
+            try_solve_2: try_solve_2::<S>,
+        }
+    }
+}
+
+fn try_solve_1<S: Solution>(input: &str) -> Result<String, Error> {
+    S::try_solve_1(input).map(|answer| answer.to_string())
+}
+
+fn try_solve_2<S: Solution>(input: &str) -> Result<String, Error> {
+    S::try_solve_2(input).map(|answer| answer.to_string())
+}
+
+pub const DAYS: [Day; 25] = [
+    Day::new::<day01::Day>(day01::INPUT),
+    Day::new::<day02::Day>(day02::INPUT),
+    Day::new::<day03::Day>(day03::INPUT),
+    Day::new::<day04::Day>(day04::INPUT),
+    Day::new::<day05::Day>(day05::INPUT),
+    Day::new::<day06::Day>(day06::INPUT),
+    Day::new::<day07::Day>(day07::INPUT),
+    Day::new::<day08::Day>(day08::INPUT),
+    Day::new::<day09::Day>(day09::INPUT),
+    Day::new::<day10::Day>(day10::INPUT),
+    Day::new::<day11::Day>(day11::INPUT),
+    Day::new::<day12::Day>(day12::INPUT),
+    Day::new::<day13::Day>(day13::INPUT),
+    Day::new::<day14::Day>(day14::INPUT),
+    Day::new::<day15::Day>(day15::INPUT),
+    Day::new::<day16::Day>(day16::INPUT),
+    Day::new::<day17::Day>(day17::INPUT),
+    Day::new::<day18::Day>(day18::INPUT),
+    Day::new::<day19::Day>(day19::INPUT),
+    Day::new::<day20::Day>(day20::INPUT),
+    Day::new::<day21::Day>(day21::INPUT),
+    Day::new::<day22::Day>(day22::INPUT),
+    Day::new::<day23::Day>(day23::INPUT),
+    Day::new::<day24::Day>(day24::INPUT),
+    Day::new::<day25::Day>(day25::INPUT),
+];
@@ -0,0 +1,382 @@
+use std::env;
+use std::fs;
+use std::ops::RangeInclusive;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+mod board;
+mod days;
+
+use days::{Day, DAYS};
+
+const USAGE: &str = "usage:
+    aoc2024 run <DAYS> [--part 1|2] [--input FILE]
+    aoc2024 board <DEVICE> <DAYS> [--input FILE] [--baud RATE] [--timeout SECONDS]
+    aoc2024 list
+
+DAYS: 7, all, 3..9, 3..=9 or a comma separated list of them";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Run {
+        days: Vec<u32>,
+        part: Option<u32>,
+        input: Option<String>,
+    },
+    Board {
+        device: String,
+        days: Vec<u32>,
+        input: Option<String>,
+        baud: u32,
+        timeout: Duration,
+    },
+    List,
+}
+
+fn parse_range(value: &str) -> Result<RangeInclusive<u32>, String> {
+    let parse = |value: &str| {
+        value
+            .parse::<u32>()
+            .map_err(|_| format!("invalid day: {value}"))
+    };
+
+    let range = if value == "all" {
+        1..=25
+    } else if let Some((start, end)) = value.split_once("..=") {
+        parse(start)?..=parse(end)?
+    } else if let Some((start, end)) = value.split_once("..") {
+        parse(start)?..=parse(end)?.saturating_sub(1)
+    } else {
+        let day = parse(value)?;
+        day..=day
+    };
+
+    if range.is_empty() || *range.start() < 1 || *range.end() > 25 {
+        return Err(format!("invalid days: {value}"));
+    }
+
+    Ok(range)
+}
+
+/// The days and the options after a command.
+#[derive(Debug, Default)]
+struct Options {
+    days: Vec<u32>,
+    part: Option<u32>,
+    input: Option<String>,
+    baud: Option<u32>,
+    timeout: Option<Duration>,
+}
+
+/// Parses the days and the options of a command, only the `allowed` options
+/// are accepted.
+fn parse_options(
+    mut args: impl Iterator<Item = String>,
+    allowed: &[&str],
+) -> Result<Options, String> {
+    let mut options = Options::default();
+    while let Some(arg) = args.next() {
+        if arg.starts_with("--") && !allowed.contains(&arg.as_str()) {
+            return Err(format!("invalid option: {arg}"));
+        }
+
+        match arg.as_str() {
+            "--part" => match args.next().as_deref() {
+                Some("1") => options.part = Some(1),
+                Some("2") => options.part = Some(2),
+                _ => return Err("invalid part".to_string()),
+            },
+            "--input" => {
+                options.input = Some(args.next().ok_or("missing input file")?);
+            }
+            "--baud" => {
+                options.baud = Some(
+                    args.next()
+                        .and_then(|baud| baud.parse().ok())
+                        .ok_or("invalid baud rate")?,
+                );
+            }
+            "--timeout" => {
+                options.timeout = Some(
+                    args.next()
+                        .and_then(|timeout| timeout.parse().ok())
+                        .map(Duration::from_secs)
+                        .ok_or("invalid timeout")?,
+                );
+            }
+            _ => {
+                for value in arg.split(',') {
+                    options.days.extend(parse_range(value)?);
+                }
+            }
+        }
+    }
+
+    if options.days.is_empty() {
+        return Err("missing days".to_string());
+    }
+
+    if options.input.is_some() && options.days.len() != 1 {
+        return Err("--input requires a single day".to_string());
+    }
+
+    Ok(options)
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Command, String> {
+    match args.next().as_deref() {
+        Some("list") => Ok(Command::List),
+        Some("run") => {
+            let Options {
+                days, part, input, ..
+            } = parse_options(args, &["--part", "--input"])?;
+
+            Ok(Command::Run { days, part, input })
+        }
+        Some("board") => {
+            let device = args.next().ok_or("missing device")?;
+
+            let Options {
+                days,
+                input,
+                baud,
+                timeout,
+                ..
+            } = parse_options(args, &["--input", "--baud", "--timeout"])?;
+
+            Ok(Command::Board {
+                device,
+                days,
+                input,
+                baud: baud.unwrap_or(115_200),
+                timeout: timeout.unwrap_or(Duration::from_mins(1)),
+            })
+        }
+        _ => Err("invalid command".to_string()),
+    }
+}
+
+fn format_duration(elapsed: Duration) -> String {
+    format!("{}ms ({}us)", elapsed.as_millis(), elapsed.as_micros())
+}
+
+fn run(day: &Day, part: Option<u32>, input: &str) -> Duration {
+    let now = Instant::now();
+
+    if part.is_none_or(|part| part == 1) {
+        let start = Instant::now();
+        let result = (day.try_solve_1)(input).unwrap_or_else(|err| format!("error: {err}"));
+        println!(
+            "[{:02}] part 1: {result} ({})",
+            day.number,
+            format_duration(start.elapsed())
+        );
+    }
+
+    if part.is_none_or(|part| part == 2) {
+        let start = Instant::now();
+        let result = (day.try_solve_2)(input).unwrap_or_else(|err| format!("error: {err}"));
+        println!(
+            "[{:02}] part 2: {result} ({})",
+            day.number,
+            format_duration(start.elapsed())
+        );
+    }
+
+    let elapsed = now.elapsed();
+    println!("[{:02}] elapsed: {}", day.number, format_duration(elapsed));
+
+    elapsed
+}
+
+fn main() -> ExitCode {
+    let command = match parse_args(env::args().skip(1)) {
+        Ok(command) => command,
+        Err(err) => {
+            eprintln!("{err}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match command {
+        Command::List => {
+            for day in &DAYS {
+                let status = if day.input.is_empty() {
+                    "no input"
+                } else {
+                    "input"
+                };
+                println!("[{:02}] {status}", day.number);
+            }
+        }
+        Command::Run { days, part, input } => {
+            let input = match input.map(fs::read_to_string).transpose() {
+                Ok(input) => input,
+                Err(err) => {
+                    eprintln!("cannot read input: {err}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let mut total = Duration::ZERO;
+            for day in days.iter().map(|&day| &DAYS[day as usize - 1]) {
+                let input = input.as_deref().unwrap_or(day.input);
+                if input.is_empty() {
+                    println!("[{:02}] no input", day.number);
+                    continue;
+                }
+
+                total += run(day, part, input);
+            }
+
+            if days.len() > 1 {
+                println!("total elapsed: {}", format_duration(total));
+            }
+        }
+        Command::Board {
+            device,
+            days,
+            input,
+            baud,
+            timeout,
+        } => {
+            let input = match input.map(fs::read_to_string).transpose() {
+                Ok(input) => input,
+                Err(err) => {
+                    eprintln!("cannot read input: {err}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            let mut port = match serialport::new(&device, baud)
+                .timeout(Duration::from_millis(100))
+                .open()
+            {
+                Ok(port) => port,
+                Err(err) => {
+                    eprintln!("cannot open {device}: {err}");
+                    return ExitCode::FAILURE;
+                }
+            };
+
+            println!("{}", board::HEADER);
+
+            let (mut board_total, mut host_total, mut failed) =
+                (Duration::ZERO, Duration::ZERO, false);
+            for day in days.iter().map(|&day| &DAYS[day as usize - 1]) {
+                let input = input.as_deref().unwrap_or(day.input);
+                if input.is_empty() {
+                    println!("[{:02}] no input", day.number);
+                    continue;
+                }
+
+                let row = board::Row::new(&mut port, day, input, timeout);
+                println!("{row}");
+
+                failed |= row.verdict().is_err();
+                board_total += row
+                    .board
+                    .as_ref()
+                    .map_or(Duration::ZERO, |board| board.elapsed);
+                host_total += row
+                    .host
+                    .as_ref()
+                    .map_or(Duration::ZERO, |host| host.elapsed);
+            }
+
+            if days.len() > 1 {
+                println!(
+                    "total   {:>14}  {:>14}",
+                    format!("{}us", board_total.as_micros()),
+                    format!("{}us", host_total.as_micros())
+                );
+            }
+
+            if failed {
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    ExitCode::SUCCESS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &str) -> impl Iterator<Item = String> {
+        args.split_whitespace().map(String::from)
+    }
+
+    #[test]
+    fn days_table() {
+        for (i, day) in DAYS.iter().enumerate() {
+            assert_eq!(day.number as usize, i + 1);
+        }
+    }
+
+    #[test]
+    fn parse_run() {
+        assert_eq!(
+            parse_args(args("run 7")),
+            Ok(Command::Run {
+                days: vec![7],
+                part: None,
+                input: None
+            })
+        );
+        assert_eq!(
+            parse_args(args("run 3..9 --part 2")),
+            Ok(Command::Run {
+                days: (3..9).collect(),
+                part: Some(2),
+                input: None
+            })
+        );
+        assert_eq!(
+            parse_args(args("run 1,3..=4 --input input.txt")),
+            Err("--input requires a single day".to_string())
+        );
+        assert_eq!(
+            parse_args(args("run all")),
+            Ok(Command::Run {
+                days: (1..=25).collect(),
+                part: None,
+                input: None
+            })
+        );
+        assert_eq!(parse_args(args("list")), Ok(Command::List));
+    }
+
+    #[test]
+    fn parse_board() {
+        assert_eq!(
+            parse_args(args("board /dev/ttyACM0 1..=3 --timeout 5")),
+            Ok(Command::Board {
+                device: "/dev/ttyACM0".to_string(),
+                days: vec![1, 2, 3],
+                input: None,
+                baud: 115_200,
+                timeout: Duration::from_secs(5)
+            })
+        );
+        assert!(parse_args(args("board")).is_err());
+        assert!(parse_args(args("board /dev/ttyACM0")).is_err());
+        assert!(parse_args(args("board /dev/ttyACM0 7 --baud fast")).is_err());
+    }
+
+    #[test]
+    fn parse_invalid() {
+        assert!(parse_args(args("")).is_err());
+        assert!(parse_args(args("run")).is_err());
+        assert!(parse_args(args("run 0")).is_err());
+        assert!(parse_args(args("run 26")).is_err());
+        assert!(parse_args(args("run 9..3")).is_err());
+        assert!(parse_args(args("run 7 --part 3")).is_err());
+        assert_eq!(
+            parse_args(args("run 7 --baud 9600")),
+            Err("invalid option: --baud".to_string())
+        );
+    }
+}
@@ -0,0 +1,94 @@
+#![no_std]
+
+use core::fmt;
+
+/// Generates the `answer_1`/`answer_2` tests comparing `part_1()`/`part_2()`
+/// with the answers exported by `aoc::try_get_answers_info_from_cargo`.
+///
+/// The tests pass without checking anything when the input is the empty
+/// locked-day placeholder or when the answer is not recorded yet.
+// `crate::` is meant: it is the day crate invoking the macro
+#[allow(clippy::crate_in_macro_def)]
+#[macro_export]
+macro_rules! tests {
+    () => {
+        $crate::tests!(crate::INPUT, crate::part_1, crate::part_2);
+    };
+    ($input:expr, $part_1:path, $part_2:path) => {
+        #[test]
+        fn answer_1() {
+            $crate::check($input, option_env!("AOC_ANSWER_PART_1"), $part_1);
+        }
+
+        #[test]
+        fn answer_2() {
+            $crate::check($input, option_env!("AOC_ANSWER_PART_2"), $part_2);
+        }
+    };
+}
+
+/// # Panics
+/// Panics if the result of `part` is not `answer`.
+pub fn check<R: fmt::Display>(input: &str, answer: Option<&str>, part: impl FnOnce() -> R) {
+    let Some(answer) = answer else {
+        return;
+    };
+
+    if is_locked(input) {
+        return;
+    }
+
+    let result = part();
+    assert!(
+        displays_as(&result, answer),
+        "expected answer {answer}, found {result}"
+    );
+}
+
+/// `true` for the empty input written for locked (or not yet downloaded) days.
+#[must_use]
+pub fn is_locked(input: &str) -> bool {
+    input.trim().is_empty()
+}
+
+/// Compares the `Display` output of `value` with `expected` without allocating.
+pub fn displays_as(value: &impl fmt::Display, expected: &str) -> bool {
+    struct Matcher<'a>(&'a str);
+
+    impl fmt::Write for Matcher<'_> {
+        fn write_str(&mut self, s: &str) -> fmt::Result {
+            self.0 = self.0.strip_prefix(s).ok_or(fmt::Error)?;
+            Ok(())
+        }
+    }
+
+    let mut matcher = Matcher(expected);
+    fmt::write(&mut matcher, format_args!("{value}")).is_ok() && matcher.0.is_empty()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const INPUT: &str = "3   4";
+
+    #[test]
+    fn matches_display() {
+        assert!(displays_as(&1234, "1234"));
+        assert!(displays_as(&"co,de,ka,ta", "co,de,ka,ta"));
+        assert!(!displays_as(&123, "1234"));
+        assert!(!displays_as(&12345, "1234"));
+    }
+
+    #[test]
+    fn skip_locked_day() {
+        check("", Some("11"), || 0);
+        check(INPUT, None, || 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "expected answer 11, found 0")]
+    fn wrong_answer() {
+        check(INPUT, Some("11"), || 0);
+    }
+}
@@ -0,0 +1,50 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+use dirs::cache_dir;
+
+use crate::error::Result;
+use crate::fetcher::{DirFetcher, Fetcher};
+
+/// Local input cache, laid out as `<dir>/<year>/dayNN.txt`.
+pub struct Cache {
+    dir: DirFetcher,
+}
+
+impl Cache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: DirFetcher::new(dir),
+        }
+    }
+
+    /// `AOC_CACHE_DIR` if set, `<cache dir>/aoc` otherwise.
+    pub fn default_dir() -> Option<PathBuf> {
+        env::var_os("AOC_CACHE_DIR")
+            .map(PathBuf::from)
+            .or_else(|| cache_dir().map(|dir| dir.join("aoc")))
+    }
+
+    #[must_use]
+    pub fn path(&self, year: i32, day: u32) -> PathBuf {
+        self.dir.path(year, day)
+    }
+
+    /// # Errors
+    /// Returns an error if the input is not cached.
+    pub fn get(&self, year: i32, day: u32) -> Result<String> {
+        self.dir.fetch(year, day)
+    }
+
+    /// # Errors
+    /// Returns an error if the cache file cannot be written.
+    pub fn put(&self, year: i32, day: u32, data: &str) -> Result<()> {
+        let path = self.dir.path(year, day);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        Ok(fs::write(path, data)?)
+    }
+}
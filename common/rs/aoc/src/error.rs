@@ -0,0 +1,81 @@
+use std::{error, fmt, io};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+#[derive(Debug)]
+pub enum Error {
+    /// `Cargo.toml` cannot be read.
+    CargoToml(io::Error),
+    /// `Cargo.toml` is not valid TOML.
+    InvalidCargoToml(toml::de::Error),
+    /// A `package.metadata.aoc` key is missing.
+    MissingMetadata(&'static str),
+    /// A `package.metadata.aoc` key has the wrong type or value.
+    InvalidMetadata(&'static str),
+    /// There is no such puzzle.
+    InvalidDay { year: i32, day: u32 },
+    /// The session key cannot be read from the config dir.
+    Session(io::Error),
+    /// The request failed or the server answered with a 4xx/5xx status.
+    Http(reqwest::Error),
+    /// The fetcher has no input for this puzzle.
+    NotAvailable { year: i32, day: u32 },
+    /// Only parts 1 and 2 can be submitted.
+    InvalidPart(u32),
+    /// The answer page does not contain a known verdict.
+    UnexpectedResponse,
+    /// Reading or writing an input file failed.
+    Io(io::Error),
+    /// There is no cache dir for the answer log, see [`crate::Cache::default_dir`].
+    NoCacheDir,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::CargoToml(err) => write!(f, "cannot read Cargo.toml: {err}"),
+            Error::InvalidCargoToml(err) => write!(f, "invalid Cargo.toml: {err}"),
+            Error::MissingMetadata(key) => write!(f, "cannot find package.metadata.aoc.{key}"),
+            Error::InvalidMetadata(key) => write!(f, "invalid package.metadata.aoc.{key}"),
+            Error::InvalidDay { year, day } => write!(f, "invalid puzzle {year} day {day}"),
+            Error::Session(err) => write!(f, "cannot read session key: {err}"),
+            Error::Http(err) => write!(f, "http error: {err}"),
+            Error::NotAvailable { year, day } => {
+                write!(f, "input {year} day {day} not available")
+            }
+            Error::InvalidPart(part) => write!(f, "invalid part {part}"),
+            Error::UnexpectedResponse => write!(f, "unexpected response"),
+            Error::Io(err) => write!(f, "io error: {err}"),
+            Error::NoCacheDir => write!(f, "cannot find the cache dir for the answer log"),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::CargoToml(err) | Error::Session(err) | Error::Io(err) => Some(err),
+            Error::InvalidCargoToml(err) => Some(err),
+            Error::Http(err) => Some(err),
+            Error::MissingMetadata(_)
+            | Error::InvalidMetadata(_)
+            | Error::InvalidDay { .. }
+            | Error::NotAvailable { .. }
+            | Error::InvalidPart(_)
+            | Error::UnexpectedResponse
+            | Error::NoCacheDir => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(err: reqwest::Error) -> Self {
+        Error::Http(err)
+    }
+}
@@ -0,0 +1,231 @@
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use dirs::config_dir;
+
+use reqwest::{
+    blocking::{Client as HttpClient, Response},
+    header::{self, HeaderMap, HeaderValue},
+    redirect::Policy,
+};
+
+use crate::cache::Cache;
+use crate::error::{Error, Result};
+
+/// Source of puzzle inputs.
+pub trait Fetcher {
+    /// # Errors
+    /// Returns an error if the input for `year`/`day` cannot be provided.
+    fn fetch(&self, year: i32, day: u32) -> Result<String>;
+}
+
+impl<F: Fetcher + ?Sized> Fetcher for &F {
+    fn fetch(&self, year: i32, day: u32) -> Result<String> {
+        (**self).fetch(year, day)
+    }
+}
+
+impl<F: Fetcher + ?Sized> Fetcher for Box<F> {
+    fn fetch(&self, year: i32, day: u32) -> Result<String> {
+        (**self).fetch(year, day)
+    }
+}
+
+/// Downloads inputs from adventofcode.com (or a stand-in server).
+pub struct HttpFetcher {
+    base_url: String,
+    session: String,
+}
+
+impl HttpFetcher {
+    pub const BASE_URL: &'static str = "https://adventofcode.com";
+
+    pub fn new(session: impl Into<String>) -> Self {
+        Self {
+            base_url: Self::BASE_URL.to_string(),
+            session: session.into(),
+        }
+    }
+
+    /// Reads the session key from `<config dir>/adventofcode.session`.
+    ///
+    /// # Errors
+    /// Returns an error if the config dir or the session file are missing.
+    pub fn from_config() -> Result<Self> {
+        let session_file = config_dir()
+            .ok_or_else(|| {
+                Error::Session(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "cannot find config dir",
+                ))
+            })?
+            .join("adventofcode.session");
+
+        Ok(Self::new(
+            fs::read_to_string(session_file)
+                .map_err(Error::Session)?
+                .trim(),
+        ))
+    }
+
+    /// [`HttpFetcher::from_config`], honouring `AOC_BASE_URL`.
+    ///
+    /// # Errors
+    /// Returns an error if the config dir or the session file are missing.
+    pub fn from_env() -> Result<Self> {
+        let fetcher = Self::from_config()?;
+
+        Ok(match env::var("AOC_BASE_URL") {
+            Ok(base_url) => fetcher.with_base_url(base_url),
+            Err(_) => fetcher,
+        })
+    }
+
+    #[must_use]
+    pub fn with_base_url(self, base_url: impl Into<String>) -> Self {
+        Self {
+            base_url: base_url.into(),
+            ..self
+        }
+    }
+
+    pub(crate) fn base_url(&self) -> &str {
+        &self.base_url
+    }
+
+    pub(crate) fn client(&self) -> Result<HttpClient> {
+        let cookie_header = HeaderValue::from_str(&format!("session={}", self.session))
+            .map_err(|err| Error::Session(io::Error::new(io::ErrorKind::InvalidData, err)))?;
+        let content_type_header = HeaderValue::from_static("text/plain");
+        let user_agent_header = HeaderValue::from_static(concat!(
+            env!("CARGO_PKG_REPOSITORY"),
+            " ",
+            env!("CARGO_PKG_VERSION")
+        ));
+
+        let mut headers = HeaderMap::new();
+        headers.insert(header::COOKIE, cookie_header);
+        headers.insert(header::CONTENT_TYPE, content_type_header);
+        headers.insert(header::USER_AGENT, user_agent_header);
+
+        Ok(HttpClient::builder()
+            .default_headers(headers)
+            .redirect(Policy::none())
+            .build()?)
+    }
+}
+
+impl Fetcher for HttpFetcher {
+    fn fetch(&self, year: i32, day: u32) -> Result<String> {
+        let url = format!("{}/{year}/day/{day}/input", self.base_url);
+
+        Ok(self
+            .client()?
+            .get(url)
+            .send()
+            .and_then(Response::error_for_status)
+            .and_then(Response::text)?)
+    }
+}
+
+/// Reads inputs from a local directory laid out as `<root>/<year>/dayNN.txt`.
+pub struct DirFetcher {
+    root: PathBuf,
+}
+
+impl DirFetcher {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+
+    #[must_use]
+    pub fn path(&self, year: i32, day: u32) -> PathBuf {
+        self.root
+            .join(year.to_string())
+            .join(format!("day{day:02}.txt"))
+    }
+}
+
+impl Fetcher for DirFetcher {
+    fn fetch(&self, year: i32, day: u32) -> Result<String> {
+        let data = match fs::read_to_string(self.path(year, day)) {
+            Ok(data) => data,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Err(Error::NotAvailable { year, day });
+            }
+            Err(err) => return Err(Error::Io(err)),
+        };
+        if data.is_empty() {
+            return Err(Error::NotAvailable { year, day });
+        }
+
+        Ok(data)
+    }
+}
+
+/// Never fetches anything: used when running offline.
+pub struct OfflineFetcher;
+
+impl Fetcher for OfflineFetcher {
+    fn fetch(&self, year: i32, day: u32) -> Result<String> {
+        Err(Error::NotAvailable { year, day })
+    }
+}
+
+/// Looks up the [`Cache`] first and stores whatever the inner fetcher returns.
+pub struct CachedFetcher<F> {
+    cache: Cache,
+    fetcher: F,
+}
+
+impl<F> CachedFetcher<F> {
+    pub fn new(cache: Cache, fetcher: F) -> Self {
+        Self { cache, fetcher }
+    }
+}
+
+impl<F: Fetcher> Fetcher for CachedFetcher<F> {
+    fn fetch(&self, year: i32, day: u32) -> Result<String> {
+        if let Ok(data) = self.cache.get(year, day) {
+            return Ok(data);
+        }
+
+        let data = self.fetcher.fetch(year, day)?;
+
+        // the cache is best effort: a read-only cache dir must not break the build
+        self.cache.put(year, day, &data).ok();
+
+        Ok(data)
+    }
+}
+
+/// Fetcher used by [`crate::get_input`] and [`crate::get_puzzle`].
+///
+/// - `AOC_INPUT_DIR`: read inputs from a local directory (see [`DirFetcher`]);
+/// - `AOC_OFFLINE`: never touch the network;
+/// - `AOC_BASE_URL`: download from a stand-in server instead of adventofcode.com.
+pub struct DefaultFetcher;
+
+impl DefaultFetcher {
+    /// Whether the network must not be touched, the puzzles are not in
+    /// `AOC_INPUT_DIR`.
+    pub(crate) fn is_offline() -> bool {
+        env::var_os("AOC_INPUT_DIR").is_some() || env::var_os("AOC_OFFLINE").is_some()
+    }
+}
+
+impl Fetcher for DefaultFetcher {
+    fn fetch(&self, year: i32, day: u32) -> Result<String> {
+        if let Some(root) = env::var_os("AOC_INPUT_DIR") {
+            return DirFetcher::new(root).fetch(year, day);
+        }
+
+        if Self::is_offline() {
+            return OfflineFetcher.fetch(year, day);
+        }
+
+        HttpFetcher::from_env()?.fetch(year, day)
+    }
+}
@@ -1,24 +1,31 @@
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use toml::{Table, Value};
 
 use chrono::{FixedOffset, NaiveDate, TimeZone, Utc};
 
-use dirs::config_dir;
+mod cache;
+mod error;
+mod fetcher;
+mod puzzle;
+mod submit;
 
-use reqwest::{
-    blocking::{Client as HttpClient, Response},
-    header::{self, HeaderMap, HeaderValue},
-    redirect::Policy,
+pub use cache::Cache;
+pub use error::{Error, Result};
+pub use fetcher::{
+    CachedFetcher, DefaultFetcher, DirFetcher, Fetcher, HttpFetcher, OfflineFetcher,
 };
+pub use puzzle::{ANSWERS_FILE, EXAMPLES_DIR, Puzzle, get_puzzle, get_puzzle_with};
+pub use submit::{AnswerLog, Verdict, submit, submit_with};
 
-fn is_day_unlocked(year: i32, day: u32) -> bool {
+fn is_day_unlocked(year: i32, day: u32) -> Result<bool> {
     let timezone = FixedOffset::east_opt(-5 * 3600).unwrap();
     let now = timezone.from_utc_datetime(&Utc::now().naive_utc());
 
     let local_datetime = NaiveDate::from_ymd_opt(year, 12, day)
-        .unwrap()
+        .filter(|_| (1..=25).contains(&day))
+        .ok_or(Error::InvalidDay { year, day })?
         .and_hms_opt(0, 0, 0)
         .unwrap();
 
@@ -27,105 +34,225 @@ fn is_day_unlocked(year: i32, day: u32) -> bool {
         .single()
         .unwrap();
 
-    now.signed_duration_since(unlock_datetime)
+    Ok(now
+        .signed_duration_since(unlock_datetime)
         .num_milliseconds()
-        >= 0
+        >= 0)
+}
+
+/// Writes the empty input used for locked days, unless already there.
+///
+/// # Errors
+/// Returns an error if the input file cannot be written.
+pub fn write_empty_input(input_file: &str) -> Result<()> {
+    let input = PathBuf::from(input_file);
+    if !input.exists() {
+        fs::write(&input, "")?;
+    }
+
+    Ok(())
 }
 
 /// # Panics
 pub fn get_input(year: i32, day: u32, input_file: &str) {
+    try_get_input(year, day, input_file).unwrap_or_else(|err| panic!("{err}"));
+}
+
+/// Resolves the input through the local cache first, then [`DefaultFetcher`].
+///
+/// # Errors
+/// See [`Error`].
+pub fn try_get_input(year: i32, day: u32, input_file: &str) -> Result<()> {
+    match Cache::default_dir() {
+        Some(dir) => try_get_input_with(
+            year,
+            day,
+            input_file,
+            CachedFetcher::new(Cache::new(dir), DefaultFetcher),
+        ),
+        None => try_get_input_with(year, day, input_file, DefaultFetcher),
+    }
+}
+
+/// # Panics
+pub fn get_input_with(year: i32, day: u32, input_file: &str, fetcher: impl Fetcher) {
+    try_get_input_with(year, day, input_file, fetcher).unwrap_or_else(|err| panic!("{err}"));
+}
+
+/// # Errors
+/// See [`Error`].
+pub fn try_get_input_with(
+    year: i32,
+    day: u32,
+    input_file: &str,
+    fetcher: impl Fetcher,
+) -> Result<()> {
     let input = PathBuf::from(input_file);
 
     let input_data = fs::read_to_string(&input);
 
-    let input_is_missing = input_data.as_ref().map(String::is_empty).unwrap_or(true);
+    let input_is_missing = input_data.as_ref().map_or(true, String::is_empty);
     if !input_is_missing {
-        return;
+        return Ok(());
     }
 
-    if !is_day_unlocked(year, day) {
-        let data = "";
-        let must_write = input_data.map(|content| content != data).unwrap_or(true);
-        if must_write {
-            fs::write(&input, data).expect("cannot write (default) input file");
-        }
-        return;
+    if !is_day_unlocked(year, day)? {
+        return write_empty_input(input_file);
     }
 
-    let session_key = fs::read_to_string(
-        config_dir()
-            .expect("cannot find config dir")
-            .join("adventofcode.session"),
-    )
-    .expect("cannot read session key");
-
-    let cookie_header = HeaderValue::from_str(&format!("session={}", session_key.trim())).unwrap();
-    let content_type_header = HeaderValue::from_str("text/plain").unwrap();
-    let user_agent_header = HeaderValue::from_str(&format!(
-        "{} {}",
-        env!("CARGO_PKG_REPOSITORY"),
-        env!("CARGO_PKG_VERSION")
-    ))
-    .unwrap();
-
-    let url = format!("https://adventofcode.com/{year}/day/{day}/input");
-
-    let mut headers = HeaderMap::new();
-    headers.insert(header::COOKIE, cookie_header);
-    headers.insert(header::CONTENT_TYPE, content_type_header);
-    headers.insert(header::USER_AGENT, user_agent_header);
-
-    let data = HttpClient::builder()
-        .default_headers(headers)
-        .redirect(Policy::none())
-        .build()
-        .unwrap()
-        .get(url)
-        .send()
-        .and_then(Response::error_for_status)
-        .and_then(Response::text)
-        .unwrap();
+    let data = fetcher.fetch(year, day)?;
 
-    let must_write = input_data.map(|content| content != data).unwrap_or(true);
+    let must_write = input_data.map_or(true, |content| content != data);
     if must_write {
-        fs::write(&input, &data).expect("cannot write (default) input file");
+        fs::write(&input, &data)?;
     }
+
+    Ok(())
 }
 
 /// # Panics
 pub fn get_input_info_from_cargo(input_file: Option<String>) {
-    let config = fs::read_to_string("Cargo.toml")
-        .expect("cannot find Cargo.toml")
-        .parse::<Table>()
-        .expect("invalid Cargo.toml");
+    try_get_input_info_from_cargo(input_file).unwrap_or_else(|err| panic!("{err}"));
+}
 
-    let data = config
-        .get("package")
-        .and_then(|value| value.get("metadata"))
-        .and_then(|value| value.get("aoc"))
-        .expect("cannot find package.metadata.aoc");
+/// Reads `package.metadata.aoc` from `Cargo.toml` and resolves the input.
+///
+/// # Errors
+/// See [`Error`].
+pub fn try_get_input_info_from_cargo(input_file: Option<String>) -> Result<()> {
+    let config = fs::read_to_string("Cargo.toml").map_err(Error::CargoToml)?;
 
-    let year = get_int(
-        data.get("year")
-            .expect("cannot find package.metadata.aoc.year"),
-    )
-    .expect("invalid year")
-    .try_into()
-    .expect("invalid year value");
-    let day = get_int(
-        data.get("day")
-            .expect("cannot find package.metadata.aoc.day"),
+    let (year, day, input_file) = input_info(&config, input_file)?;
+
+    try_get_input(year, day, &input_file)
+}
+
+fn metadata(config: &str) -> Result<Value> {
+    let mut config = config.parse::<Table>().map_err(Error::InvalidCargoToml)?;
+
+    config
+        .remove("package")
+        .and_then(|mut value| value.as_table_mut()?.remove("metadata"))
+        .and_then(|mut value| value.as_table_mut()?.remove("aoc"))
+        .ok_or(Error::MissingMetadata("*"))
+}
+
+fn year_day(data: &Value) -> Result<(i32, u32)> {
+    let year = get_int(data.get("year").ok_or(Error::MissingMetadata("year"))?)
+        .and_then(|value| value.try_into().ok())
+        .ok_or(Error::InvalidMetadata("year"))?;
+    let day = get_int(data.get("day").ok_or(Error::MissingMetadata("day"))?)
+        .and_then(|value| value.try_into().ok())
+        .ok_or(Error::InvalidMetadata("day"))?;
+
+    Ok((year, day))
+}
+
+fn input_info(config: &str, input_file: Option<String>) -> Result<(i32, u32, String)> {
+    let data = metadata(config)?;
+
+    let (year, day) = year_day(&data)?;
+    let input_file = match data.get("input_file") {
+        Some(value) => get_string(value).ok_or(Error::InvalidMetadata("input_file"))?,
+        None => input_file.ok_or(Error::MissingMetadata("input_file"))?,
+    };
+
+    Ok((year, day, input_file))
+}
+
+/// Reads the answers file pointed by `package.metadata.aoc.answers` and
+/// exports each `part_N` as the `AOC_ANSWER_PART_N` compile time env var.
+///
+/// The answers are downloaded first if missing, see
+/// [`try_get_puzzle_info_from_cargo`]: a failed download is only a warning.
+///
+/// # Errors
+/// See [`Error`].
+pub fn try_get_answers_info_from_cargo() -> Result<()> {
+    let config = fs::read_to_string("Cargo.toml").map_err(Error::CargoToml)?;
+
+    // also when missing, to pick up the answers once they are written
+    if let Some(answers_file) = answers_file(&config)? {
+        println!("cargo::rerun-if-changed={answers_file}");
+    }
+
+    if let Err(err) = puzzle_info(&config) {
+        println!("cargo::warning=cannot download the puzzle: {err}");
+    }
+
+    for (key, value) in answers_info(&config)? {
+        println!("cargo::rustc-env=AOC_ANSWER_{}={value}", key.to_uppercase());
+    }
+
+    Ok(())
+}
+
+/// Downloads the examples and the answers of the day next to the answers file
+/// pointed by `package.metadata.aoc.answers`, see [`get_puzzle`].
+///
+/// Nothing is downloaded for a locked day or once the answers are there, the
+/// answers file must be named [`ANSWERS_FILE`].
+///
+/// # Errors
+/// See [`Error`].
+pub fn try_get_puzzle_info_from_cargo() -> Result<()> {
+    let config = fs::read_to_string("Cargo.toml").map_err(Error::CargoToml)?;
+
+    puzzle_info(&config)
+}
+
+fn puzzle_info(config: &str) -> Result<()> {
+    let Some(answers_file) = answers_file(config)? else {
+        return Ok(());
+    };
+
+    let (year, day) = year_day(&metadata(config)?)?;
+
+    if !is_day_unlocked(year, day)? {
+        return Ok(());
+    }
+
+    let answers_file = Path::new(&answers_file);
+    if answers_file.file_name() != Some(ANSWERS_FILE.as_ref()) {
+        return Err(Error::InvalidMetadata("answers"));
+    }
+
+    get_puzzle(
+        year,
+        day,
+        answers_file.parent().unwrap_or_else(|| Path::new(".")),
     )
-    .expect("invalid day")
-    .try_into()
-    .expect("invalid day value");
-    let input_file = data
-        .get("input_file")
-        .and_then(get_string)
-        .or(input_file)
-        .expect("invalid input file");
+}
+
+fn answers_file(config: &str) -> Result<Option<String>> {
+    metadata(config)?
+        .get("answers")
+        .map(|value| get_string(value).ok_or(Error::InvalidMetadata("answers")))
+        .transpose()
+}
 
-    get_input(year, day, &input_file);
+fn answers_info(config: &str) -> Result<Vec<(String, String)>> {
+    let Some(answers_file) = answers_file(config)? else {
+        return Ok(Vec::new());
+    };
+
+    let answers = match fs::read_to_string(&answers_file) {
+        Ok(answers) => answers,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => return Err(Error::Io(err)),
+    };
+
+    answers
+        .parse::<Table>()
+        .map_err(|_| Error::InvalidMetadata("answers"))?
+        .into_iter()
+        .filter(|(key, _)| key.starts_with("part_"))
+        .map(|(key, value)| match value {
+            Value::String(value) => Ok((key, value)),
+            Value::Integer(value) => Ok((key, value.to_string())),
+            _ => Err(Error::InvalidMetadata("answers")),
+        })
+        .collect()
 }
 
 fn get_int(value: &Value) -> Option<i64> {
@@ -141,3 +268,238 @@ fn get_string(value: &Value) -> Option<String> {
         _ => None,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::path::Path;
+    use std::thread;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = env::temp_dir().join(format!("aoc-{name}-{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn write_input(root: &Path, year: i32, day: u32, data: &str) {
+        let fetcher = DirFetcher::new(root);
+        fs::create_dir_all(fetcher.path(year, day).parent().unwrap()).unwrap();
+        fs::write(fetcher.path(year, day), data).unwrap();
+    }
+
+    #[test]
+    fn dir_fetcher() {
+        let root = temp_dir("dir-fetcher");
+        write_input(&root, 2024, 1, "3   4\n");
+
+        let fetcher = DirFetcher::new(&root);
+        assert_eq!(fetcher.fetch(2024, 1).unwrap(), "3   4\n");
+        assert!(fetcher.fetch(2024, 2).is_err());
+    }
+
+    #[test]
+    fn cached_fetcher_populates_cache() {
+        let root = temp_dir("cached-fetcher");
+        write_input(&root.join("source"), 2024, 1, "3   4\n");
+
+        let fetcher = CachedFetcher::new(
+            Cache::new(root.join("cache")),
+            DirFetcher::new(root.join("source")),
+        );
+        assert_eq!(fetcher.fetch(2024, 1).unwrap(), "3   4\n");
+
+        let offline = CachedFetcher::new(Cache::new(root.join("cache")), OfflineFetcher);
+        assert_eq!(offline.fetch(2024, 1).unwrap(), "3   4\n");
+        assert!(offline.fetch(2024, 2).is_err());
+    }
+
+    #[test]
+    fn get_input_with_writes_input_file() {
+        let root = temp_dir("get-input");
+        write_input(&root, 2024, 1, "3   4\n");
+
+        let input_file = root.join("input");
+        get_input_with(
+            2024,
+            1,
+            input_file.to_str().unwrap(),
+            DirFetcher::new(&root),
+        );
+
+        assert_eq!(fs::read_to_string(input_file).unwrap(), "3   4\n");
+    }
+
+    #[test]
+    fn locked_day_writes_empty_input() {
+        let root = temp_dir("locked-day");
+
+        let input_file = root.join("input");
+        try_get_input_with(9999, 1, input_file.to_str().unwrap(), OfflineFetcher).unwrap();
+
+        assert_eq!(fs::read_to_string(input_file).unwrap(), "");
+    }
+
+    #[test]
+    fn unavailable_input_is_an_error() {
+        let root = temp_dir("unavailable");
+
+        let input_file = root.join("input");
+        assert!(matches!(
+            try_get_input_with(2024, 1, input_file.to_str().unwrap(), OfflineFetcher),
+            Err(Error::NotAvailable { year: 2024, day: 1 })
+        ));
+        assert!(matches!(
+            try_get_input_with(2024, 26, input_file.to_str().unwrap(), OfflineFetcher),
+            Err(Error::InvalidDay {
+                year: 2024,
+                day: 26
+            })
+        ));
+    }
+
+    #[test]
+    fn input_info_from_metadata() {
+        let config = r#"
+[package]
+name = "day01"
+
+[package.metadata.aoc]
+year = 2024
+day = 1
+"#;
+        assert_eq!(
+            input_info(config, Some("../input".to_string())).unwrap(),
+            (2024, 1, "../input".to_string())
+        );
+
+        assert!(matches!(
+            input_info("[package]", None),
+            Err(Error::MissingMetadata(_))
+        ));
+        assert!(matches!(
+            input_info(&config.replace("day = 1", "day = \"1\""), None),
+            Err(Error::InvalidMetadata("day"))
+        ));
+        assert!(matches!(
+            input_info(config, None),
+            Err(Error::MissingMetadata("input_file"))
+        ));
+        assert!(matches!(
+            input_info("[package", None),
+            Err(Error::InvalidCargoToml(_))
+        ));
+    }
+
+    #[test]
+    fn answers_from_metadata() {
+        let root = temp_dir("answers");
+        let answers_file = root.join(ANSWERS_FILE);
+        fs::write(&answers_file, "part_1 = \"1234\"\npart_2 = 5678\n").unwrap();
+
+        let config = format!(
+            "[package.metadata.aoc]\nyear = 2024\nday = 1\nanswers = {:?}\n",
+            answers_file.to_str().unwrap()
+        );
+        assert_eq!(
+            answers_info(&config).unwrap(),
+            vec![
+                ("part_1".to_string(), "1234".to_string()),
+                ("part_2".to_string(), "5678".to_string())
+            ]
+        );
+
+        assert!(
+            answers_info("[package.metadata.aoc]\nyear = 2024\nday = 1\n")
+                .unwrap()
+                .is_empty()
+        );
+
+        // the answers are there, nothing to download
+        puzzle_info(&config).unwrap();
+        // a locked day is not downloaded
+        let missing = root.join("missing").join(ANSWERS_FILE);
+        puzzle_info(
+            &config
+                .replace("year = 2024", "year = 2999")
+                .replace(answers_file.to_str().unwrap(), missing.to_str().unwrap()),
+        )
+        .unwrap();
+        assert!(!missing.exists());
+        assert!(matches!(
+            puzzle_info(&config.replace(ANSWERS_FILE, "answers.txt")),
+            Err(Error::InvalidMetadata("answers"))
+        ));
+    }
+
+    #[test]
+    fn http_fetcher_stand_in_server() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let request = BufReader::new(&mut stream)
+                .lines()
+                .map_while(std::io::Result::ok)
+                .take_while(|line| !line.is_empty())
+                .collect::<Vec<_>>();
+
+            let body = "3   4\n";
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+
+            request
+        });
+
+        let data = HttpFetcher::new("secret")
+            .with_base_url(base_url)
+            .fetch(2024, 1)
+            .unwrap();
+        assert_eq!(data, "3   4\n");
+
+        let request = server.join().unwrap();
+        assert_eq!(request[0], "GET /2024/day/1/input HTTP/1.1");
+        assert!(request.iter().any(|line| line == "cookie: session=secret"));
+    }
+
+    #[test]
+    fn http_fetcher_error_status() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            BufReader::new(&mut stream)
+                .lines()
+                .map_while(std::io::Result::ok)
+                .take_while(|line| !line.is_empty())
+                .for_each(drop);
+
+            write!(
+                stream,
+                "HTTP/1.1 400 Bad Request\r\ncontent-length: 0\r\nconnection: close\r\n\r\n"
+            )
+            .unwrap();
+        });
+
+        let result = HttpFetcher::new("secret")
+            .with_base_url(base_url)
+            .fetch(2024, 1);
+        assert!(
+            matches!(result, Err(Error::Http(err)) if err.status().is_some_and(|status| status.as_u16() == 400))
+        );
+
+        server.join().unwrap();
+    }
+}
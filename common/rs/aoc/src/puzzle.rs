@@ -0,0 +1,226 @@
+use std::fs;
+use std::path::Path;
+
+use reqwest::blocking::Response;
+
+use toml::{Table, Value};
+
+use crate::error::{Error, Result};
+use crate::fetcher::{DefaultFetcher, HttpFetcher};
+
+const PRE_CODE_START: &str = "<pre><code>";
+const PRE_CODE_END: &str = "</code></pre>";
+const ANSWER_START: &str = "Your puzzle answer was <code>";
+const ANSWER_END: &str = "</code>";
+const COMPLETE: &str = "Both parts of this puzzle are complete!";
+
+pub const ANSWERS_FILE: &str = "answers.toml";
+pub const EXAMPLES_DIR: &str = "examples";
+
+/// Examples and accepted answers extracted from a puzzle page.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Puzzle {
+    pub examples: Vec<String>,
+    pub answers: Vec<String>,
+    pub complete: bool,
+}
+
+impl Puzzle {
+    #[must_use]
+    pub fn parse(page: &str) -> Self {
+        Self {
+            examples: between(page, PRE_CODE_START, PRE_CODE_END)
+                .map(|block| unescape(&strip_tags(block)))
+                .collect(),
+            answers: between(page, ANSWER_START, ANSWER_END)
+                .map(|answer| unescape(&strip_tags(answer)))
+                .collect(),
+            complete: page.contains(COMPLETE),
+        }
+    }
+
+    /// Writes `<dir>/examples/NN.txt` and, once the puzzle is complete,
+    /// `<dir>/answers.toml`.
+    ///
+    /// # Errors
+    /// Returns an error if the files cannot be written.
+    pub fn write(&self, dir: &Path) -> Result<()> {
+        if !self.examples.is_empty() {
+            let examples = dir.join(EXAMPLES_DIR);
+            fs::create_dir_all(&examples)?;
+            for (i, example) in self.examples.iter().enumerate() {
+                fs::write(examples.join(format!("{:02}.txt", i + 1)), example)?;
+            }
+        }
+
+        if self.complete {
+            let mut answers = Table::new();
+            for (i, answer) in self.answers.iter().enumerate() {
+                answers.insert(format!("part_{}", i + 1), Value::String(answer.clone()));
+            }
+
+            fs::write(dir.join(ANSWERS_FILE), answers.to_string())?;
+        }
+
+        Ok(())
+    }
+}
+
+fn between<'a>(page: &'a str, start: &'a str, end: &'a str) -> impl Iterator<Item = &'a str> {
+    page.split(start)
+        .skip(1)
+        .filter_map(move |block| block.split_once(end).map(|(block, _)| block))
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut result = String::with_capacity(html.len());
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            c if !in_tag => result.push(c),
+            _ => {}
+        }
+    }
+
+    result
+}
+
+fn unescape(html: &str) -> String {
+    html.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+impl HttpFetcher {
+    /// Downloads the puzzle page: answers are only there with a valid session.
+    ///
+    /// # Errors
+    /// See [`crate::Error`].
+    pub fn fetch_puzzle(&self, year: i32, day: u32) -> Result<Puzzle> {
+        let url = format!("{}/{year}/day/{day}", self.base_url());
+
+        let page = self
+            .client()?
+            .get(url)
+            .send()
+            .and_then(Response::error_for_status)
+            .and_then(Response::text)?;
+
+        Ok(Puzzle::parse(&page))
+    }
+}
+
+impl DefaultFetcher {
+    /// [`HttpFetcher::fetch_puzzle`], unless `AOC_INPUT_DIR` or `AOC_OFFLINE`
+    /// is set.
+    ///
+    /// # Errors
+    /// [`Error::NotAvailable`] when offline, see [`crate::Error`].
+    pub fn fetch_puzzle(&self, year: i32, day: u32) -> Result<Puzzle> {
+        if Self::is_offline() {
+            return Err(Error::NotAvailable { year, day });
+        }
+
+        HttpFetcher::from_env()?.fetch_puzzle(year, day)
+    }
+}
+
+/// Downloads examples and answers into `dir`, unless `dir/answers.toml`
+/// already exists.
+///
+/// # Errors
+/// See [`crate::Error`].
+pub fn get_puzzle_with(fetcher: &HttpFetcher, year: i32, day: u32, dir: &Path) -> Result<()> {
+    if dir.join(ANSWERS_FILE).exists() {
+        return Ok(());
+    }
+
+    fetcher.fetch_puzzle(year, day)?.write(dir)
+}
+
+/// [`get_puzzle_with`] through [`DefaultFetcher`].
+///
+/// # Errors
+/// See [`DefaultFetcher::fetch_puzzle`].
+pub fn get_puzzle(year: i32, day: u32, dir: &Path) -> Result<()> {
+    if dir.join(ANSWERS_FILE).exists() {
+        return Ok(());
+    }
+
+    DefaultFetcher.fetch_puzzle(year, day)?.write(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+
+    const PAGE: &str = r#"<main>
+<article class="day-desc"><h2>--- Day 1: Historian Hysteria ---</h2><p>For example:</p>
+<pre><code>3   4
+4   3
+</code></pre>
+<p>Result: <code><em>11</em></code>.</p>
+</article>
+<p>Your puzzle answer was <code>1234</code>.</p>
+<article class="day-desc"><h2 id="part2">--- Part Two ---</h2>
+<pre><code>a -&gt; <em>b</em> &amp; c
+</code></pre>
+</article>
+<p>Your puzzle answer was <code>5678</code>.</p>
+<p class="day-success">Both parts of this puzzle are complete! They provide two gold stars: **</p>
+</main>"#;
+
+    #[test]
+    fn parse_page() {
+        assert_eq!(
+            Puzzle::parse(PAGE),
+            Puzzle {
+                examples: vec!["3   4\n4   3\n".to_string(), "a -> b & c\n".to_string()],
+                answers: vec!["1234".to_string(), "5678".to_string()],
+                complete: true,
+            }
+        );
+    }
+
+    #[test]
+    fn write_puzzle() {
+        let dir = env::temp_dir().join(format!("aoc-puzzle-{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+
+        Puzzle::parse(PAGE).write(&dir).unwrap();
+
+        assert_eq!(
+            fs::read_to_string(dir.join(EXAMPLES_DIR).join("01.txt")).unwrap(),
+            "3   4\n4   3\n"
+        );
+        assert_eq!(
+            fs::read_to_string(dir.join(EXAMPLES_DIR).join("02.txt")).unwrap(),
+            "a -> b & c\n"
+        );
+
+        let answers = fs::read_to_string(dir.join(ANSWERS_FILE))
+            .unwrap()
+            .parse::<Table>()
+            .unwrap();
+        assert_eq!(answers["part_1"].as_str(), Some("1234"));
+        assert_eq!(answers["part_2"].as_str(), Some("5678"));
+    }
+
+    #[test]
+    fn incomplete_puzzle_has_no_answers() {
+        let dir = env::temp_dir().join(format!("aoc-puzzle-incomplete-{}", std::process::id()));
+        fs::remove_dir_all(&dir).ok();
+
+        let page = PAGE.replace(COMPLETE, "");
+        Puzzle::parse(&page).write(&dir).unwrap();
+
+        assert!(dir.join(EXAMPLES_DIR).join("01.txt").exists());
+        assert!(!dir.join(ANSWERS_FILE).exists());
+    }
+}
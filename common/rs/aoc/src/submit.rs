@@ -0,0 +1,428 @@
+use std::fmt;
+use std::fs;
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use reqwest::blocking::Response;
+
+use crate::cache::Cache;
+use crate::error::{Error, Result};
+use crate::fetcher::HttpFetcher;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    /// Wrong answer, without a hint.
+    Incorrect,
+    TooHigh,
+    TooLow,
+    RateLimited(Duration),
+    AlreadySolved,
+}
+
+impl Verdict {
+    #[must_use]
+    pub fn is_wrong(self) -> bool {
+        matches!(
+            self,
+            Verdict::Incorrect | Verdict::TooHigh | Verdict::TooLow
+        )
+    }
+
+    /// Parses the `<article>` of the answer page.
+    ///
+    /// # Errors
+    /// Returns [`Error::UnexpectedResponse`] if the page is not recognized.
+    pub fn parse(page: &str) -> Result<Self> {
+        if page.contains("That's the right answer") {
+            Ok(Verdict::Correct)
+        } else if page.contains("your answer is too high") {
+            Ok(Verdict::TooHigh)
+        } else if page.contains("your answer is too low") {
+            Ok(Verdict::TooLow)
+        } else if page.contains("That's not the right answer") {
+            Ok(Verdict::Incorrect)
+        } else if page.contains("You don't seem to be solving the right level") {
+            Ok(Verdict::AlreadySolved)
+        } else if let Some(position) = page.find("You gave an answer too recently") {
+            Ok(Verdict::RateLimited(parse_wait(&page[position..])))
+        } else {
+            Err(Error::UnexpectedResponse)
+        }
+    }
+}
+
+/// Parses `You have 1m 5s left to wait`, after the lowercase `you have to wait`
+/// of the same sentence.
+fn parse_wait(text: &str) -> Duration {
+    let Some(text) = text
+        .split_once("You have ")
+        .and_then(|(_, text)| text.split_once(" left to wait"))
+        .map(|(text, _)| text)
+    else {
+        return Duration::default();
+    };
+
+    Duration::from_secs(
+        text.split_whitespace()
+            .filter_map(|token| {
+                if let Some(value) = token.strip_suffix('h') {
+                    value.parse::<u64>().ok().map(|value| value * 3600)
+                } else if let Some(value) = token.strip_suffix('m') {
+                    value.parse::<u64>().ok().map(|value| value * 60)
+                } else {
+                    token.strip_suffix('s')?.parse::<u64>().ok()
+                }
+            })
+            .sum(),
+    )
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::TooHigh => write!(f, "too-high"),
+            Verdict::TooLow => write!(f, "too-low"),
+            Verdict::RateLimited(wait) => write!(f, "rate-limited {}", wait.as_secs()),
+            Verdict::AlreadySolved => write!(f, "already-solved"),
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.split_once(' ') {
+            None => match value {
+                "correct" => Ok(Verdict::Correct),
+                "incorrect" => Ok(Verdict::Incorrect),
+                "too-high" => Ok(Verdict::TooHigh),
+                "too-low" => Ok(Verdict::TooLow),
+                "already-solved" => Ok(Verdict::AlreadySolved),
+                _ => Err(Error::UnexpectedResponse),
+            },
+            Some(("rate-limited", wait)) => wait
+                .parse()
+                .map(|wait| Verdict::RateLimited(Duration::from_secs(wait)))
+                .map_err(|_| Error::UnexpectedResponse),
+            Some(_) => Err(Error::UnexpectedResponse),
+        }
+    }
+}
+
+/// Answers already submitted for a day, one `<part> <verdict> <answer>` per line.
+pub struct AnswerLog {
+    path: PathBuf,
+}
+
+impl AnswerLog {
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+
+    /// `<cache dir>/<year>/dayNN.answers`
+    #[must_use]
+    pub fn in_cache(cache: &Cache, year: i32, day: u32) -> Self {
+        Self::new(cache.path(year, day).with_extension("answers"))
+    }
+
+    /// # Errors
+    /// Returns an error if the log exists but cannot be read.
+    pub fn entries(&self) -> Result<Vec<(u32, Verdict, String)>> {
+        let data = match fs::read_to_string(&self.path) {
+            Ok(data) => data,
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(err) => return Err(Error::Io(err)),
+        };
+
+        Ok(data
+            .lines()
+            .filter_map(|line| {
+                let (part, line) = line.split_once(' ')?;
+                // the verdict of a rate limit has a space before the wait
+                let skip = usize::from(line.starts_with("rate-limited "));
+                let (end, _) = line.match_indices(' ').nth(skip)?;
+                let (verdict, answer) = (&line[..end], &line[end + 1..]);
+                Some((
+                    part.parse().ok()?,
+                    verdict.parse().ok()?,
+                    answer.to_string(),
+                ))
+            })
+            .collect())
+    }
+
+    /// The verdict already known for `answer`, including the one implied by
+    /// previous too high/too low answers.
+    ///
+    /// # Errors
+    /// Returns an error if the log cannot be read.
+    pub fn known(&self, part: u32, answer: &str) -> Result<Option<Verdict>> {
+        let value = answer.parse::<i64>().ok();
+
+        Ok(self
+            .entries()?
+            .into_iter()
+            .filter(|(p, _, _)| *p == part)
+            .find_map(
+                |(_, verdict, known)| match (verdict, value, known.parse::<i64>()) {
+                    (Verdict::Correct, _, _) if known == answer => Some(Verdict::Correct),
+                    (Verdict::Correct, _, _) => Some(Verdict::AlreadySolved),
+                    (verdict, _, _) if verdict.is_wrong() && known == answer => Some(verdict),
+                    (Verdict::TooHigh, Some(value), Ok(known)) if value >= known => {
+                        Some(Verdict::TooHigh)
+                    }
+                    (Verdict::TooLow, Some(value), Ok(known)) if value <= known => {
+                        Some(Verdict::TooLow)
+                    }
+                    _ => None,
+                },
+            ))
+    }
+
+    /// # Errors
+    /// Returns an error if the log cannot be written.
+    pub fn record(&self, part: u32, answer: &str, verdict: Verdict) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        writeln!(file, "{part} {verdict} {answer}")?;
+
+        Ok(())
+    }
+}
+
+impl HttpFetcher {
+    /// Posts `answer`, without looking at any [`AnswerLog`].
+    ///
+    /// # Errors
+    /// See [`Error`].
+    pub fn submit(&self, year: i32, day: u32, part: u32, answer: &str) -> Result<Verdict> {
+        if !matches!(part, 1 | 2) {
+            return Err(Error::InvalidPart(part));
+        }
+
+        let url = format!("{}/{year}/day/{day}/answer", self.base_url());
+
+        let page = self
+            .client()?
+            .post(url)
+            .form(&[("level", part.to_string().as_str()), ("answer", answer)])
+            .send()
+            .and_then(Response::error_for_status)
+            .and_then(Response::text)?;
+
+        let article = page
+            .split_once("<article")
+            .and_then(|(_, page)| page.split_once("</article>"))
+            .map_or(page.as_str(), |(article, _)| article);
+
+        Verdict::parse(article)
+    }
+}
+
+/// Submits `answer` unless its verdict is already known from `log`, and
+/// records the new verdict, the wait of a rate limit included.
+///
+/// # Errors
+/// See [`Error`].
+pub fn submit_with(
+    fetcher: &HttpFetcher,
+    log: &AnswerLog,
+    year: i32,
+    day: u32,
+    part: u32,
+    answer: &str,
+) -> Result<Verdict> {
+    let answer = answer.trim();
+
+    if let Some(verdict) = log.known(part, answer)? {
+        return Ok(verdict);
+    }
+
+    let verdict = fetcher.submit(year, day, part, answer)?;
+    if verdict != Verdict::AlreadySolved {
+        log.record(part, answer, verdict)?;
+    }
+
+    Ok(verdict)
+}
+
+/// Submits `answer` with the session from the config dir, keeping the answer
+/// log next to the cached input.
+///
+/// # Errors
+/// [`Error::NoCacheDir`] if there is no cache dir, nothing is submitted
+/// without the answer log. See [`Error`].
+pub fn submit(year: i32, day: u32, part: u32, answer: &str) -> Result<Verdict> {
+    let dir = Cache::default_dir().ok_or(Error::NoCacheDir)?;
+
+    submit_with(
+        &HttpFetcher::from_env()?,
+        &AnswerLog::in_cache(&Cache::new(dir), year, day),
+        year,
+        day,
+        part,
+        answer,
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    #[test]
+    fn parse_verdicts() {
+        assert_eq!(
+            Verdict::parse(
+                "<p>That's the right answer!  You are <em>one gold star</em> closer.</p>"
+            )
+            .unwrap(),
+            Verdict::Correct
+        );
+        assert_eq!(
+            Verdict::parse(
+                "<p>That's not the right answer; your answer is too high.  If you're stuck, ...</p>"
+            )
+            .unwrap(),
+            Verdict::TooHigh
+        );
+        assert_eq!(
+            Verdict::parse("<p>That's not the right answer; your answer is too low.</p>").unwrap(),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            Verdict::parse("<p>That's not the right answer.  If you're stuck, ...</p>").unwrap(),
+            Verdict::Incorrect
+        );
+        assert_eq!(
+            Verdict::parse("<p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait.</p>").unwrap(),
+            Verdict::RateLimited(Duration::from_secs(65))
+        );
+        assert_eq!(
+            Verdict::parse(
+                "<p>You don't seem to be solving the right level.  Did you already complete it?</p>"
+            )
+            .unwrap(),
+            Verdict::AlreadySolved
+        );
+        assert!(Verdict::parse("<p>?</p>").is_err());
+    }
+
+    #[test]
+    fn parse_rate_limited() {
+        assert_eq!(
+            Verdict::parse("<article><p>You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait. <a href=\"/2024/day/1\">[Return to Day 1]</a></p></article>").unwrap(),
+            Verdict::RateLimited(Duration::from_secs(34))
+        );
+        assert_eq!(parse_wait("you have 2m left to wait"), Duration::default());
+    }
+
+    #[test]
+    fn answer_log() {
+        let path = env::temp_dir().join(format!("aoc-answer-log-{}", std::process::id()));
+        fs::remove_file(&path).ok();
+
+        let log = AnswerLog::new(&path);
+        log.record(1, "100", Verdict::TooHigh).unwrap();
+        log.record(1, "10", Verdict::TooLow).unwrap();
+        log.record(1, "42", Verdict::Incorrect).unwrap();
+        log.record(1, "43", Verdict::RateLimited(Duration::from_secs(34)))
+            .unwrap();
+        log.record(2, "co,de,ka,ta", Verdict::Correct).unwrap();
+
+        assert_eq!(log.known(1, "100").unwrap(), Some(Verdict::TooHigh));
+        assert_eq!(log.known(1, "150").unwrap(), Some(Verdict::TooHigh));
+        assert_eq!(log.known(1, "5").unwrap(), Some(Verdict::TooLow));
+        assert_eq!(log.known(1, "42").unwrap(), Some(Verdict::Incorrect));
+        // a rate limited answer can be submitted again
+        assert_eq!(log.known(1, "43").unwrap(), None);
+        assert_eq!(
+            log.entries().unwrap()[3],
+            (
+                1,
+                Verdict::RateLimited(Duration::from_secs(34)),
+                "43".to_string()
+            )
+        );
+        assert_eq!(log.known(2, "co,de,ka,ta").unwrap(), Some(Verdict::Correct));
+        assert_eq!(log.known(2, "other").unwrap(), Some(Verdict::AlreadySolved));
+    }
+
+    #[test]
+    fn submit_known_wrong_answer_once() {
+        let path = env::temp_dir().join(format!("aoc-submit-{}", std::process::id()));
+        fs::remove_file(&path).ok();
+
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+
+            let mut reader = BufReader::new(&mut stream);
+            let request = reader
+                .by_ref()
+                .lines()
+                .map_while(std::io::Result::ok)
+                .take_while(|line| !line.is_empty())
+                .collect::<Vec<_>>();
+            let length = request
+                .iter()
+                .find_map(|line| line.strip_prefix("content-length: "))
+                .unwrap()
+                .parse::<usize>()
+                .unwrap();
+            let mut body = vec![0; length];
+            reader.read_exact(&mut body).unwrap();
+
+            let page = "<main><article><p>That's not the right answer; your answer is too low.</p></article></main>";
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{page}",
+                page.len()
+            )
+            .unwrap();
+
+            (request[0].clone(), String::from_utf8(body).unwrap())
+        });
+
+        let fetcher = HttpFetcher::new("secret").with_base_url(base_url);
+        let log = AnswerLog::new(&path);
+
+        assert_eq!(
+            submit_with(&fetcher, &log, 2024, 1, 1, "10").unwrap(),
+            Verdict::TooLow
+        );
+
+        let (request, body) = server.join().unwrap();
+        assert_eq!(request, "POST /2024/day/1/answer HTTP/1.1");
+        assert_eq!(body, "level=1&answer=10");
+
+        // the server is gone: the verdict must come from the log
+        assert_eq!(
+            submit_with(&fetcher, &log, 2024, 1, 1, "10").unwrap(),
+            Verdict::TooLow
+        );
+        assert_eq!(
+            submit_with(&fetcher, &log, 2024, 1, 1, "7").unwrap(),
+            Verdict::TooLow
+        );
+    }
+}
@@ -0,0 +1,164 @@
+use core::{
+    marker,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+use crate::{Error, Key};
+
+const BITS: usize = u64::BITS as usize;
+
+/// A [`BitSet`](crate::BitSet) that can be shared between threads, over `SIZE` atomic words.
+///
+/// Elements are inserted through a shared reference, so e.g. rayon workers can track the
+/// visited states of a parallel search in one set.
+pub struct AtomicBitSet<T, K, const SIZE: usize> {
+    data: [AtomicU64; SIZE],
+    key: K,
+    // the set does not own any `T`
+    _marker: marker::PhantomData<fn(&T)>,
+}
+
+impl AtomicBitSet<(), fn(&()) -> usize, 0> {
+    pub const fn with_capacity(size: usize) -> usize {
+        debug_assert!(size > 0, "invalid size");
+
+        size.div_ceil(BITS)
+    }
+}
+
+impl<T, K, const SIZE: usize> AtomicBitSet<T, K, SIZE> {
+    pub fn len(&self) -> usize {
+        self.data
+            .iter()
+            .map(|value| value.load(Ordering::Acquire).count_ones() as usize)
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.data
+            .iter()
+            .all(|value| value.load(Ordering::Acquire) == 0)
+    }
+
+    pub fn clear(&mut self) {
+        for value in &mut self.data {
+            *value.get_mut() = 0;
+        }
+    }
+
+    /// Iterates over the keys of the elements in the set, in increasing order.
+    ///
+    /// The words are loaded one at a time, concurrent updates may or may not be seen.
+    pub fn indices(&self) -> impl Iterator<Item = usize> + '_ {
+        self.data.iter().enumerate().flat_map(|(i, value)| {
+            let mut word = value.load(Ordering::Acquire);
+            core::iter::from_fn(move || {
+                if word == 0 {
+                    None
+                } else {
+                    let b = word.trailing_zeros() as usize;
+                    word &= word - 1;
+                    Some(i * BITS + b)
+                }
+            })
+        })
+    }
+}
+
+impl<T, K: Fn(&T) -> usize, const SIZE: usize> AtomicBitSet<T, K, SIZE> {
+    pub const fn new(key: K) -> Self {
+        Self::from_key(key)
+    }
+}
+
+impl<T, K: Key<T>, const SIZE: usize> AtomicBitSet<T, K, SIZE> {
+    /// Like [`AtomicBitSet::new`], for keys that are not closures.
+    pub const fn from_key(key: K) -> Self {
+        Self {
+            data: [const { AtomicU64::new(0) }; SIZE],
+            key,
+            _marker: marker::PhantomData,
+        }
+    }
+
+    fn word(&self, idx: &T) -> Result<(&AtomicU64, u64), Error> {
+        let idx = self.key.to_index(idx);
+        let (i, b) = (idx / BITS, idx % BITS);
+
+        Ok((self.data.get(i).ok_or(Error)?, 1 << b))
+    }
+
+    /// Inserts `idx`, returning whether it was already in the set.
+    ///
+    /// Of many threads inserting the same element exactly one gets `false`.
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn fetch_insert(&self, idx: T) -> Result<bool, Error> {
+        let (data, mask) = self.word(&idx)?;
+
+        Ok(data.fetch_or(mask, Ordering::AcqRel) & mask != 0)
+    }
+
+    /// Removes `idx`, returning whether it was in the set.
+    pub fn fetch_remove(&self, idx: &T) -> Result<bool, Error> {
+        let (data, mask) = self.word(idx)?;
+
+        Ok(data.fetch_and(!mask, Ordering::AcqRel) & mask != 0)
+    }
+
+    pub fn contains(&self, idx: &T) -> Result<bool, Error> {
+        let (data, mask) = self.word(idx)?;
+
+        Ok(data.load(Ordering::Acquire) & mask != 0)
+    }
+}
+
+impl<T, K, const SIZE: usize> AtomicBitSet<T, K, SIZE>
+where
+    K: Key<T> + Clone,
+{
+    pub fn key(&self) -> K {
+        self.key.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+
+    #[test]
+    fn fetch_insert() {
+        let set = AtomicBitSet::<_, _, { AtomicBitSet::with_capacity(200) }>::new(|i: &usize| *i);
+
+        assert!(!set.fetch_insert(3).unwrap());
+        assert!(set.fetch_insert(3).unwrap());
+        assert!(!set.fetch_insert(199).unwrap());
+        assert!(set.fetch_insert(256).is_err());
+
+        assert!(set.indices().eq([3, 199]));
+        assert!(set.fetch_remove(&3).unwrap());
+        assert!(!set.contains(&3).unwrap());
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn shared() {
+        const SIZE: usize = 1000;
+
+        let set = AtomicBitSet::<_, _, { AtomicBitSet::with_capacity(SIZE) }>::new(|i: &usize| *i);
+        let first = std::thread::scope(|s| {
+            let workers = (0..4)
+                .map(|_| s.spawn(|| (0..SIZE).filter(|&i| !set.fetch_insert(i).unwrap()).count()))
+                .collect::<std::vec::Vec<_>>();
+
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap())
+                .sum::<usize>()
+        });
+
+        assert_eq!(first, SIZE);
+        assert_eq!(set.len(), SIZE);
+    }
+}
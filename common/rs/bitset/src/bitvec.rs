@@ -0,0 +1,261 @@
+use core::{fmt, marker, ops};
+
+#[cfg(feature = "alloc")]
+use alloc::{vec, vec::Vec};
+
+use crate::{words, BitIndex, Display, Error, Indices, Iter, Key, BITS};
+
+/// A [`BitSet`](crate::BitSet) sized at runtime, over the `u128` words of `D`.
+///
+/// `D` is usually a caller provided `&mut [u128]` or, with the `alloc` feature, a `Vec<u128>`.
+/// A set of `size` keys needs [`BitSet::with_capacity(size)`](crate::BitSet::with_capacity)
+/// words.
+#[derive(Clone)]
+pub struct BitVec<D, T, K> {
+    data: D,
+    key: K,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<D: AsRef<[u128]>, T, K> BitVec<D, T, K> {
+    /// The number of keys the set can hold.
+    pub fn capacity(&self) -> usize {
+        self.data.as_ref().len() * BITS
+    }
+
+    pub fn len(&self) -> usize {
+        words::len(self.data.as_ref())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        words::is_empty(self.data.as_ref())
+    }
+
+    /// Iterates over the keys of the elements in the set, in increasing order.
+    pub fn indices(&self) -> Indices<'_> {
+        words::indices(self.data.as_ref())
+    }
+
+    /// The smallest key in the set.
+    pub fn first(&self) -> Option<usize> {
+        self.indices().next()
+    }
+
+    /// The smallest key in the set greater than `idx`.
+    pub fn next_after(&self, idx: usize) -> Option<usize> {
+        words::next_after(self.data.as_ref(), idx)
+    }
+
+    /// The number of elements in the union of the sets, without building it.
+    pub fn union_count<O: AsRef<[u128]>>(&self, other: &BitVec<O, T, K>) -> usize {
+        words::union_count(self.data.as_ref(), other.data.as_ref())
+    }
+
+    pub fn is_subset<O: AsRef<[u128]>>(&self, other: &BitVec<O, T, K>) -> bool {
+        words::is_subset(self.data.as_ref(), other.data.as_ref())
+    }
+
+    /// The backing words.
+    pub fn into_inner(self) -> D {
+        self.data
+    }
+
+    /// See [`BitSet::display`](crate::BitSet::display).
+    pub fn display(&self, width: usize, height: usize) -> Display<'_> {
+        Display::new(self.data.as_ref(), width, height)
+    }
+}
+
+impl<D: AsRef<[u128]>, T, K> fmt::Debug for BitVec<D, T, K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.indices()).finish()
+    }
+}
+
+impl<D: AsMut<[u128]>, T, K: Fn(&T) -> usize> BitVec<D, T, K> {
+    /// Builds an empty set over `data`, clearing it.
+    pub fn new(data: D, key: K) -> Self {
+        Self::from_key(data, key)
+    }
+}
+
+impl<D: AsMut<[u128]>, T, K: Key<T>> BitVec<D, T, K> {
+    /// Like [`BitVec::new`], for keys that are not closures, like
+    /// [`GridIndex`](crate::GridIndex).
+    pub fn from_key(mut data: D, key: K) -> Self {
+        data.as_mut().fill(0);
+
+        Self {
+            data,
+            key,
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, K: Fn(&T) -> usize> BitVec<Vec<u128>, T, K> {
+    /// Builds an empty set for the keys up to `size`, backed by a `Vec`.
+    pub fn with_size(size: usize, key: K) -> Self {
+        Self::with_size_from_key(size, key)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, K: Key<T>> BitVec<Vec<u128>, T, K> {
+    /// Like [`BitVec::with_size`], for keys that are not closures.
+    pub fn with_size_from_key(size: usize, key: K) -> Self {
+        Self::from_key(vec![0; size.div_ceil(BITS)], key)
+    }
+}
+
+impl<D: AsMut<[u128]>, T, K> BitVec<D, T, K> {
+    pub fn clear(&mut self) {
+        self.data.as_mut().fill(0);
+    }
+}
+
+impl<D: AsRef<[u128]> + AsMut<[u128]>, T, K: Key<T>> BitVec<D, T, K> {
+    #[allow(clippy::needless_pass_by_value)]
+    pub fn insert(&mut self, idx: T) -> Result<bool, Error> {
+        words::insert(self.data.as_mut(), self.key.to_index(&idx))
+    }
+
+    pub fn contains(&self, idx: &T) -> Result<bool, Error> {
+        words::contains(self.data.as_ref(), self.key.to_index(idx))
+    }
+
+    pub fn remove(&mut self, idx: &T) -> Result<bool, Error> {
+        words::remove(self.data.as_mut(), self.key.to_index(idx))
+    }
+}
+
+impl<D, T, K> BitVec<D, T, K>
+where
+    K: Key<T> + Clone,
+{
+    pub fn key(&self) -> K {
+        self.key.clone()
+    }
+}
+
+impl<D: AsRef<[u128]>, T, K: BitIndex<T>> BitVec<D, T, K> {
+    /// Iterates over the elements in the set, in increasing key order.
+    pub fn iter(&self) -> Iter<'_, T, K> {
+        Iter {
+            indices: self.indices(),
+            key: &self.key,
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, D: AsRef<[u128]>, T, K: BitIndex<T>> IntoIterator for &'a BitVec<D, T, K> {
+    type Item = T;
+    type IntoIter = Iter<'a, T, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+// the words of `other` past the end of `self` are dropped, the missing ones count as zero
+macro_rules! impl_op {
+    ($op:ident, $op_fn:ident, $op_assign:ident, $op_assign_fn:ident, |$a:ident, $b:ident| $body:expr) => {
+        impl<D, O, T, K> ops::$op_assign<&BitVec<O, T, K>> for BitVec<D, T, K>
+        where
+            D: AsMut<[u128]>,
+            O: AsRef<[u128]>,
+        {
+            fn $op_assign_fn(&mut self, other: &BitVec<O, T, K>) {
+                words::apply(self.data.as_mut(), other.data.as_ref(), |$a, $b| $body);
+            }
+        }
+
+        impl<D, O, T, K> ops::$op<&BitVec<O, T, K>> for BitVec<D, T, K>
+        where
+            D: AsMut<[u128]>,
+            O: AsRef<[u128]>,
+        {
+            type Output = Self;
+
+            fn $op_fn(mut self, other: &BitVec<O, T, K>) -> Self {
+                ops::$op_assign::$op_assign_fn(&mut self, other);
+                self
+            }
+        }
+    };
+}
+
+impl_op!(BitOr, bitor, BitOrAssign, bitor_assign, |a, b| a | b);
+impl_op!(BitAnd, bitand, BitAndAssign, bitand_assign, |a, b| a & b);
+impl_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, |a, b| a ^ b);
+impl_op!(Sub, sub, SubAssign, sub_assign, |a, b| a & !b);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::{BitSet, GridIndex};
+
+    #[test]
+    fn slice() {
+        let (width, height) = (141, 142);
+
+        let mut data = [u128::MAX; BitSet::with_capacity(141 * 142)];
+        let mut set = BitVec::from_key(&mut data[..], GridIndex::new(width));
+        assert!(set.is_empty());
+        assert!(set.capacity() >= width * height);
+
+        assert!(!set.insert((140usize, 3usize)).unwrap());
+        assert!(set.insert((140, 3)).unwrap());
+        set.insert((0, 140)).unwrap();
+        assert!(set.insert((height + 1, 0)).is_err());
+
+        assert!(set.iter().eq([(0, 140), (140, 3)]));
+        assert_eq!(set.next_after(140), Some(140 * width + 3));
+
+        let mut other_data = [0; BitSet::with_capacity(141 * 142)];
+        let mut other = BitVec::from_key(&mut other_data[..], GridIndex::new(width));
+        other.insert((0, 140)).unwrap();
+
+        assert!(other.is_subset(&set));
+        assert_eq!(set.union_count(&other), 2);
+        assert!((set - &other).iter().eq([(140, 3)]));
+    }
+
+    #[test]
+    fn unequal_lengths() {
+        let key = |i: &usize| *i;
+        let (mut long, mut short) = ([0; 2], [0; 1]);
+        let mut a = BitVec::from_key(&mut long[..], key);
+        let mut b = BitVec::from_key(&mut short[..], key);
+        a.insert(3).unwrap();
+        a.insert(200).unwrap();
+        b.insert(3).unwrap();
+
+        assert!(b.is_subset(&a));
+        assert!(!a.is_subset(&b));
+        assert_eq!(a.union_count(&b), 2);
+        assert_eq!(b.union_count(&a), 2);
+
+        b |= &a;
+        assert!(b.indices().eq([3]));
+
+        a &= &b;
+        assert!(a.indices().eq([3]));
+    }
+
+    #[cfg(feature = "alloc")]
+    #[test]
+    fn vec() {
+        let mut set = BitVec::with_size(1000, |i: &usize| *i);
+
+        set.insert(999).unwrap();
+        set.insert(3).unwrap();
+        assert!(set.indices().eq([3, 999]));
+        assert!(set.remove(&3).unwrap());
+        assert_eq!(set.len(), 1);
+        assert!(set.contains(&1024).is_err());
+    }
+}
@@ -0,0 +1,97 @@
+use core::fmt;
+
+use crate::{words, BitSet};
+
+/// Renders the keys `0..width * height` of a set as `height` rows of `#` (set) and `.` (unset),
+/// see [`BitSet::display`].
+pub struct Display<'a> {
+    data: &'a [u128],
+    width: usize,
+    height: usize,
+}
+
+impl<'a> Display<'a> {
+    pub(crate) fn new(data: &'a [u128], width: usize, height: usize) -> Self {
+        Self {
+            data,
+            width,
+            height,
+        }
+    }
+
+    fn contains(&self, r: usize, c: usize) -> bool {
+        words::contains(self.data, r * self.width + c).unwrap_or(false)
+    }
+}
+
+impl fmt::Display for Display<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for r in 0..self.height {
+            for c in 0..self.width {
+                f.write_str(if self.contains(r, c) { "#" } else { "." })?;
+            }
+            f.write_str("\n")?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl defmt::Format for Display<'_> {
+    fn format(&self, f: defmt::Formatter) {
+        for r in 0..self.height {
+            for c in 0..self.width {
+                defmt::write!(f, "{=str}", if self.contains(r, c) { "#" } else { "." });
+            }
+            defmt::write!(f, "\n");
+        }
+    }
+}
+
+impl<T, K, const SIZE: usize> BitSet<T, K, SIZE> {
+    /// Renders the set as a grid of `width` x `height` cells, for row-major keys like
+    /// [`GridIndex`](crate::GridIndex).
+    pub fn display(&self, width: usize, height: usize) -> Display<'_> {
+        Display::new(&self.data, width, height)
+    }
+}
+
+impl<T, K, const SIZE: usize> fmt::Debug for BitSet<T, K, SIZE> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_set().entries(self.indices()).finish()
+    }
+}
+
+#[cfg(feature = "defmt")]
+impl<T, K, const SIZE: usize> defmt::Format for BitSet<T, K, SIZE> {
+    fn format(&self, f: defmt::Formatter) {
+        defmt::write!(f, "{{");
+        for (i, index) in self.indices().enumerate() {
+            if i > 0 {
+                defmt::write!(f, ", ");
+            }
+            defmt::write!(f, "{=usize}", index);
+        }
+        defmt::write!(f, "}}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::format;
+
+    use crate::{BitSet, GridIndex};
+
+    #[test]
+    fn display() {
+        let mut set = BitSet::<_, _, { BitSet::with_capacity(4 * 3) }>::from_key(GridIndex::new(4));
+        for p in [(0usize, 0usize), (1, 2), (2, 3)] {
+            set.insert(p).unwrap();
+        }
+
+        assert_eq!(format!("{}", set.display(4, 3)), "#...\n..#.\n...#\n");
+        assert_eq!(format!("{set:?}"), "{0, 6, 11}");
+    }
+}
@@ -0,0 +1,360 @@
+use core::ops;
+
+use crate::{Error, BITS};
+
+const ROW_WORDS: usize = 2;
+
+/// The maximum width of a [`BitGrid`].
+pub const MAX_WIDTH: usize = ROW_WORDS * BITS;
+
+type Row = [u128; ROW_WORDS];
+
+/// Dense set of the cells `(r, c)` of a `W` x `H` grid, one bit per cell.
+///
+/// Every row is a bit mask, so whole grids can be shifted and combined at word speed, e.g. to
+/// grow a flood fill frontier with [`BitGrid::neighbours`].
+// Cannot be copy: this structure can be too heavy
+#[derive(Clone, PartialEq, Eq)]
+pub struct BitGrid<const W: usize, const H: usize> {
+    rows: [Row; H],
+}
+
+impl<const W: usize, const H: usize> BitGrid<W, H> {
+    const ROW_MASK: Row = {
+        assert!(W > 0 && W <= MAX_WIDTH && H > 0, "invalid size");
+
+        let mut mask = [0; ROW_WORDS];
+        let mut i = 0;
+        while i < ROW_WORDS {
+            let bits = W.saturating_sub(i * BITS);
+            mask[i] = if bits >= BITS {
+                u128::MAX
+            } else {
+                (1 << bits) - 1
+            };
+            i += 1;
+        }
+        mask
+    };
+
+    pub const fn new() -> Self {
+        let _ = Self::ROW_MASK;
+
+        Self {
+            rows: [[0; ROW_WORDS]; H],
+        }
+    }
+
+    /// The grid with every cell set.
+    pub const fn full() -> Self {
+        Self {
+            rows: [Self::ROW_MASK; H],
+        }
+    }
+
+    pub const fn width(&self) -> usize {
+        W
+    }
+
+    pub const fn height(&self) -> usize {
+        H
+    }
+
+    fn cell(r: usize, c: usize) -> Result<(usize, usize, u128), Error> {
+        if r < H && c < W {
+            Ok((r, c / BITS, 1 << (c % BITS)))
+        } else {
+            Err(Error)
+        }
+    }
+
+    pub fn insert(&mut self, (r, c): (usize, usize)) -> Result<bool, Error> {
+        let (r, i, mask) = Self::cell(r, c)?;
+
+        let data = &mut self.rows[r][i];
+
+        let result = *data & mask != 0;
+
+        *data |= mask;
+
+        Ok(result)
+    }
+
+    pub fn contains(&self, &(r, c): &(usize, usize)) -> Result<bool, Error> {
+        let (r, i, mask) = Self::cell(r, c)?;
+
+        Ok(self.rows[r][i] & mask != 0)
+    }
+
+    pub fn remove(&mut self, &(r, c): &(usize, usize)) -> Result<bool, Error> {
+        let (r, i, mask) = Self::cell(r, c)?;
+
+        let data = &mut self.rows[r][i];
+
+        let result = *data & mask != 0;
+
+        *data &= !mask;
+
+        Ok(result)
+    }
+
+    pub fn len(&self) -> usize {
+        (0..H).map(|r| self.row_count(r)).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.iter().flatten().all(|&value| value == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.rows = [[0; ROW_WORDS]; H];
+    }
+
+    /// The number of cells set in the row `r`, 0 outside of the grid.
+    pub fn row_count(&self, r: usize) -> usize {
+        self.rows.get(r).map_or(0, |row| {
+            row.iter().map(|value| value.count_ones() as usize).sum()
+        })
+    }
+
+    /// The number of cells set in the column `c`, 0 outside of the grid.
+    pub fn column_count(&self, c: usize) -> usize {
+        self.column(c).count()
+    }
+
+    /// The columns of the cells set in the row `r`, none outside of the grid.
+    pub fn row(&self, r: usize) -> impl Iterator<Item = usize> + '_ {
+        self.rows
+            .get(r)
+            .into_iter()
+            .flatten()
+            .enumerate()
+            .flat_map(|(i, &word)| {
+                let mut word = word;
+                core::iter::from_fn(move || {
+                    if word == 0 {
+                        None
+                    } else {
+                        let b = word.trailing_zeros() as usize;
+                        word &= word - 1;
+                        Some(i * BITS + b)
+                    }
+                })
+            })
+    }
+
+    /// The rows of the cells set in the column `c`, none outside of the grid.
+    pub fn column(&self, c: usize) -> impl Iterator<Item = usize> + '_ {
+        let (i, mask) = (c / BITS, 1 << (c % BITS));
+
+        self.rows.iter().enumerate().filter_map(move |(r, row)| {
+            row.get(i).is_some_and(|word| word & mask != 0).then_some(r)
+        })
+    }
+
+    /// Iterates over the cells in the grid, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        (0..H).flat_map(move |r| self.row(r).map(move |c| (r, c)))
+    }
+
+    /// Every cell moved one row up, the first row is lost.
+    #[must_use]
+    pub fn shift_up(&self) -> Self {
+        let mut result = Self::new();
+        result.rows[..H - 1].copy_from_slice(&self.rows[1..]);
+        result
+    }
+
+    /// Every cell moved one row down, the last row is lost.
+    #[must_use]
+    pub fn shift_down(&self) -> Self {
+        let mut result = Self::new();
+        result.rows[1..].copy_from_slice(&self.rows[..H - 1]);
+        result
+    }
+
+    /// Every cell moved one column left, the first column is lost.
+    #[must_use]
+    pub fn shift_left(&self) -> Self {
+        let mut result = self.clone();
+        for row in &mut result.rows {
+            for i in 0..ROW_WORDS {
+                let carry = row.get(i + 1).map_or(0, |next| next << (BITS - 1));
+                row[i] = (row[i] >> 1) | carry;
+            }
+        }
+        result
+    }
+
+    /// Every cell moved one column right, the last column is lost.
+    #[must_use]
+    pub fn shift_right(&self) -> Self {
+        let mut result = self.clone();
+        for row in &mut result.rows {
+            for i in (0..ROW_WORDS).rev() {
+                let carry = if i > 0 { row[i - 1] >> (BITS - 1) } else { 0 };
+                row[i] = (row[i] << 1) | carry;
+            }
+            for (word, mask) in row.iter_mut().zip(Self::ROW_MASK) {
+                *word &= mask;
+            }
+        }
+        result
+    }
+
+    /// The cells orthogonally adjacent to a cell in the grid, excluding the grid itself.
+    #[must_use]
+    pub fn neighbours(&self) -> Self {
+        (self.shift_up() | &self.shift_down() | &self.shift_left() | &self.shift_right()) - self
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.rows
+            .iter()
+            .flatten()
+            .zip(other.rows.iter().flatten())
+            .all(|(a, b)| a & !b == 0)
+    }
+}
+
+impl<const W: usize, const H: usize> Default for BitGrid<W, H> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const W: usize, const H: usize> ops::Not for BitGrid<W, H> {
+    type Output = Self;
+
+    fn not(self) -> Self {
+        Self::full() - &self
+    }
+}
+
+macro_rules! impl_op {
+    ($op:ident, $op_fn:ident, $op_assign:ident, $op_assign_fn:ident, |$a:ident, $b:ident| $body:expr) => {
+        impl<const W: usize, const H: usize> ops::$op_assign<&Self> for BitGrid<W, H> {
+            fn $op_assign_fn(&mut self, other: &Self) {
+                for ($a, &$b) in self
+                    .rows
+                    .iter_mut()
+                    .flatten()
+                    .zip(other.rows.iter().flatten())
+                {
+                    *$a = $body;
+                }
+            }
+        }
+
+        impl<const W: usize, const H: usize> ops::$op_assign for BitGrid<W, H> {
+            fn $op_assign_fn(&mut self, other: Self) {
+                ops::$op_assign::$op_assign_fn(self, &other);
+            }
+        }
+
+        impl<const W: usize, const H: usize> ops::$op<&Self> for BitGrid<W, H> {
+            type Output = Self;
+
+            fn $op_fn(mut self, other: &Self) -> Self {
+                ops::$op_assign::$op_assign_fn(&mut self, other);
+                self
+            }
+        }
+
+        impl<const W: usize, const H: usize> ops::$op for BitGrid<W, H> {
+            type Output = Self;
+
+            fn $op_fn(mut self, other: Self) -> Self {
+                ops::$op_assign::$op_assign_fn(&mut self, &other);
+                self
+            }
+        }
+    };
+}
+
+impl_op!(BitOr, bitor, BitOrAssign, bitor_assign, |a, b| *a | b);
+impl_op!(BitAnd, bitand, BitAndAssign, bitand_assign, |a, b| *a & b);
+impl_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, |a, b| *a ^ b);
+impl_op!(Sub, sub, SubAssign, sub_assign, |a, b| *a & !b);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn grid<const W: usize, const H: usize>(map: &str) -> BitGrid<W, H> {
+        let mut grid = BitGrid::new();
+        for (r, row) in map.lines().enumerate() {
+            for (c, tile) in row.bytes().enumerate() {
+                if tile == b'#' {
+                    grid.insert((r, c)).unwrap();
+                }
+            }
+        }
+        grid
+    }
+
+    #[test]
+    fn cells() {
+        let mut g = BitGrid::<200, 3>::new();
+
+        assert!(!g.insert((1, 150)).unwrap());
+        assert!(g.insert((1, 150)).unwrap());
+        g.insert((2, 3)).unwrap();
+        g.insert((0, 199)).unwrap();
+        assert!(g.insert((0, 200)).is_err());
+        assert!(g.contains(&(3, 0)).is_err());
+
+        assert!(g.iter().eq([(0, 199), (1, 150), (2, 3)]));
+        assert_eq!(g.len(), 3);
+        assert_eq!(g.row_count(1), 1);
+        assert!(g.column(150).eq([1]));
+        assert_eq!(g.column_count(3), 1);
+        assert_eq!(g.row_count(3), 0);
+        assert_eq!(g.row(3).count(), 0);
+        assert_eq!(g.column_count(MAX_WIDTH), 0);
+
+        assert!(g.remove(&(1, 150)).unwrap());
+        assert_eq!(g.len(), 2);
+    }
+
+    #[test]
+    fn shifts() {
+        let g = BitGrid::<130, 2>::full();
+
+        assert_eq!(g.shift_right().row_count(0), 129);
+        assert!(!g.shift_right().contains(&(0, 0)).unwrap());
+        assert!(!g.shift_left().contains(&(1, 129)).unwrap());
+        assert_eq!(g.shift_up().row_count(1), 0);
+        assert_eq!(g.shift_down().row_count(0), 0);
+
+        let mut g = BitGrid::<130, 2>::new();
+        g.insert((0, 127)).unwrap();
+        assert!(g.shift_right().iter().eq([(0, 128)]));
+        assert!(g.shift_right().shift_left().iter().eq([(0, 127)]));
+    }
+
+    #[test]
+    fn flood_fill() {
+        let walls = grid::<5, 4>(
+            "\
+#####
+#..##
+##..#
+#####",
+        );
+        let open = !walls;
+
+        let mut region = BitGrid::new();
+        region.insert((1, 1)).unwrap();
+        loop {
+            let frontier = region.neighbours() & &open;
+            if frontier.is_empty() {
+                break;
+            }
+            region |= frontier;
+        }
+
+        assert!(region.iter().eq([(1, 1), (1, 2), (2, 2), (2, 3)]));
+        assert!(region.is_subset(&open));
+    }
+}
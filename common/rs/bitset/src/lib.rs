@@ -1,12 +1,89 @@
 #![no_std]
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
 use core::{marker, mem, ops};
 
+#[cfg(target_has_atomic = "64")]
+mod atomic;
+mod bitvec;
+mod display;
+mod grid;
+#[cfg(feature = "serde")]
+mod serialize;
+mod words;
+
+#[cfg(target_has_atomic = "64")]
+pub use atomic::AtomicBitSet;
+pub use bitvec::BitVec;
+pub use display::Display;
+pub use grid::{BitGrid, MAX_WIDTH};
+
 const BITS: usize = mem::size_of::<u128>() * 8;
 
 #[derive(Debug)]
 pub struct Error;
 
+/// Maps the elements of a [`BitSet`] to bit indices.
+///
+/// Implemented by every `Fn(&T) -> usize` closure.
+pub trait Key<T> {
+    fn to_index(&self, value: &T) -> usize;
+}
+
+impl<T, F: Fn(&T) -> usize> Key<T> for F {
+    fn to_index(&self, value: &T) -> usize {
+        self(value)
+    }
+}
+
+/// A [`Key`] that can map bit indices back to the elements, see [`BitSet::iter`].
+pub trait BitIndex<T>: Key<T> {
+    // the inverse of `Key::to_index`, it needs the key parameters too
+    #[allow(clippy::wrong_self_convention)]
+    fn from_index(&self, index: usize) -> T;
+}
+
+/// Row-major index of the grid points `(r, c)`, with rows of `width` elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct GridIndex {
+    width: usize,
+}
+
+impl GridIndex {
+    pub const fn new(width: usize) -> Self {
+        debug_assert!(width > 0, "invalid width");
+
+        Self { width }
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+}
+
+macro_rules! impl_grid_index {
+    ($($t:ty),*) => {
+        $(
+            impl Key<($t, $t)> for GridIndex {
+                fn to_index(&self, &(r, c): &($t, $t)) -> usize {
+                    r as usize * self.width + c as usize
+                }
+            }
+
+            impl BitIndex<($t, $t)> for GridIndex {
+                #[allow(clippy::cast_possible_truncation)]
+                fn from_index(&self, index: usize) -> ($t, $t) {
+                    ((index / self.width) as $t, (index % self.width) as $t)
+                }
+            }
+        )*
+    };
+}
+
+impl_grid_index!(u8, u16, u32, usize);
+
 // Cannot be copy: this structure can be too heavy
 #[derive(Clone)]
 pub struct BitSet<T, K, const SIZE: usize> {
@@ -19,25 +96,57 @@ impl BitSet<(), fn(&()) -> usize, 0> {
     pub const fn with_capacity(size: usize) -> usize {
         debug_assert!(size > 0, "invalid size");
         
-        size / BITS + if size % BITS == 0 { 0 } else { 1 }
+        size.div_ceil(BITS)
     }
 }
 
 impl<T, K, const SIZE: usize> BitSet<T, K, SIZE> {
     pub fn len(&self) -> usize {
-        self.data
-            .iter()
-            .map(|value| value.count_ones() as usize)
-            .sum()
+        words::len(&self.data)
     }
 
     pub fn is_empty(&self) -> bool {
-        self.data.iter().all(|&value| value == 0)
+        words::is_empty(&self.data)
+    }
+
+    pub fn clear(&mut self) {
+        self.data.fill(0);
+    }
+
+    /// Iterates over the keys of the elements in the set, in increasing order.
+    pub fn indices(&self) -> Indices<'_> {
+        words::indices(&self.data)
+    }
+
+    /// The smallest key in the set.
+    pub fn first(&self) -> Option<usize> {
+        self.indices().next()
+    }
+
+    /// The smallest key in the set greater than `idx`.
+    pub fn next_after(&self, idx: usize) -> Option<usize> {
+        words::next_after(&self.data, idx)
+    }
+
+    /// The number of elements in the union of the sets, without building it.
+    pub fn union_count(&self, other: &Self) -> usize {
+        words::union_count(&self.data, &other.data)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        words::is_subset(&self.data, &other.data)
     }
 }
 
 impl<T, K: Fn(&T) -> usize, const SIZE: usize> BitSet<T, K, SIZE> {
     pub const fn new(key: K) -> Self {
+        Self::from_key(key)
+    }
+}
+
+impl<T, K: Key<T>, const SIZE: usize> BitSet<T, K, SIZE> {
+    /// Like [`BitSet::new`], for keys that are not closures, like [`GridIndex`].
+    pub const fn from_key(key: K) -> Self {
         Self {
             data: [0; SIZE],
             key,
@@ -47,56 +156,177 @@ impl<T, K: Fn(&T) -> usize, const SIZE: usize> BitSet<T, K, SIZE> {
 
     #[allow(clippy::needless_pass_by_value)]
     pub fn insert(&mut self, idx: T) -> Result<bool, Error> {
-        let idx = (self.key)(&idx);
-        let (i, b) = (idx / BITS, idx % BITS);
-
-        let data = self.data.get_mut(i).ok_or(Error)?;
-
-        let mask = 1 << b;
-
-        let result = *data & mask != 0;
-
-        *data |= mask;
-
-        Ok(result)
+        words::insert(&mut self.data, self.key.to_index(&idx))
     }
 
     pub fn contains(&self, idx: &T) -> Result<bool, Error> {
-        let idx = (self.key)(idx);
-        let (i, b) = (idx / BITS, idx % BITS);
-
-        Ok(self.data.get(i).ok_or(Error)? & (1 << b) != 0)
+        words::contains(&self.data, self.key.to_index(idx))
     }
 
     pub fn remove(&mut self, idx: &T) -> Result<bool, Error> {
-        let idx = (self.key)(idx);
-        let (i, b) = (idx / BITS, idx % BITS);
-
-        let data = self.data.get_mut(i).ok_or(Error)?;
-        
-        let mask = 1 << b;
-        
-        let result = *data & mask != 0;
-        
-        *data &= !mask;
-
-        Ok(result)
+        words::remove(&mut self.data, self.key.to_index(idx))
     }
 }
 
 impl<T, K, const SIZE: usize> BitSet<T, K, SIZE>
 where
-    K: Fn(&T) -> usize + Clone,
+    K: Key<T> + Clone,
 {
     pub fn key(&self) -> K {
         self.key.clone()
     }
 }
 
-impl<T, K, const SIZE: usize> ops::BitOrAssign for BitSet<T, K, SIZE> {
-    fn bitor_assign(&mut self, other: Self) {
-        for (a, b) in self.data.iter_mut().zip(other.data.iter()) {
-            *a |= b;
+impl<T, K: BitIndex<T>, const SIZE: usize> BitSet<T, K, SIZE> {
+    /// Iterates over the elements in the set, in increasing key order.
+    pub fn iter(&self) -> Iter<'_, T, K> {
+        Iter {
+            indices: self.indices(),
+            key: &self.key,
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+impl<'a, T, K: BitIndex<T>, const SIZE: usize> IntoIterator for &'a BitSet<T, K, SIZE> {
+    type Item = T;
+    type IntoIter = Iter<'a, T, K>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+macro_rules! impl_op {
+    ($op:ident, $op_fn:ident, $op_assign:ident, $op_assign_fn:ident, |$a:ident, $b:ident| $body:expr) => {
+        impl<T, K, const SIZE: usize> ops::$op_assign<&Self> for BitSet<T, K, SIZE> {
+            fn $op_assign_fn(&mut self, other: &Self) {
+                words::apply(&mut self.data, &other.data, |$a, $b| $body);
+            }
+        }
+
+        impl<T, K, const SIZE: usize> ops::$op_assign for BitSet<T, K, SIZE> {
+            fn $op_assign_fn(&mut self, other: Self) {
+                ops::$op_assign::$op_assign_fn(self, &other);
+            }
+        }
+
+        impl<T, K, const SIZE: usize> ops::$op<&Self> for BitSet<T, K, SIZE> {
+            type Output = Self;
+
+            fn $op_fn(mut self, other: &Self) -> Self {
+                ops::$op_assign::$op_assign_fn(&mut self, other);
+                self
+            }
         }
+
+        impl<T, K, const SIZE: usize> ops::$op for BitSet<T, K, SIZE> {
+            type Output = Self;
+
+            fn $op_fn(mut self, other: Self) -> Self {
+                ops::$op_assign::$op_assign_fn(&mut self, &other);
+                self
+            }
+        }
+    };
+}
+
+impl_op!(BitOr, bitor, BitOrAssign, bitor_assign, |a, b| a | b);
+impl_op!(BitAnd, bitand, BitAndAssign, bitand_assign, |a, b| a & b);
+impl_op!(BitXor, bitxor, BitXorAssign, bitxor_assign, |a, b| a ^ b);
+impl_op!(Sub, sub, SubAssign, sub_assign, |a, b| a & !b);
+
+/// Iterator over the keys of a [`BitSet`] or a [`BitVec`], see [`BitSet::indices`].
+pub struct Indices<'a> {
+    data: &'a [u128],
+    word: usize,
+    current: u128,
+}
+
+impl Iterator for Indices<'_> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<usize> {
+        while self.current == 0 {
+            self.word += 1;
+            self.current = *self.data.get(self.word)?;
+        }
+
+        let b = self.current.trailing_zeros() as usize;
+        self.current &= self.current - 1;
+
+        Some(self.word * BITS + b)
+    }
+}
+
+/// Iterator over the elements of a [`BitSet`] or a [`BitVec`], see [`BitSet::iter`].
+pub struct Iter<'a, T, K> {
+    indices: Indices<'a>,
+    key: &'a K,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T, K: BitIndex<T>> Iterator for Iter<'_, T, K> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        self.indices.next().map(|index| self.key.from_index(index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn set<const SIZE: usize>(values: &[usize]) -> BitSet<usize, fn(&usize) -> usize, SIZE> {
+        let mut set = BitSet::new((|i| *i) as fn(&usize) -> usize);
+        for &value in values {
+            set.insert(value).unwrap();
+        }
+        set
+    }
+
+    #[test]
+    fn indices() {
+        let s = set::<3>(&[0, 5, 127, 128, 300, 383]);
+
+        assert!(s.indices().eq([0, 5, 127, 128, 300, 383]));
+        assert_eq!(s.first(), Some(0));
+        assert_eq!(s.next_after(5), Some(127));
+        assert_eq!(s.next_after(127), Some(128));
+        assert_eq!(s.next_after(300), Some(383));
+        assert_eq!(s.next_after(383), None);
+        assert_eq!(set::<3>(&[]).first(), None);
+    }
+
+    #[test]
+    fn ops() {
+        let a = set::<2>(&[1, 2, 130]);
+        let b = set::<2>(&[2, 3, 130, 200]);
+
+        assert!((a.clone() & &b).indices().eq([2, 130]));
+        assert!((a.clone() | &b).indices().eq([1, 2, 3, 130, 200]));
+        assert!((a.clone() ^ &b).indices().eq([1, 3, 200]));
+        assert!((a.clone() - &b).indices().eq([1]));
+        assert_eq!(a.union_count(&b), 5);
+
+        assert!(set::<2>(&[2, 130]).is_subset(&a));
+        assert!(!a.is_subset(&b));
+
+        let mut c = a;
+        c.clear();
+        assert!(c.is_empty());
+    }
+
+    #[test]
+    fn grid_index() {
+        let mut s =
+            BitSet::<_, _, { BitSet::with_capacity(12 * 11) }>::from_key(GridIndex::new(12));
+        for p in [(10u8, 0u8), (0, 11), (3, 4)] {
+            s.insert(p).unwrap();
+        }
+
+        assert!(s.iter().eq([(0, 11), (3, 4), (10, 0)]));
+        assert_eq!(s.indices().next(), Some(11));
     }
 }
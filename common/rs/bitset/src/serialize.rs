@@ -0,0 +1,91 @@
+use core::fmt;
+
+use serde::{
+    de::{self, DeserializeSeed, SeqAccess, Visitor},
+    ser::SerializeSeq,
+    Deserializer, Serialize, Serializer,
+};
+
+use crate::{words, BitSet, Key};
+
+/// Serialized as the sequence of the keys in the set.
+impl<T, K, const SIZE: usize> Serialize for BitSet<T, K, SIZE> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut seq = serializer.serialize_seq(Some(self.len()))?;
+        for index in self.indices() {
+            seq.serialize_element(&index)?;
+        }
+        seq.end()
+    }
+}
+
+impl<T, K: Key<T>, const SIZE: usize> BitSet<T, K, SIZE> {
+    /// Deserializes a set written by its `Serialize` implementation: the key function is not
+    /// serialized, so it is given back here.
+    ///
+    /// # Errors
+    /// Fails if the keys are malformed or do not fit the set.
+    pub fn deserialize_with<'de, D: Deserializer<'de>>(
+        key: K,
+        deserializer: D,
+    ) -> Result<Self, D::Error> {
+        let mut set = Self::from_key(key);
+        Indices(&mut set.data).deserialize(deserializer)?;
+        Ok(set)
+    }
+}
+
+struct Indices<'a>(&'a mut [u128]);
+
+impl<'de> DeserializeSeed<'de> for Indices<'_> {
+    type Value = ();
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<(), D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> Visitor<'de> for Indices<'_> {
+    type Value = ();
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a sequence of keys")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<(), A::Error> {
+        while let Some(index) = seq.next_element()? {
+            words::insert(self.0, index).map_err(|_| de::Error::custom("key out of capacity"))?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{BitSet, GridIndex};
+
+    #[test]
+    fn round_trip() {
+        type Set = BitSet<(usize, usize), GridIndex, { BitSet::with_capacity(10 * 10) }>;
+
+        let mut set = Set::from_key(GridIndex::new(10));
+        set.insert((0, 3)).unwrap();
+        set.insert((9, 9)).unwrap();
+
+        let json = serde_json::to_string(&set).unwrap();
+        assert_eq!(json, "[3,99]");
+
+        let set = Set::deserialize_with(
+            GridIndex::new(10),
+            &mut serde_json::Deserializer::from_str(&json),
+        )
+        .unwrap();
+        assert!(set.iter().eq([(0, 3), (9, 9)]));
+
+        assert!(Set::deserialize_with(
+            GridIndex::new(10),
+            &mut serde_json::Deserializer::from_str("[128]")
+        )
+        .is_err());
+    }
+}
@@ -0,0 +1,91 @@
+//! The operations on the `u128` words shared by [`BitSet`](crate::BitSet) and
+//! [`BitVec`](crate::BitVec).
+
+use crate::{Error, Indices, BITS};
+
+pub fn insert(data: &mut [u128], idx: usize) -> Result<bool, Error> {
+    let (i, b) = (idx / BITS, idx % BITS);
+
+    let data = data.get_mut(i).ok_or(Error)?;
+
+    let mask = 1 << b;
+
+    let result = *data & mask != 0;
+
+    *data |= mask;
+
+    Ok(result)
+}
+
+pub fn contains(data: &[u128], idx: usize) -> Result<bool, Error> {
+    let (i, b) = (idx / BITS, idx % BITS);
+
+    Ok(data.get(i).ok_or(Error)? & (1 << b) != 0)
+}
+
+pub fn remove(data: &mut [u128], idx: usize) -> Result<bool, Error> {
+    let (i, b) = (idx / BITS, idx % BITS);
+
+    let data = data.get_mut(i).ok_or(Error)?;
+
+    let mask = 1 << b;
+
+    let result = *data & mask != 0;
+
+    *data &= !mask;
+
+    Ok(result)
+}
+
+pub fn len(data: &[u128]) -> usize {
+    data.iter().map(|value| value.count_ones() as usize).sum()
+}
+
+pub fn is_empty(data: &[u128]) -> bool {
+    data.iter().all(|&value| value == 0)
+}
+
+pub fn indices(data: &[u128]) -> Indices<'_> {
+    Indices {
+        data,
+        word: 0,
+        current: data.first().copied().unwrap_or(0),
+    }
+}
+
+pub fn next_after(data: &[u128], idx: usize) -> Option<usize> {
+    let idx = idx.checked_add(1)?;
+    let (i, b) = (idx / BITS, idx % BITS);
+
+    let current = data.get(i)? & (u128::MAX << b);
+
+    Indices {
+        data,
+        word: i,
+        current,
+    }
+    .next()
+}
+
+// the words past the end of the shorter slice count as zero
+
+pub fn union_count(a: &[u128], b: &[u128]) -> usize {
+    let (short, long) = if a.len() < b.len() { (a, b) } else { (b, a) };
+
+    short
+        .iter()
+        .zip(long.iter())
+        .map(|(a, b)| (a | b).count_ones() as usize)
+        .sum::<usize>()
+        + len(&long[short.len()..])
+}
+
+pub fn is_subset(a: &[u128], b: &[u128]) -> bool {
+    a.iter().zip(b.iter()).all(|(a, b)| a & !b == 0) && is_empty(a.get(b.len()..).unwrap_or(&[]))
+}
+
+pub fn apply(a: &mut [u128], b: &[u128], op: impl Fn(u128, u128) -> u128) {
+    for (i, a) in a.iter_mut().enumerate() {
+        *a = op(*a, b.get(i).copied().unwrap_or(0));
+    }
+}
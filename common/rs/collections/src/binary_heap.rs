@@ -0,0 +1,90 @@
+use core::{cmp::Ordering, marker::PhantomData};
+
+use alloc::collections::BinaryHeap as AHeap;
+
+/// Ordering of a [`BinaryHeap`].
+pub trait Kind {
+    fn cmp<T: Ord>(a: &T, b: &T) -> Ordering;
+}
+
+/// Min-heap.
+pub enum Min {}
+
+/// Max-heap.
+pub enum Max {}
+
+impl Kind for Min {
+    fn cmp<T: Ord>(a: &T, b: &T) -> Ordering {
+        b.cmp(a)
+    }
+}
+
+impl Kind for Max {
+    fn cmp<T: Ord>(a: &T, b: &T) -> Ordering {
+        a.cmp(b)
+    }
+}
+
+struct Item<T, K>(T, PhantomData<K>);
+
+impl<T: Ord, K: Kind> PartialEq for Item<T, K> {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0
+    }
+}
+
+impl<T: Ord, K: Kind> Eq for Item<T, K> {}
+
+impl<T: Ord, K: Kind> PartialOrd for Item<T, K> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T: Ord, K: Kind> Ord for Item<T, K> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        K::cmp(&self.0, &other.0)
+    }
+}
+
+/// Growable counterpart of `heapless::BinaryHeap`.
+pub struct BinaryHeap<T, K, const N: usize>(AHeap<Item<T, K>>);
+
+impl<T, K, const N: usize> BinaryHeap<T, K, N> {
+    pub const fn new() -> Self {
+        Self(AHeap::new())
+    }
+}
+
+impl<T: Ord, K: Kind, const N: usize> BinaryHeap<T, K, N> {
+    pub fn push(&mut self, item: T) -> Result<(), T> {
+        self.0.push(Item(item, PhantomData));
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<T> {
+        self.0.pop().map(|Item(item, _)| item)
+    }
+
+    pub fn peek(&self) -> Option<&T> {
+        self.0.peek().map(|Item(item, _)| item)
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl<T: Ord, K: Kind, const N: usize> Default for BinaryHeap<T, K, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,79 @@
+use alloc::collections::{vec_deque, VecDeque};
+
+/// Growable counterpart of `heapless::Deque`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Deque<T, const N: usize>(VecDeque<T>);
+
+impl<T, const N: usize> Deque<T, N> {
+    pub const fn new() -> Self {
+        Self(VecDeque::new())
+    }
+
+    pub fn push_back(&mut self, item: T) -> Result<(), T> {
+        self.0.push_back(item);
+        Ok(())
+    }
+
+    pub fn push_front(&mut self, item: T) -> Result<(), T> {
+        self.0.push_front(item);
+        Ok(())
+    }
+
+    pub fn pop_back(&mut self) -> Option<T> {
+        self.0.pop_back()
+    }
+
+    pub fn pop_front(&mut self) -> Option<T> {
+        self.0.pop_front()
+    }
+
+    pub fn front(&self) -> Option<&T> {
+        self.0.front()
+    }
+
+    pub fn back(&self) -> Option<&T> {
+        self.0.back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn is_full(&self) -> bool {
+        false
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn iter(&self) -> vec_deque::Iter<'_, T> {
+        self.0.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> vec_deque::IterMut<'_, T> {
+        self.0.iter_mut()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a Deque<T, N> {
+    type Item = &'a T;
+    type IntoIter = vec_deque::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a mut Deque<T, N> {
+    type Item = &'a mut T;
+    type IntoIter = vec_deque::IterMut<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
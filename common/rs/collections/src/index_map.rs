@@ -0,0 +1,185 @@
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasherDefault, Hash},
+    ops::Index,
+};
+
+use fnv::FnvHasher;
+use indexmap::{map, IndexMap};
+
+type Inner<K, V> = IndexMap<K, V, BuildHasherDefault<FnvHasher>>;
+
+/// Growable counterpart of `heapless::FnvIndexMap`.
+#[derive(Clone, Debug)]
+pub struct FnvIndexMap<K, V, const N: usize>(Inner<K, V>);
+
+/// Growable counterpart of `heapless::Entry`.
+pub enum Entry<'a, K, V> {
+    Occupied(OccupiedEntry<'a, K, V>),
+    Vacant(VacantEntry<'a, K, V>),
+}
+
+pub struct OccupiedEntry<'a, K, V>(map::OccupiedEntry<'a, K, V>);
+
+pub struct VacantEntry<'a, K, V>(map::VacantEntry<'a, K, V>);
+
+impl<K, V, const N: usize> FnvIndexMap<K, V, N> {
+    pub const fn new() -> Self {
+        Self(IndexMap::with_hasher(BuildHasherDefault::new()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn keys(&self) -> map::Keys<'_, K, V> {
+        self.0.keys()
+    }
+
+    pub fn values(&self) -> map::Values<'_, K, V> {
+        self.0.values()
+    }
+
+    pub fn values_mut(&mut self) -> map::ValuesMut<'_, K, V> {
+        self.0.values_mut()
+    }
+
+    pub fn iter(&self) -> map::Iter<'_, K, V> {
+        self.0.iter()
+    }
+
+    pub fn iter_mut(&mut self) -> map::IterMut<'_, K, V> {
+        self.0.iter_mut()
+    }
+}
+
+impl<K: Eq + Hash, V, const N: usize> FnvIndexMap<K, V, N> {
+    pub fn insert(&mut self, key: K, value: V) -> Result<Option<V>, (K, V)> {
+        Ok(self.0.insert(key, value))
+    }
+
+    pub fn entry(&mut self, key: K) -> Entry<'_, K, V> {
+        match self.0.entry(key) {
+            map::Entry::Occupied(entry) => Entry::Occupied(OccupiedEntry(entry)),
+            map::Entry::Vacant(entry) => Entry::Vacant(VacantEntry(entry)),
+        }
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.0.get(key)
+    }
+
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.0.get_mut(key)
+    }
+
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.0.contains_key(key)
+    }
+
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.0.swap_remove(key)
+    }
+}
+
+impl<K: Eq + Hash, V, const N: usize> Default for FnvIndexMap<K, V, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K, V, Q, const N: usize> Index<&Q> for FnvIndexMap<K, V, N>
+where
+    K: Eq + Hash + Borrow<Q>,
+    Q: ?Sized + Eq + Hash,
+{
+    type Output = V;
+
+    fn index(&self, key: &Q) -> &V {
+        &self.0[key]
+    }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a FnvIndexMap<K, V, N> {
+    type Item = (&'a K, &'a V);
+    type IntoIter = map::Iter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<'a, K, V, const N: usize> IntoIterator for &'a mut FnvIndexMap<K, V, N> {
+    type Item = (&'a K, &'a mut V);
+    type IntoIter = map::IterMut<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter_mut()
+    }
+}
+
+impl<K: Eq + Hash, V, const N: usize> FromIterator<(K, V)> for FnvIndexMap<K, V, N> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        Self(Inner::from_iter(iter))
+    }
+}
+
+impl<'a, K, V> OccupiedEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        self.0.key()
+    }
+
+    pub fn get(&self) -> &V {
+        self.0.get()
+    }
+
+    pub fn get_mut(&mut self) -> &mut V {
+        self.0.get_mut()
+    }
+
+    pub fn into_mut(self) -> &'a mut V {
+        self.0.into_mut()
+    }
+
+    pub fn insert(&mut self, value: V) -> V {
+        self.0.insert(value)
+    }
+
+    pub fn remove(self) -> V {
+        self.0.swap_remove()
+    }
+}
+
+impl<'a, K, V> VacantEntry<'a, K, V> {
+    pub fn key(&self) -> &K {
+        self.0.key()
+    }
+
+    pub fn insert(self, value: V) -> Result<&'a mut V, V> {
+        Ok(self.0.insert(value))
+    }
+}
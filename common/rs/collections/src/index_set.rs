@@ -0,0 +1,76 @@
+use core::{
+    borrow::Borrow,
+    hash::{BuildHasherDefault, Hash},
+};
+
+use fnv::FnvHasher;
+use indexmap::{set, IndexSet};
+
+/// Growable counterpart of `heapless::FnvIndexSet`.
+#[derive(Clone, Debug)]
+pub struct FnvIndexSet<T, const N: usize>(IndexSet<T, BuildHasherDefault<FnvHasher>>);
+
+impl<T, const N: usize> FnvIndexSet<T, N> {
+    pub const fn new() -> Self {
+        Self(IndexSet::with_hasher(BuildHasherDefault::new()))
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn iter(&self) -> set::Iter<'_, T> {
+        self.0.iter()
+    }
+}
+
+impl<T: Eq + Hash, const N: usize> FnvIndexSet<T, N> {
+    pub fn insert(&mut self, value: T) -> Result<bool, T> {
+        Ok(self.0.insert(value))
+    }
+
+    pub fn contains<Q>(&self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.0.contains(value)
+    }
+
+    pub fn remove<Q>(&mut self, value: &Q) -> bool
+    where
+        T: Borrow<Q>,
+        Q: ?Sized + Eq + Hash,
+    {
+        self.0.swap_remove(value)
+    }
+}
+
+impl<T: Eq + Hash, const N: usize> Default for FnvIndexSet<T, N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a, T, const N: usize> IntoIterator for &'a FnvIndexSet<T, N> {
+    type Item = &'a T;
+    type IntoIter = set::Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.iter()
+    }
+}
+
+impl<T: Eq + Hash, const N: usize> FromIterator<T> for FnvIndexSet<T, N> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self(IndexSet::from_iter(iter))
+    }
+}
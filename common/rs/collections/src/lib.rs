@@ -0,0 +1,38 @@
+#![no_std]
+#![allow(clippy::must_use_candidate)]
+// The API mirrors heapless, including its unit errors.
+#![allow(clippy::result_unit_err, clippy::missing_errors_doc)]
+//! The collections of the day crates.
+//!
+//! By default these are the fixed capacity `heapless` ones. With the `alloc`
+//! feature they are replaced by growable collections with the same API: the
+//! capacity parameters are ignored and the fallible operations never fail.
+
+#[cfg(not(feature = "alloc"))]
+pub use heapless::{binary_heap, BinaryHeap, Deque, Entry, FnvIndexMap, FnvIndexSet, String, Vec};
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+pub mod binary_heap;
+#[cfg(feature = "alloc")]
+mod deque;
+#[cfg(feature = "alloc")]
+mod index_map;
+#[cfg(feature = "alloc")]
+mod index_set;
+#[cfg(feature = "alloc")]
+mod string;
+#[cfg(feature = "alloc")]
+mod vec;
+
+#[cfg(feature = "alloc")]
+pub use {
+    binary_heap::BinaryHeap,
+    deque::Deque,
+    index_map::{Entry, FnvIndexMap, OccupiedEntry, VacantEntry},
+    index_set::FnvIndexSet,
+    string::String,
+    vec::Vec,
+};
@@ -0,0 +1,88 @@
+use core::{fmt, ops::Deref};
+
+use alloc::string::String as AString;
+
+/// Growable counterpart of `heapless::String`.
+#[derive(Clone, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct String<const N: usize>(AString);
+
+impl<const N: usize> String<N> {
+    pub const fn new() -> Self {
+        Self(AString::new())
+    }
+
+    pub fn push(&mut self, c: char) -> Result<(), ()> {
+        self.0.push(c);
+        Ok(())
+    }
+
+    pub fn push_str(&mut self, string: &str) -> Result<(), ()> {
+        self.0.push_str(string);
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<char> {
+        self.0.pop()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const N: usize> Deref for String<N> {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const N: usize> AsRef<str> for String<N> {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl<const N: usize> TryFrom<&str> for String<N> {
+    type Error = ();
+
+    fn try_from(s: &str) -> Result<Self, ()> {
+        Ok(Self(s.into()))
+    }
+}
+
+impl<const N: usize> fmt::Write for String<N> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        self.0.push_str(s);
+        Ok(())
+    }
+}
+
+impl<const N: usize> fmt::Display for String<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.0, f)
+    }
+}
+
+impl<const N: usize> fmt::Debug for String<N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
+
+impl<const N: usize> PartialEq<str> for String<N> {
+    fn eq(&self, other: &str) -> bool {
+        self.0 == other
+    }
+}
+
+impl<const N: usize> PartialEq<&str> for String<N> {
+    fn eq(&self, other: &&str) -> bool {
+        self.0 == *other
+    }
+}
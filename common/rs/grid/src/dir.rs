@@ -0,0 +1,195 @@
+use crate::BoundedPoint;
+
+/// The orthogonal directions, clockwise from up.
+///
+/// A direction is a 2 bit number, see [`Dir4::index`], so it can be packed in
+/// the low bits of a bitset key.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum Dir4 {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Dir4 {
+    pub const ALL: [Self; 4] = [Self::Up, Self::Right, Self::Down, Self::Left];
+
+    /// The bits of [`Dir4::index`].
+    pub const BITS: u32 = 2;
+
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The direction with the 2 low bits of `index`.
+    pub const fn from_index(index: usize) -> Self {
+        Self::ALL[index & 0b11]
+    }
+
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 3)
+    }
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        Self::from_index(self.index() + 2)
+    }
+
+    /// The four directions clockwise from `self`, e.g. the facings a guard tries
+    /// turning right until the way is free.
+    pub fn clockwise(self) -> impl Iterator<Item = Self> {
+        (0..4).map(move |turns| Self::from_index(self.index() + turns))
+    }
+
+    /// (rows x columns)
+    pub const fn delta(self) -> (isize, isize) {
+        match self {
+            Self::Up => (-1, 0),
+            Self::Right => (0, 1),
+            Self::Down => (1, 0),
+            Self::Left => (0, -1),
+        }
+    }
+
+    /// The point next to `point`, if it is inside the same area.
+    pub const fn checked_step(self, point: BoundedPoint) -> Option<BoundedPoint> {
+        checked_step(point, self.delta())
+    }
+}
+
+impl From<Dir4> for Dir8 {
+    fn from(dir: Dir4) -> Self {
+        Self::from_index(dir.index() * 2)
+    }
+}
+
+/// The orthogonal and diagonal directions, clockwise from up.
+///
+/// A direction is a 3 bit number, see [`Dir8::index`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(u8)]
+pub enum Dir8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Dir8 {
+    pub const ALL: [Self; 8] = [
+        Self::Up,
+        Self::UpRight,
+        Self::Right,
+        Self::DownRight,
+        Self::Down,
+        Self::DownLeft,
+        Self::Left,
+        Self::UpLeft,
+    ];
+
+    /// The bits of [`Dir8::index`].
+    pub const BITS: u32 = 3;
+
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// The direction with the 3 low bits of `index`.
+    pub const fn from_index(index: usize) -> Self {
+        Self::ALL[index & 0b111]
+    }
+
+    /// The next direction clockwise, 45 degrees to the right.
+    #[must_use]
+    pub const fn turn_right(self) -> Self {
+        Self::from_index(self.index() + 1)
+    }
+
+    /// The next direction counterclockwise, 45 degrees to the left.
+    #[must_use]
+    pub const fn turn_left(self) -> Self {
+        Self::from_index(self.index() + 7)
+    }
+
+    #[must_use]
+    pub const fn opposite(self) -> Self {
+        Self::from_index(self.index() + 4)
+    }
+
+    /// (rows x columns)
+    pub const fn delta(self) -> (isize, isize) {
+        match self {
+            Self::Up => (-1, 0),
+            Self::UpRight => (-1, 1),
+            Self::Right => (0, 1),
+            Self::DownRight => (1, 1),
+            Self::Down => (1, 0),
+            Self::DownLeft => (1, -1),
+            Self::Left => (0, -1),
+            Self::UpLeft => (-1, -1),
+        }
+    }
+
+    /// See [`Dir4::checked_step`].
+    pub const fn checked_step(self, point: BoundedPoint) -> Option<BoundedPoint> {
+        checked_step(point, self.delta())
+    }
+}
+
+const fn checked_step(point: BoundedPoint, (dr, dc): (isize, isize)) -> Option<BoundedPoint> {
+    let (r, c) = point.point();
+    match (r.checked_add_signed(dr), c.checked_add_signed(dc)) {
+        (Some(r), Some(c)) => BoundedPoint::new((r, c), point.size()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        for dir in Dir4::ALL {
+            assert_eq!(dir.turn_right().turn_left(), dir);
+            assert_eq!(dir.turn_right().turn_right(), dir.opposite());
+            assert_eq!(Dir4::from_index(dir.index()), dir);
+            assert_eq!(Dir8::from(dir).delta(), dir.delta());
+        }
+
+        for dir in Dir8::ALL {
+            assert_eq!(dir.opposite().delta(), (-dir.delta().0, -dir.delta().1));
+            assert_eq!(dir.turn_left().turn_right(), dir);
+        }
+
+        assert!(Dir4::Left
+            .clockwise()
+            .eq([Dir4::Left, Dir4::Up, Dir4::Right, Dir4::Down]));
+        assert_eq!(Dir4::Left.index() << Dir4::BITS, 12);
+    }
+
+    #[test]
+    fn steps() {
+        let point = |point| BoundedPoint::new(point, (2, 3));
+        let step = |dir: Dir8, from| dir.checked_step(point(from).unwrap());
+
+        assert_eq!(Dir4::Up.checked_step(point((0, 1)).unwrap()), None);
+        assert_eq!(step(Dir4::Right.into(), (0, 1)), point((0, 2)));
+        assert_eq!(step(Dir4::Right.into(), (0, 2)), None);
+        assert_eq!(step(Dir8::DownLeft, (0, 1)), point((1, 0)));
+        assert_eq!(step(Dir8::DownLeft, (1, 1)), None);
+        assert_eq!(point((2, 0)), None);
+    }
+}
@@ -0,0 +1,337 @@
+#![no_std]
+#![allow(clippy::must_use_candidate)]
+
+use core::{fmt, ops};
+
+mod dir;
+
+pub use dir::{Dir4, Dir8};
+
+/// Why an input is not a grid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The input has no cells.
+    Empty,
+    /// The first line has no line ending.
+    MissingLineEnding,
+    /// The line, counting from 0, has not the length or the line ending of the first one.
+    Ragged { line: usize },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Empty => write!(f, "empty grid"),
+            Error::MissingLineEnding => write!(f, "missing line ending"),
+            Error::Ragged { line } => write!(f, "ragged grid at line {line}"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+impl From<Error> for solution::Error {
+    fn from(_: Error) -> Self {
+        solution::Error::Parse
+    }
+}
+
+/// (rows x columns)
+pub type Point = (usize, usize);
+
+/// A point inside a `height` x `width` area, so the steps from it can be
+/// checked, see [`Dir4::checked_step`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BoundedPoint {
+    point: Point,
+    size: (usize, usize),
+}
+
+impl BoundedPoint {
+    /// `None` if `point` is not inside the `size` (rows x columns) area.
+    pub const fn new(point: Point, size: (usize, usize)) -> Option<Self> {
+        if point.0 < size.0 && point.1 < size.1 {
+            Some(Self { point, size })
+        } else {
+            None
+        }
+    }
+
+    pub const fn point(&self) -> Point {
+        self.point
+    }
+
+    /// (rows x columns)
+    pub const fn size(&self) -> (usize, usize) {
+        self.size
+    }
+}
+
+/// The width, height and stride of a grid.
+fn shape(data: &[u8]) -> Result<(usize, usize, usize), Error> {
+    let (width, eol): (usize, &[u8]) = match data.iter().position(|&tile| tile == b'\n') {
+        Some(position) if position > 0 && data[position - 1] == b'\r' => (position - 1, b"\r\n"),
+        Some(position) => (position, b"\n"),
+        None if data.is_empty() => return Err(Error::Empty),
+        None => return Err(Error::MissingLineEnding),
+    };
+
+    if width == 0 {
+        return Err(Error::Empty);
+    }
+
+    let stride = width + eol.len();
+    let height = data.len().div_ceil(stride);
+
+    for (line, row) in data.chunks(stride).enumerate() {
+        let (cells, ending) = row.split_at(width.min(row.len()));
+        if cells.len() != width
+            || !(ending == eol || (line + 1 == height && ending.is_empty()))
+            || cells.iter().any(|&tile| tile == b'\n' || tile == b'\r')
+        {
+            return Err(Error::Ragged { line });
+        }
+    }
+
+    Ok((width, height, stride))
+}
+
+/// A zero-copy view of a rectangular map, one byte per cell and one line per row.
+///
+/// The lines end with `\n` or `\r\n`, only the last one may have no line ending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Grid<'a> {
+    data: &'a [u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+impl<'a> Grid<'a> {
+    /// # Errors
+    /// See [`Error`].
+    pub fn new(data: &'a [u8]) -> Result<Self, Error> {
+        let (width, height, stride) = shape(data)?;
+
+        Ok(Self {
+            data,
+            width,
+            height,
+            stride,
+        })
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The distance between two rows in the underlying bytes, the width plus the line ending.
+    pub const fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// The underlying bytes.
+    pub const fn as_bytes(&self) -> &'a [u8] {
+        self.data
+    }
+
+    /// The offset of a cell in the underlying bytes.
+    pub fn index(&self, (r, c): Point) -> Option<usize> {
+        (r < self.height && c < self.width).then_some(r * self.stride + c)
+    }
+
+    pub fn get(&self, r: usize, c: usize) -> Option<u8> {
+        self.index((r, c)).map(|index| self.data[index])
+    }
+
+    /// The cell `(r + dr, c + dc)`, if it is in the grid.
+    pub fn get_offset(&self, (r, c): Point, (dr, dc): (isize, isize)) -> Option<(Point, u8)> {
+        let point = (r.checked_add_signed(dr)?, c.checked_add_signed(dc)?);
+        self.get(point.0, point.1).map(|tile| (point, tile))
+    }
+
+    pub fn row(&self, r: usize) -> Option<&'a [u8]> {
+        (r < self.height).then(|| &self.data[r * self.stride..][..self.width])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &'a [u8]> + use<'a> {
+        let width = self.width;
+        self.data.chunks(self.stride).map(move |row| &row[..width])
+    }
+
+    /// Iterates over the cells, in row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, u8)> + use<'a> {
+        self.rows()
+            .enumerate()
+            .flat_map(|(r, row)| row.iter().enumerate().map(move |(c, &tile)| ((r, c), tile)))
+    }
+
+    /// The position of the first cell with `tile`, in row-major order.
+    pub fn find(&self, tile: u8) -> Option<Point> {
+        self.iter()
+            .find_map(|(point, value)| (value == tile).then_some(point))
+    }
+
+    /// The orthogonal neighbours of a cell that are in the grid, clockwise from the one above.
+    pub fn neighbours(&self, point: Point) -> impl Iterator<Item = (Point, u8)> + use<'a> {
+        let grid = *self;
+        Dir4::ALL
+            .into_iter()
+            .filter_map(move |dir| grid.get_offset(point, dir.delta()))
+    }
+
+    /// The orthogonal and diagonal neighbours of a cell that are in the grid, clockwise from the
+    /// one above.
+    pub fn neighbours8(&self, point: Point) -> impl Iterator<Item = (Point, u8)> + use<'a> {
+        let grid = *self;
+        Dir8::ALL
+            .into_iter()
+            .filter_map(move |dir| grid.get_offset(point, dir.delta()))
+    }
+}
+
+impl ops::Index<Point> for Grid<'_> {
+    type Output = u8;
+
+    fn index(&self, (r, c): Point) -> &u8 {
+        assert!(
+            r < self.height && c < self.width,
+            "({r}, {c}) out of the grid"
+        );
+
+        &self.data[r * self.stride + c]
+    }
+}
+
+/// A [`Grid`] whose cells can be changed, e.g. a map of boxes pushed around.
+#[derive(Debug, PartialEq, Eq)]
+pub struct GridMut<'a> {
+    data: &'a mut [u8],
+    width: usize,
+    height: usize,
+    stride: usize,
+}
+
+impl<'a> GridMut<'a> {
+    /// # Errors
+    /// See [`Error`].
+    pub fn new(data: &'a mut [u8]) -> Result<Self, Error> {
+        let (width, height, stride) = shape(data)?;
+
+        Ok(Self {
+            data,
+            width,
+            height,
+            stride,
+        })
+    }
+
+    /// A read-only view of the cells.
+    pub fn as_grid(&self) -> Grid<'_> {
+        Grid {
+            data: self.data,
+            width: self.width,
+            height: self.height,
+            stride: self.stride,
+        }
+    }
+
+    pub const fn width(&self) -> usize {
+        self.width
+    }
+
+    pub const fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get_mut(&mut self, r: usize, c: usize) -> Option<&mut u8> {
+        (r < self.height && c < self.width).then(|| &mut self.data[r * self.stride + c])
+    }
+}
+
+impl ops::Index<Point> for GridMut<'_> {
+    type Output = u8;
+
+    fn index(&self, (r, c): Point) -> &u8 {
+        assert!(
+            r < self.height && c < self.width,
+            "({r}, {c}) out of the grid"
+        );
+
+        &self.data[r * self.stride + c]
+    }
+}
+
+impl ops::IndexMut<Point> for GridMut<'_> {
+    fn index_mut(&mut self, (r, c): Point) -> &mut u8 {
+        self.get_mut(r, c)
+            .unwrap_or_else(|| panic!("({r}, {c}) out of the grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shape() {
+        for input in ["ab.\n.cd\n", "ab.\n.cd", "ab.\r\n.cd\r\n", "ab.\r\n.cd"] {
+            let grid = Grid::new(input.as_bytes()).unwrap();
+
+            assert_eq!((grid.height(), grid.width()), (2, 3), "{input:?}");
+            assert_eq!(grid.get(1, 2), Some(b'd'));
+            assert_eq!(grid.get(2, 0), None);
+            assert_eq!(grid[(0, 1)], b'b');
+            assert!(grid.rows().eq([b"ab.", b".cd"]));
+        }
+
+        assert_eq!(Grid::new(b"abc\n").map(|grid| grid.height()), Ok(1));
+    }
+
+    #[test]
+    fn invalid() {
+        assert_eq!(Grid::new(b""), Err(Error::Empty));
+        assert_eq!(Grid::new(b"abc"), Err(Error::MissingLineEnding));
+        assert_eq!(Grid::new(b"\nab\n"), Err(Error::Empty));
+        assert_eq!(Grid::new(b"ab\nc\nde\n"), Err(Error::Ragged { line: 1 }));
+        assert_eq!(Grid::new(b"ab\ncd\ne"), Err(Error::Ragged { line: 2 }));
+        assert_eq!(Grid::new(b"ab\r\ncd\nef"), Err(Error::Ragged { line: 1 }));
+        assert_eq!(Grid::new(b"ab\ncd\n\n"), Err(Error::Ragged { line: 2 }));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = Grid::new(b"S..\n.#.\n..E\n").unwrap();
+
+        assert_eq!(grid.find(b'S'), Some((0, 0)));
+        assert_eq!(grid.find(b'E'), Some((2, 2)));
+        assert_eq!(grid.find(b'x'), None);
+
+        assert!(grid.neighbours((0, 0)).eq([((0, 1), b'.'), ((1, 0), b'.')]));
+        assert_eq!(grid.neighbours((1, 1)).count(), 4);
+        assert_eq!(grid.neighbours8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbours8((2, 2)).count(), 3);
+    }
+
+    #[test]
+    fn mutable() {
+        let mut data = *b"ab\r\ncd";
+        let mut grid = GridMut::new(&mut data).unwrap();
+
+        grid[(1, 0)] = b'x';
+        *grid.get_mut(0, 1).unwrap() = b'y';
+        assert_eq!(grid.get_mut(0, 2), None);
+        assert_eq!(grid[(1, 1)], b'd');
+        assert_eq!(grid.as_grid().find(b'x'), Some((1, 0)));
+
+        assert_eq!(&data, b"ay\r\nxd");
+        assert_eq!(
+            GridMut::new(&mut [b'a', b'\n', b'\n']),
+            Err(Error::Ragged { line: 1 })
+        );
+    }
+}
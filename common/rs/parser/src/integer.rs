@@ -0,0 +1,107 @@
+mod sealed {
+    pub trait Sealed {}
+}
+
+/// The primitive integers a [`Scanner`](crate::Scanner) can parse.
+pub trait Integer: Copy + sealed::Sealed {
+    /// Whether the integer can be prefixed by a sign.
+    const SIGNED: bool;
+
+    const ZERO: Self;
+
+    /// `self * scale + digits`, or minus `digits` for a negative integer, if it
+    /// fits.
+    #[doc(hidden)]
+    fn push_digits(self, digits: u32, scale: u32, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($signed:literal: $($t:ty),+) => {
+        $(
+            impl sealed::Sealed for $t {}
+
+            impl Integer for $t {
+                const SIGNED: bool = $signed;
+
+                const ZERO: Self = 0;
+
+                #[inline]
+                fn push_digits(self, digits: u32, scale: u32, negative: bool) -> Option<Self> {
+                    // leading zeros must not overflow the small types
+                    let value = if self == 0 {
+                        0
+                    } else {
+                        self.checked_mul(<$t>::try_from(scale).ok()?)?
+                    };
+                    let digits = <$t>::try_from(digits).ok()?;
+
+                    if negative {
+                        value.checked_sub(digits)
+                    } else {
+                        value.checked_add(digits)
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_integer!(false: u8, u16, u32, u64, u128, usize);
+impl_integer!(true: i8, i16, i32, i64, i128, isize);
+
+/// The value of 8 ASCII digits, if they are all digits.
+///
+/// The digits are checked and combined as a single `u64`, a lane at a time
+/// would not be faster for the short numbers of the puzzles.
+#[inline]
+#[allow(clippy::cast_possible_truncation)]
+pub(crate) fn eight_digits(chunk: [u8; 8]) -> Option<u32> {
+    const ONES: u64 = 0x0101_0101_0101_0101;
+
+    let value = u64::from_le_bytes(chunk).wrapping_sub(0x30 * ONES);
+
+    // a byte below `0` sets its high bit, a byte above `9` too after adding 0x76
+    if (value | value.wrapping_add(0x76 * ONES)) & (0x80 * ONES) != 0 {
+        return None;
+    }
+
+    // pairs, then quadruples, then the whole number, the first digit is the
+    // lowest byte
+    let value = (value * 10 + (value >> 8)) & 0x00ff_00ff_00ff_00ff;
+    let value = (value * 100 + (value >> 16)) & 0x0000_ffff_0000_ffff;
+    let value = (value * 10_000 + (value >> 32)) & 0xffff_ffff;
+
+    Some(value as u32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn chunks() {
+        assert_eq!(eight_digits(*b"12345678"), Some(12_345_678));
+        assert_eq!(eight_digits(*b"00000000"), Some(0));
+        assert_eq!(eight_digits(*b"99999999"), Some(99_999_999));
+        assert_eq!(eight_digits(*b"00000012"), Some(12));
+        for invalid in [
+            b"1234567 ",
+            b"/2345678",
+            b":2345678",
+            b"1234,678",
+            b"\xb02345678",
+        ] {
+            assert_eq!(eight_digits(*invalid), None, "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn push_digits() {
+        assert_eq!(0u8.push_digits(12, 100_000_000, false), Some(12));
+        assert_eq!(1u8.push_digits(0, 100_000_000, false), None);
+        assert_eq!(25u8.push_digits(5, 10, false), Some(255));
+        assert_eq!(25u8.push_digits(6, 10, false), None);
+        assert_eq!((-12i8).push_digits(8, 10, true), Some(-128));
+        assert_eq!((-12i8).push_digits(9, 10, true), None);
+    }
+}
@@ -0,0 +1,446 @@
+#![no_std]
+#![allow(clippy::must_use_candidate)]
+//! Parsers for the numbers and the tokens of the puzzle inputs.
+//!
+//! A [`Scanner`] is a cursor over the input: it parses integers, skips tags
+//! like `Button A: X+` and reports where the input does not match, by line and
+//! column of the whole input.
+
+use core::{fmt, marker};
+
+mod integer;
+
+pub use integer::Integer;
+
+/// What the input does not match.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// A digit was expected.
+    Digit,
+    /// The integer does not fit its type.
+    Overflow,
+    /// The tag was expected.
+    Tag(&'static str),
+    /// The end of the line was expected.
+    Trailing,
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ErrorKind::Digit => write!(f, "expected a digit"),
+            ErrorKind::Overflow => write!(f, "integer overflow"),
+            ErrorKind::Tag(tag) => write!(f, "expected {tag:?}"),
+            ErrorKind::Trailing => write!(f, "trailing characters"),
+        }
+    }
+}
+
+/// Where and why the input cannot be parsed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Error {
+    pub kind: ErrorKind,
+    /// The line, counting from 1.
+    pub line: usize,
+    /// The byte in the line, counting from 1.
+    pub column: usize,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}:{}: {}", self.line, self.column, self.kind)
+    }
+}
+
+impl core::error::Error for Error {}
+
+impl From<Error> for solution::Error {
+    fn from(err: Error) -> Self {
+        solution::Error::ParseAt {
+            line: err.line,
+            column: err.column,
+        }
+    }
+}
+
+/// A cursor over a line or a whole input.
+#[derive(Debug, Clone)]
+pub struct Scanner<'a> {
+    // the whole input, for the position of the errors
+    data: &'a [u8],
+    position: usize,
+    end: usize,
+}
+
+impl<'a> Scanner<'a> {
+    pub const fn new(input: &'a str) -> Self {
+        Self::from_bytes(input.as_bytes())
+    }
+
+    pub const fn from_bytes(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            position: 0,
+            end: data.len(),
+        }
+    }
+
+    /// The offset of the cursor in the input.
+    pub const fn position(&self) -> usize {
+        self.position
+    }
+
+    /// The bytes after the cursor.
+    pub fn rest(&self) -> &'a [u8] {
+        &self.data[self.position..self.end]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.position == self.end
+    }
+
+    /// An error at the cursor.
+    pub fn error(&self, kind: ErrorKind) -> Error {
+        self.error_at(self.position, kind)
+    }
+
+    // only on errors, not worth a dependency
+    #[allow(clippy::naive_bytecount)]
+    fn error_at(&self, position: usize, kind: ErrorKind) -> Error {
+        let before = &self.data[..position];
+        let line_start = before
+            .iter()
+            .rposition(|&b| b == b'\n')
+            .map_or(0, |i| i + 1);
+
+        Error {
+            kind,
+            line: before.iter().filter(|&&b| b == b'\n').count() + 1,
+            column: position - line_start + 1,
+        }
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        self.rest().first().copied()
+    }
+
+    /// Consumes a byte.
+    pub fn bump(&mut self) -> Option<u8> {
+        let b = self.peek()?;
+        self.position += 1;
+        Some(b)
+    }
+
+    /// Consumes `tag`, if the input starts with it.
+    pub fn eat(&mut self, tag: &str) -> bool {
+        let found = self.rest().starts_with(tag.as_bytes());
+        if found {
+            self.position += tag.len();
+        }
+        found
+    }
+
+    /// # Errors
+    /// [`ErrorKind::Tag`] if the input does not start with `tag`.
+    pub fn tag(&mut self, tag: &'static str) -> Result<(), Error> {
+        if self.eat(tag) {
+            Ok(())
+        } else {
+            Err(self.error(ErrorKind::Tag(tag)))
+        }
+    }
+
+    pub fn skip_while(&mut self, mut predicate: impl FnMut(u8) -> bool) {
+        while self.peek().is_some_and(&mut predicate) {
+            self.position += 1;
+        }
+    }
+
+    /// Skips the spaces and the tabs, not the line endings.
+    pub fn skip_spaces(&mut self) {
+        self.skip_while(|b| b == b' ' || b == b'\t');
+    }
+
+    /// # Errors
+    /// [`ErrorKind::Trailing`] if the input is not over.
+    pub fn end(&self) -> Result<(), Error> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error(ErrorKind::Trailing))
+        }
+    }
+
+    /// Parses the digits of an integer, without a sign.
+    ///
+    /// # Errors
+    /// [`ErrorKind::Digit`] if there are no digits and [`ErrorKind::Overflow`]
+    /// if the integer does not fit `T`.
+    pub fn unsigned<T: Integer>(&mut self) -> Result<T, Error> {
+        self.digits(false)
+    }
+
+    /// Parses an integer, with a leading `-` or `+` if `T` is signed.
+    ///
+    /// # Errors
+    /// See [`Scanner::unsigned`].
+    pub fn integer<T: Integer>(&mut self) -> Result<T, Error> {
+        let negative = T::SIGNED && self.eat("-");
+        if T::SIGNED && !negative {
+            self.eat("+");
+        }
+
+        self.digits(negative)
+    }
+
+    fn digits<T: Integer>(&mut self, negative: bool) -> Result<T, Error> {
+        let start = self.position;
+        let overflow = |scanner: &Self| scanner.error_at(start, ErrorKind::Overflow);
+
+        let mut value = T::ZERO;
+        while let Some(digits) = self
+            .rest()
+            .first_chunk::<8>()
+            .and_then(|&chunk| integer::eight_digits(chunk))
+        {
+            value = value
+                .push_digits(digits, 100_000_000, negative)
+                .ok_or_else(|| overflow(self))?;
+            self.position += 8;
+        }
+
+        while let Some(digit @ b'0'..=b'9') = self.peek() {
+            value = value
+                .push_digits(u32::from(digit - b'0'), 10, negative)
+                .ok_or_else(|| overflow(self))?;
+            self.position += 1;
+        }
+
+        if self.position == start {
+            return Err(self.error(ErrorKind::Digit));
+        }
+
+        Ok(value)
+    }
+
+    /// Parses `key` and `N` integers split by `separator`, like `p=0,4` or
+    /// `Button A: X+94, Y+34` with the key `Button A: X+` and the separator
+    /// `, Y+`.
+    ///
+    /// # Errors
+    /// See [`Scanner::tag`] and [`Scanner::integer`].
+    pub fn key_values<T: Integer, const N: usize>(
+        &mut self,
+        key: &'static str,
+        separator: &'static str,
+    ) -> Result<[T; N], Error> {
+        self.tag(key)?;
+
+        let mut values = [T::ZERO; N];
+        for (i, value) in values.iter_mut().enumerate() {
+            if i > 0 {
+                self.tag(separator)?;
+            }
+            *value = self.integer()?;
+        }
+
+        Ok(values)
+    }
+
+    /// Splits the rest of the input in lines, without the line endings.
+    ///
+    /// The errors of the scanners of the lines are still reported by their
+    /// position in the whole input.
+    pub fn lines(self) -> impl Iterator<Item = Scanner<'a>> {
+        let Self {
+            data,
+            mut position,
+            end,
+        } = self;
+
+        core::iter::from_fn(move || {
+            if position == end {
+                return None;
+            }
+
+            let line_end = data[position..end]
+                .iter()
+                .position(|&b| b == b'\n')
+                .map_or(end, |i| position + i);
+            let line = Scanner {
+                data,
+                position,
+                end: if line_end > position && data[line_end - 1] == b'\r' {
+                    line_end - 1
+                } else {
+                    line_end
+                },
+            };
+            position = (line_end + 1).min(end);
+
+            Some(line)
+        })
+    }
+
+    /// Iterates over the integers in the rest of the input, skipping
+    /// everything else, see [`integers`].
+    pub fn integers<T: Integer>(self) -> Integers<'a, T> {
+        Integers {
+            scanner: self,
+            _marker: marker::PhantomData,
+        }
+    }
+}
+
+/// Iterates over the integers in `input`, skipping everything else, e.g. the
+/// four integers of `p=0,4 v=3,-3`.
+///
+/// A `-` or a `+` before the digits is a sign only if `T` is signed.
+pub fn integers<T: Integer>(input: &str) -> Integers<'_, T> {
+    Scanner::new(input).integers()
+}
+
+/// See [`integers`].
+pub struct Integers<'a, T> {
+    scanner: Scanner<'a>,
+    _marker: marker::PhantomData<T>,
+}
+
+impl<T: Integer> Iterator for Integers<'_, T> {
+    type Item = Result<T, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let rest = self.scanner.rest();
+        let start = rest.iter().enumerate().position(|(i, &b)| {
+            b.is_ascii_digit()
+                || (T::SIGNED
+                    && (b == b'-' || b == b'+')
+                    && rest.get(i + 1).is_some_and(u8::is_ascii_digit))
+        })?;
+        self.scanner.position += start;
+
+        let result = self.scanner.integer();
+        if result.is_err() {
+            // the rest of the number, e.g. after an overflow
+            self.scanner.skip_while(|b| b.is_ascii_digit());
+        }
+
+        Some(result)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::{string::ToString, vec::Vec};
+
+    use super::*;
+
+    #[test]
+    fn integers() {
+        let mut s = Scanner::new("123456789012 -17 +3 x");
+        assert_eq!(s.unsigned::<u64>(), Ok(123_456_789_012));
+        s.skip_spaces();
+        assert_eq!(s.integer::<i8>(), Ok(-17));
+        s.skip_spaces();
+        assert_eq!(
+            s.integer::<u8>().map_err(|err| err.kind),
+            Err(ErrorKind::Digit)
+        );
+        assert_eq!(s.integer::<i32>(), Ok(3));
+        s.skip_spaces();
+        assert_eq!(
+            s.unsigned::<u32>(),
+            Err(Error {
+                kind: ErrorKind::Digit,
+                line: 1,
+                column: 21
+            })
+        );
+
+        assert_eq!(Scanner::new("-128").integer::<i8>(), Ok(i8::MIN));
+        assert_eq!(Scanner::new("000000000000255").unsigned::<u8>(), Ok(255));
+        assert_eq!(
+            Scanner::new("256").unsigned::<u8>().map_err(|err| err.kind),
+            Err(ErrorKind::Overflow)
+        );
+        assert_eq!(
+            Scanner::new("18446744073709551616")
+                .unsigned::<u64>()
+                .map_err(|err| err.kind),
+            Err(ErrorKind::Overflow)
+        );
+        assert_eq!(
+            Scanner::new("18446744073709551615").unsigned::<u64>(),
+            Ok(u64::MAX)
+        );
+    }
+
+    #[test]
+    fn all_integers() {
+        assert_eq!(
+            super::integers::<i32>("p=0,4 v=3,-3 - 5-").collect::<Result<Vec<_>, _>>(),
+            Ok([0, 4, 3, -3, 5].into())
+        );
+        assert_eq!(
+            super::integers::<u32>("p=0,4 v=3,-3").collect::<Result<Vec<_>, _>>(),
+            Ok([0, 4, 3, 3].into())
+        );
+    }
+
+    #[test]
+    fn integers_after_overflow() {
+        let overflow = |column| Error {
+            kind: ErrorKind::Overflow,
+            line: 1,
+            column,
+        };
+
+        assert_eq!(
+            super::integers::<u8>("256 7").collect::<Vec<_>>(),
+            [Err(overflow(1)), Ok(7)]
+        );
+        assert_eq!(
+            super::integers::<u8>("123456789").collect::<Vec<_>>(),
+            [Err(overflow(1))]
+        );
+        assert_eq!(
+            super::integers::<i8>("1 -1234567890,2").collect::<Vec<_>>(),
+            [Ok(1), Err(overflow(4)), Ok(2)]
+        );
+    }
+
+    #[test]
+    fn into_solution_error() {
+        let mut line = Scanner::new("1\n2x").lines().nth(1).unwrap();
+        assert_eq!(line.unsigned::<u32>(), Ok(2));
+        assert_eq!(
+            line.end().map_err(solution::Error::from),
+            Err(solution::Error::ParseAt { line: 2, column: 2 })
+        );
+    }
+
+    #[test]
+    fn tags() {
+        let mut lines = Scanner::new("Button A: X+94, Y+34\r\nPrize: X=8400, Y=5400\n").lines();
+
+        let mut button = lines.next().unwrap();
+        assert_eq!(button.key_values("Button A: X+", ", Y+"), Ok([94u32, 34]));
+        assert_eq!(button.end(), Ok(()));
+
+        let mut prize = lines.next().unwrap();
+        let err = prize.key_values::<u32, 2>("Prize: X=", ", Y+").unwrap_err();
+        assert_eq!(
+            err,
+            Error {
+                kind: ErrorKind::Tag(", Y+"),
+                line: 2,
+                column: 14
+            }
+        );
+        assert_eq!(err.to_string(), r#"2:14: expected ", Y+""#);
+
+        assert!(lines.next().is_none());
+    }
+}
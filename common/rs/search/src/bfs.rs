@@ -0,0 +1,143 @@
+use collections::{Deque, Vec};
+
+use solution::{Error, OrCapacity};
+
+use crate::Visited;
+
+/// Breadth first search from `start`, returns the first node accepted by
+/// `is_target` and its distance in steps.
+///
+/// With an `is_target` that accepts nothing, the search visits every node
+/// reachable from `start`, and they are left in `visited`.
+///
+/// # Errors
+/// Returns [`Error::Capacity`] if the queue is full or a node does not fit
+/// `visited`.
+pub fn bfs<N, V, I, const QUEUE: usize>(
+    start: N,
+    visited: &mut V,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_target: impl FnMut(&N) -> bool,
+) -> Result<Option<(N, usize)>, Error>
+where
+    N: Clone,
+    V: Visited<N>,
+    I: IntoIterator<Item = N>,
+{
+    visited.visit(start.clone())?;
+
+    let mut queue = Deque::<_, QUEUE>::new();
+    queue.push_back((start, 0)).or_capacity()?;
+
+    while let Some((node, steps)) = queue.pop_front() {
+        if is_target(&node) {
+            return Ok(Some((node, steps)));
+        }
+
+        for next in neighbours(&node) {
+            if visited.visit(next.clone())? {
+                queue.push_back((next, steps + 1)).or_capacity()?;
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Depth first search from `start`, returns the first node accepted by
+/// `is_target`.
+///
+/// The stack only holds the nodes to visit, so it is usually much smaller than
+/// the queue of [`bfs`] would be.
+///
+/// # Errors
+/// Returns [`Error::Capacity`] if the stack is full or a node does not fit
+/// `visited`.
+pub fn dfs<N, V, I, const STACK: usize>(
+    start: N,
+    visited: &mut V,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_target: impl FnMut(&N) -> bool,
+) -> Result<Option<N>, Error>
+where
+    N: Clone,
+    V: Visited<N>,
+    I: IntoIterator<Item = N>,
+{
+    visited.visit(start.clone())?;
+
+    let mut stack = Vec::<_, STACK>::new();
+    stack.push(start).or_capacity()?;
+
+    while let Some(node) = stack.pop() {
+        if is_target(&node) {
+            return Ok(Some(node));
+        }
+
+        for next in neighbours(&node) {
+            if visited.visit(next.clone())? {
+                stack.push(next).or_capacity()?;
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use bitset::{BitGrid, BitSet, GridIndex};
+
+    use super::*;
+
+    const MAZE: &[&[u8]] = &[b"S.#.", b".##.", b"...E", b"#..."];
+
+    fn neighbours(&(r, c): &(usize, usize)) -> impl Iterator<Item = (usize, usize)> + use<> {
+        [(-1, 0), (0, 1), (1, 0), (0, -1)]
+            .into_iter()
+            .filter_map(move |(dr, dc)| {
+                let (r, c) = (r.checked_add_signed(dr)?, c.checked_add_signed(dc)?);
+                matches!(MAZE.get(r)?.get(c)?, b'.' | b'E').then_some((r, c))
+            })
+    }
+
+    fn is_exit(&(r, c): &(usize, usize)) -> bool {
+        MAZE[r][c] == b'E'
+    }
+
+    #[test]
+    fn shortest() {
+        let mut visited = BitSet::<_, _, 1>::from_key(GridIndex::new(4));
+        assert_eq!(
+            bfs::<_, _, _, 8>((0, 0), &mut visited, neighbours, is_exit),
+            Ok(Some(((2, 3), 5)))
+        );
+
+        let mut visited = BitGrid::<4, 4>::new();
+        assert_eq!(
+            dfs::<_, _, _, 8>((0, 0), &mut visited, neighbours, |&p| p == (2, 3)),
+            Ok(Some((2, 3)))
+        );
+    }
+
+    #[test]
+    fn reachable() {
+        let mut visited = BitGrid::<4, 4>::new();
+        assert_eq!(
+            bfs::<_, _, _, 8>((0, 0), &mut visited, neighbours, |_| false),
+            Ok(None)
+        );
+        assert_eq!(visited.len(), 12);
+        assert!(!visited.contains(&(3, 0)).unwrap());
+    }
+
+    #[cfg(not(feature = "alloc"))]
+    #[test]
+    fn capacity() {
+        let mut visited = BitGrid::<4, 4>::new();
+        assert_eq!(
+            bfs::<_, _, _, 1>((0, 0), &mut visited, neighbours, |_| false),
+            Err(Error::Capacity)
+        );
+    }
+}
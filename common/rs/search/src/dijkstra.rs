@@ -0,0 +1,240 @@
+use collections::{binary_heap::Min, BinaryHeap, Vec};
+
+use solution::{Error, OrCapacity};
+
+use crate::{Cost, Distances, Visited};
+
+/// Dijkstra search from `start`, returns the first node accepted by
+/// `is_target` and its distance.
+///
+/// `neighbours` yields the nodes reached by the edges of a node with their
+/// weights. The search stops at the first target, so only the distances up to
+/// it are final in `distances`, which is enough for [`backtrack`].
+///
+/// # Errors
+/// Returns [`Error::Capacity`] if the queue is full or a node does not fit
+/// `distances`.
+pub fn dijkstra<N, C, D, I, const QUEUE: usize>(
+    start: N,
+    distances: &mut D,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut is_target: impl FnMut(&N) -> bool,
+) -> Result<Option<(N, C)>, Error>
+where
+    N: Ord + Clone,
+    C: Cost,
+    D: Distances<N, C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    distances.set(start.clone(), C::ZERO)?;
+
+    let mut queue = BinaryHeap::<_, Min, QUEUE>::new();
+    queue.push((C::ZERO, start)).or_capacity()?;
+
+    while let Some((cost, node)) = queue.pop() {
+        if distances.get(&node).is_some_and(|best| cost > best) {
+            continue;
+        }
+
+        if is_target(&node) {
+            return Ok(Some((node, cost)));
+        }
+
+        for (next, weight) in neighbours(&node) {
+            let next_cost = cost + weight;
+            if distances.get(&next).is_none_or(|best| next_cost < best) {
+                distances.set(next.clone(), next_cost)?;
+                queue.push((next_cost, next)).or_capacity()?;
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// A* search from `start`, like [`dijkstra`] but the nodes are visited in
+/// order of distance plus `heuristic`.
+///
+/// The heuristic must never overestimate the distance to a target and must
+/// not decrease by more than the weight along an edge, e.g. the manhattan
+/// distance on a grid with unit steps.
+///
+/// # Errors
+/// Returns [`Error::Capacity`] if the queue is full or a node does not fit
+/// `distances`.
+pub fn astar<N, C, D, I, const QUEUE: usize>(
+    start: N,
+    distances: &mut D,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_target: impl FnMut(&N) -> bool,
+) -> Result<Option<(N, C)>, Error>
+where
+    N: Ord + Clone,
+    C: Cost,
+    D: Distances<N, C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    distances.set(start.clone(), C::ZERO)?;
+
+    let mut queue = BinaryHeap::<_, Min, QUEUE>::new();
+    queue
+        .push((heuristic(&start), C::ZERO, start))
+        .or_capacity()?;
+
+    while let Some((_, cost, node)) = queue.pop() {
+        if distances.get(&node).is_some_and(|best| cost > best) {
+            continue;
+        }
+
+        if is_target(&node) {
+            return Ok(Some((node, cost)));
+        }
+
+        for (next, weight) in neighbours(&node) {
+            let next_cost = cost + weight;
+            if distances.get(&next).is_none_or(|best| next_cost < best) {
+                distances.set(next.clone(), next_cost)?;
+                queue
+                    .push((next_cost + heuristic(&next), next_cost, next))
+                    .or_capacity()?;
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Visits every node on a shortest path to `targets`, walking back from them
+/// through the `distances` left by [`dijkstra`] or [`astar`].
+///
+/// `predecessors` yields the nodes with an edge to a node, with the weight of
+/// the edge: a predecessor is on a shortest path when its distance plus the
+/// weight is the distance of the node. The weights must be positive.
+///
+/// # Errors
+/// Returns [`Error::Capacity`] if the stack is full or a node does not fit
+/// `visited`.
+pub fn backtrack<N, C, D, V, I, const STACK: usize>(
+    targets: impl IntoIterator<Item = N>,
+    distances: &D,
+    visited: &mut V,
+    mut predecessors: impl FnMut(&N) -> I,
+) -> Result<(), Error>
+where
+    N: Clone,
+    C: Cost,
+    D: Distances<N, C>,
+    V: Visited<N>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut stack = Vec::<_, STACK>::new();
+    for target in targets {
+        if visited.visit(target.clone())? {
+            stack.push(target).or_capacity()?;
+        }
+    }
+
+    while let Some(node) = stack.pop() {
+        let Some(cost) = distances.get(&node) else {
+            continue;
+        };
+
+        for (previous, weight) in predecessors(&node) {
+            if distances
+                .get(&previous)
+                .is_some_and(|previous_cost| previous_cost + weight == cost)
+                && visited.visit(previous.clone())?
+            {
+                stack.push(previous).or_capacity()?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use bitset::BitGrid;
+
+    use collections::FnvIndexMap;
+
+    use super::*;
+
+    // two shortest paths from 0 to 3, through 1 and through 2
+    const EDGES: [(u8, u8, u32); 6] = [
+        (0, 1, 1),
+        (1, 3, 1),
+        (0, 2, 1),
+        (2, 3, 1),
+        (3, 4, 3),
+        (2, 4, 1),
+    ];
+
+    #[allow(clippy::trivially_copy_pass_by_ref)]
+    fn neighbours(&node: &u8) -> impl Iterator<Item = (u8, u32)> + use<> {
+        EDGES.into_iter().filter_map(move |(a, b, w)| match node {
+            n if n == a => Some((b, w)),
+            n if n == b => Some((a, w)),
+            _ => None,
+        })
+    }
+
+    #[test]
+    fn shortest() {
+        let mut distances = FnvIndexMap::<_, _, 8>::new();
+        assert_eq!(
+            dijkstra::<_, _, _, _, 8>(0, &mut distances, neighbours, |&n| n == 4),
+            Ok(Some((4, 2)))
+        );
+        assert_eq!(distances.get(&3), Some(&2));
+
+        let mut distances = FnvIndexMap::<_, _, 8>::new();
+        assert_eq!(
+            astar::<_, _, _, _, 8>(
+                0,
+                &mut distances,
+                neighbours,
+                |&n| u32::from(n != 3),
+                |&n| n == 3
+            ),
+            Ok(Some((3, 2)))
+        );
+
+        let mut distances = FnvIndexMap::<_, _, 8>::new();
+        assert_eq!(
+            dijkstra::<_, _, _, _, 8>(0, &mut distances, neighbours, |&n| n == 5),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn all_shortest_paths() {
+        let grid = ["S.#.", "....", "#..E"];
+        let open =
+            |r: usize, c: usize| grid.get(r).and_then(|row| row.as_bytes().get(c)) != Some(&b'#');
+        let steps = move |&(r, c): &(usize, usize)| {
+            [(-1, 0), (0, 1), (1, 0), (0, -1)]
+                .into_iter()
+                .filter_map(move |(dr, dc)| {
+                    let (r, c) = (r.checked_add_signed(dr)?, c.checked_add_signed(dc)?);
+                    (r < 3 && c < 4 && open(r, c)).then_some(((r, c), 1u32))
+                })
+        };
+
+        let mut distances = FnvIndexMap::<_, _, 16>::new();
+        let (end, cost) =
+            dijkstra::<_, _, _, _, 16>((0, 0), &mut distances, steps, |&p| p == (2, 3))
+                .unwrap()
+                .unwrap();
+        assert_eq!(cost, 5);
+
+        let mut visited = BitGrid::<4, 3>::new();
+        backtrack::<_, _, _, _, _, 16>([end], &distances, &mut visited, steps).unwrap();
+        // every open tile but the dead end (0, 3) is on a shortest path
+        assert_eq!(visited.len(), 9);
+        assert!(!visited.contains(&(0, 3)).unwrap());
+        assert!(visited.contains(&(0, 0)).unwrap());
+    }
+}
@@ -0,0 +1,172 @@
+#![no_std]
+#![allow(clippy::must_use_candidate)]
+//! Graph searches over fixed capacity collections.
+//!
+//! The graph is never built: the searches are given a start node and a function
+//! returning the neighbours of a node, and keep their state in a [`Visited`] set
+//! or a [`Distances`] table chosen by the caller, e.g. a `BitSet` keyed by the
+//! grid position. The queues are `heapless` ones, sized by the `QUEUE` and
+//! `STACK` parameters, and a full queue is an [`Error::Capacity`].
+
+use core::{hash::Hash, ops};
+
+use bitset::{BitGrid, BitSet, BitVec, Key};
+
+use collections::{FnvIndexMap, FnvIndexSet};
+
+use solution::{Error, OrCapacity};
+
+mod bfs;
+mod dijkstra;
+
+pub use bfs::{bfs, dfs};
+pub use dijkstra::{astar, backtrack, dijkstra};
+
+/// The nodes already reached by a search.
+pub trait Visited<N> {
+    /// Marks `node` as visited, returning whether it was not visited before.
+    ///
+    /// # Errors
+    /// Returns [`Error::Capacity`] if `node` does not fit the set.
+    fn visit(&mut self, node: N) -> Result<bool, Error>;
+}
+
+impl<N, K: Key<N>, const SIZE: usize> Visited<N> for BitSet<N, K, SIZE> {
+    fn visit(&mut self, node: N) -> Result<bool, Error> {
+        self.insert(node).map(|seen| !seen).or_capacity()
+    }
+}
+
+impl<D, N, K> Visited<N> for BitVec<D, N, K>
+where
+    D: AsRef<[u128]> + AsMut<[u128]>,
+    K: Key<N>,
+{
+    fn visit(&mut self, node: N) -> Result<bool, Error> {
+        self.insert(node).map(|seen| !seen).or_capacity()
+    }
+}
+
+impl<const W: usize, const H: usize> Visited<(usize, usize)> for BitGrid<W, H> {
+    fn visit(&mut self, node: (usize, usize)) -> Result<bool, Error> {
+        self.insert(node).map(|seen| !seen).or_capacity()
+    }
+}
+
+impl<N: Eq + Hash, const SIZE: usize> Visited<N> for FnvIndexSet<N, SIZE> {
+    fn visit(&mut self, node: N) -> Result<bool, Error> {
+        self.insert(node).or_capacity()
+    }
+}
+
+/// The weight of an edge and the length of a path.
+pub trait Cost: Copy + Ord + ops::Add<Output = Self> {
+    const ZERO: Self;
+    const MAX: Self;
+}
+
+macro_rules! impl_cost {
+    ($($t:ty),+) => {
+        $(
+            impl Cost for $t {
+                const ZERO: Self = 0;
+                const MAX: Self = <$t>::MAX;
+            }
+        )+
+    };
+}
+
+impl_cost!(u8, u16, u32, u64, usize);
+
+/// The best known distance of the nodes reached by a search.
+pub trait Distances<N, C> {
+    fn get(&self, node: &N) -> Option<C>;
+
+    /// # Errors
+    /// Returns [`Error::Capacity`] if `node` does not fit the table.
+    fn set(&mut self, node: N, cost: C) -> Result<(), Error>;
+}
+
+impl<N: Eq + Hash, C: Copy, const SIZE: usize> Distances<N, C> for FnvIndexMap<N, C, SIZE> {
+    fn get(&self, node: &N) -> Option<C> {
+        FnvIndexMap::get(self, node).copied()
+    }
+
+    fn set(&mut self, node: N, cost: C) -> Result<(), Error> {
+        self.insert(node, cost).map(|_| ()).or_capacity()
+    }
+}
+
+/// [`Distances`] stored in the slice `D` at the index given by the key `K`.
+///
+/// The entries at [`Cost::MAX`] are the nodes not reached yet, so `data` is
+/// usually filled with it before the search.
+pub struct Table<D, K> {
+    data: D,
+    key: K,
+}
+
+impl<D, K> Table<D, K> {
+    pub const fn new(data: D, key: K) -> Self {
+        Self { data, key }
+    }
+
+    /// The distances, by key.
+    pub fn into_inner(self) -> D {
+        self.data
+    }
+}
+
+impl<N, C, D, K> Distances<N, C> for Table<D, K>
+where
+    C: Cost,
+    D: AsRef<[C]> + AsMut<[C]>,
+    K: Key<N>,
+{
+    fn get(&self, node: &N) -> Option<C> {
+        self.data
+            .as_ref()
+            .get(self.key.to_index(node))
+            .copied()
+            .filter(|&cost| cost != C::MAX)
+    }
+
+    fn set(&mut self, node: N, cost: C) -> Result<(), Error> {
+        *self
+            .data
+            .as_mut()
+            .get_mut(self.key.to_index(&node))
+            .ok_or(Error::Capacity)? = cost;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn visited() {
+        let mut set = BitSet::<usize, _, 1>::new(|i: &usize| *i);
+        assert_eq!(set.visit(3), Ok(true));
+        assert_eq!(set.visit(3), Ok(false));
+        assert_eq!(set.visit(128), Err(Error::Capacity));
+
+        let mut set = FnvIndexSet::<u8, 2>::new();
+        assert_eq!(set.visit(1), Ok(true));
+        assert_eq!(set.visit(1), Ok(false));
+    }
+
+    #[test]
+    fn table() {
+        let mut data = [u32::MAX; 4];
+        let mut table = Table::new(&mut data[..], |i: &usize| *i);
+
+        assert_eq!(table.get(&1), None);
+        table.set(1, 7).unwrap();
+        assert_eq!(table.get(&1), Some(7));
+        assert_eq!(table.get(&4), None);
+        assert_eq!(table.set(4, 0), Err(Error::Capacity));
+    }
+}
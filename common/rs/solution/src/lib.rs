@@ -0,0 +1,125 @@
+#![no_std]
+
+use core::fmt::{self, Display};
+use core::num::ParseIntError;
+
+/// Why a solver cannot give an answer.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The input is malformed.
+    Parse,
+    /// The input is malformed at a line and a byte of the line, both counting
+    /// from 1.
+    ParseAt { line: usize, column: usize },
+    /// The input does not fit a fixed capacity collection.
+    Capacity,
+    /// The input is well formed but has no answer.
+    Unsolvable,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Parse => write!(f, "parse error"),
+            Error::ParseAt { line, column } => write!(f, "parse error at {line}:{column}"),
+            Error::Capacity => write!(f, "capacity overflow"),
+            Error::Unsolvable => write!(f, "unsolvable input"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+impl From<ParseIntError> for Error {
+    fn from(_: ParseIntError) -> Self {
+        Error::Parse
+    }
+}
+
+/// Maps the error of a full `heapless` collection to [`Error::Capacity`].
+pub trait OrCapacity<T> {
+    /// # Errors
+    /// Returns [`Error::Capacity`] on error.
+    fn or_capacity(self) -> Result<T, Error>;
+}
+
+impl<T, E> OrCapacity<T> for Result<T, E> {
+    fn or_capacity(self) -> Result<T, Error> {
+        self.map_err(|_| Error::Capacity)
+    }
+}
+
+/// The solution of a puzzle day, implemented by every day crate.
+///
+/// Runners can be generic over it instead of wiring the `solve_1`/`solve_2`
+/// free functions of each day by hand.
+pub trait Solution {
+    /// The puzzle day, `1..=25`.
+    const DAY: u32;
+
+    type Output1: Display;
+    type Output2: Display;
+
+    /// # Errors
+    /// See [`Error`].
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error>;
+
+    /// # Errors
+    /// See [`Error`].
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error>;
+
+    /// # Panics
+    /// Panics if [`Solution::try_solve_1`] fails.
+    #[must_use]
+    fn solve_1(input: &str) -> Self::Output1 {
+        Self::try_solve_1(input).unwrap_or_else(|err| panic!("{err}"))
+    }
+
+    /// # Panics
+    /// Panics if [`Solution::try_solve_2`] fails.
+    #[must_use]
+    fn solve_2(input: &str) -> Self::Output2 {
+        Self::try_solve_2(input).unwrap_or_else(|err| panic!("{err}"))
+    }
+}
+
+/// A [`Solution`] that consumes its input a line at a time.
+///
+/// A runner can push the lines as they arrive instead of keeping the whole
+/// input in memory.
+pub trait StreamingSolution: Solution {
+    /// What the solver keeps of the lines pushed so far.
+    type State: Default;
+
+    /// Consumes a line of the input, without the line ending.
+    ///
+    /// # Errors
+    /// See [`Error`].
+    fn push_line(state: &mut Self::State, line: &str) -> Result<(), Error>;
+
+    /// # Errors
+    /// See [`Error`].
+    fn try_finish_1(state: &mut Self::State) -> Result<Self::Output1, Error>;
+
+    /// # Errors
+    /// See [`Error`].
+    fn try_finish_2(state: &mut Self::State) -> Result<Self::Output2, Error>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_int_is_parse() {
+        assert_eq!("x".parse::<u32>().map_err(Error::from), Err(Error::Parse));
+    }
+
+    #[test]
+    fn or_capacity() {
+        let mut v = heapless::Vec::<u32, 1>::new();
+        assert_eq!(v.push(1).or_capacity(), Ok(()));
+        assert_eq!(v.push(2).or_capacity(), Err(Error::Capacity));
+    }
+}
@@ -1,50 +1,41 @@
 use std::marker::PhantomData;
-use std::fmt::Display;
 use std::time::Duration;
 
 use web_sys::HtmlInputElement;
 
 use instant::Instant;
 
+use solution::Solution;
+
 use yew::prelude::*;
 
-#[derive(Properties)]
-pub struct ModelProps<SOLVE1, SOLVE2> {
+#[derive(Properties, PartialEq)]
+pub struct ModelProps {
     pub input: String,
-    pub solve_1: SOLVE1,
-    pub solve_2: SOLVE2,
 }
 
 pub enum Msg {
     Run(String),
 }
 
-pub struct Model<SOLVE1, P1T, SOLVE2, P2T> {
+pub struct Model<S: Solution> {
     input_ref: NodeRef,
-    part1: Option<P1T>,
-    part2: Option<P2T>,
+    part1: Option<S::Output1>,
+    part2: Option<S::Output2>,
     input: String,
     elapsed_part_1: Option<Duration>,
     elapsed_part_2: Option<Duration>,
     elapsed_total: Option<Duration>,
-    _solve1: PhantomData<SOLVE1>,
-    _solve2: PhantomData<SOLVE2>,
-}
-
-impl<SOLVE1, SOLVE2> PartialEq for ModelProps<SOLVE1, SOLVE2> {
-    fn eq(&self, other: &Self) -> bool {
-        self.input.eq(&other.input)
-    }
+    _solution: PhantomData<S>,
 }
 
-impl<SOLVE1, P1T, SOLVE2, P2T> Component for Model<SOLVE1, P1T, SOLVE2, P2T>
-where SOLVE1: Fn(&str) -> P1T + 'static,
-      SOLVE2: Fn(&str) -> P2T + 'static,
-      P1T: Display + Clone + 'static,
-      P2T: Display + Clone + 'static,
+impl<S> Component for Model<S>
+where S: Solution + 'static,
+      S::Output1: Clone,
+      S::Output2: Clone,
 {
     type Message = Msg;
-    type Properties = ModelProps<SOLVE1, SOLVE2>;
+    type Properties = ModelProps;
 
     fn create(ctx: &Context<Self>) -> Self {
         let input = ctx.props().input.clone();
@@ -57,19 +48,18 @@ where SOLVE1: Fn(&str) -> P1T + 'static,
             elapsed_part_1: None,
             elapsed_part_2: None,
             elapsed_total: None,
-            _solve1: PhantomData,
-            _solve2: PhantomData,
+            _solution: PhantomData,
         }
     }
 
-    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+    fn update(&mut self, _ctx: &Context<Self>, msg: Self::Message) -> bool {
         match msg {
             Msg::Run(input) => {
                 let now_part_1 = Instant::now();
-                self.part1 = Some((ctx.props().solve_1)(&input));
+                self.part1 = Some(S::solve_1(&input));
                 self.elapsed_part_1 = Some(now_part_1.elapsed());
                 let now_part_2 = Instant::now();
-                self.part2 = Some((ctx.props().solve_2)(&input));
+                self.part2 = Some(S::solve_2(&input));
                 self.elapsed_part_2 = Some(now_part_2.elapsed());
                 self.elapsed_total = Some(now_part_1.elapsed());
                 self.input = input;
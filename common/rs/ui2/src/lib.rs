@@ -2,10 +2,12 @@
 
 use std::time::Duration;
 use std::marker::PhantomData;
-use std::fmt::Debug;
+use std::fmt::{self, Debug, Display};
 
 use instant::Instant;
 
+use solution::Solution;
+
 use web_sys::HtmlInputElement;
 
 use yew::prelude::*;
@@ -19,6 +21,37 @@ where Self: 'static,
     fn f(input: &str) -> impl Debug;
 }
 
+/// Shows an answer of a [`Solution`] as it is displayed, without the quotes of
+/// [`Debug`].
+struct Answer<T>(T);
+
+impl<T: Display> Debug for Answer<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        Display::fmt(&self.0, f)
+    }
+}
+
+/// Part 1 of a [`Solution`] as a worker [`Function`].
+pub struct Part1<S>(PhantomData<S>);
+
+impl<S: Solution + 'static> Function for Part1<S> {
+    fn f(input: &str) -> impl Debug {
+        Answer(S::solve_1(input))
+    }
+}
+
+/// Part 2 of a [`Solution`] as a worker [`Function`].
+pub struct Part2<S>(PhantomData<S>);
+
+impl<S: Solution + 'static> Function for Part2<S> {
+    fn f(input: &str) -> impl Debug {
+        Answer(S::solve_2(input))
+    }
+}
+
+/// The [`Model`] solving both parts of `S`.
+pub type SolutionModel<S> = Model<Part1<S>, Part2<S>>;
+
 pub struct Solve<F>(PhantomData<F>);
 
 impl<F: Function> Worker for Solve<F> {
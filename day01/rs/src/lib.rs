@@ -1,10 +1,13 @@
 #![no_std]
 #![allow(clippy::must_use_candidate)]
 
-use heapless::{Entry, FnvIndexMap, Vec as HLVec};
+use collections::Vec as HLVec;
 
-type HashMap<K, V> = FnvIndexMap<K, V, 1024>;
-type Vec<T> = HLVec<T, 1024>;
+use parser::Scanner;
+
+use solution::{Error, OrCapacity, StreamingSolution};
+
+type Vec<T, const N: usize> = HLVec<T, N>;
 
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
@@ -12,93 +15,96 @@ pub const INPUT: &str = include_str!("../../input");
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = "";
 
-struct CountHashMap<K, T>(HashMap<K, T>);
+/// Default capacity for the input lines.
+pub const LINES: usize = 1024;
 
-impl<K, V> CountHashMap<K, V> {
-    const fn new() -> Self {
-        Self(HashMap::new())
-    }
+/// Parses `3   4`.
+fn parse(mut line: Scanner) -> Result<(u32, u32), Error> {
+    let v1 = line.unsigned()?;
+    line.skip_spaces();
+    let v2 = line.unsigned()?;
+    line.end()?;
+
+    Ok((v1, v2))
 }
 
-impl<K> CountHashMap<K, u32>
-where K: Eq + core::hash::Hash,
-{
-    fn add(&mut self, value: K) -> Result<(), u32> {
-        match self.0.entry(value) {
-            Entry::Occupied(mut v) => {
-                *v.get_mut() += 1;
-                Ok(())
-            }
-            Entry::Vacant(v) => {
-                v.insert(1)?;
-                Ok(())
-            }
+/// The two lists of the lines pushed so far, see [`StreamingSolution`]: the
+/// distance pairs the sorted lists, so every line is kept.
+#[derive(Default)]
+pub struct Lists<const LINES: usize> {
+    left: Vec<u32, LINES>,
+    right: Vec<u32, LINES>,
+}
+
+impl<const LINES: usize> Lists<LINES> {
+    fn parse(input: &str) -> Result<Self, Error> {
+        let mut lists = Self::default();
+        for line in Scanner::new(input).lines() {
+            lists.push(line)?;
         }
+
+        Ok(lists)
+    }
+
+    fn push(&mut self, line: Scanner) -> Result<(), Error> {
+        let (v1, v2) = parse(line)?;
+        self.left.push(v1).or_capacity()?;
+        self.right.push(v2).or_capacity()
     }
-}
 
-impl<K, T> core::ops::Deref for CountHashMap<K, T> {
-    type Target = HashMap<K, T>;
+    fn distance(&mut self) -> u32 {
+        self.left.sort_unstable();
+        self.right.sort_unstable();
 
-    fn deref(&self) -> &Self::Target {
-        &self.0
+        self.left
+            .iter()
+            .zip(&self.right)
+            .map(|(a, &b)| a.abs_diff(b))
+            .sum()
+    }
+
+    /// The similarity, with a binary search of each id in the sorted right
+    /// list.
+    fn similarity(&mut self) -> u32 {
+        self.right.sort_unstable();
+
+        self.left
+            .iter()
+            .map(|&id| {
+                let start = self.right.partition_point(|&value| value < id);
+                self.right[start..]
+                    .iter()
+                    .take_while(|&&value| value == id)
+                    .sum::<u32>()
+            })
+            .sum()
     }
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> u32 {
-    let mut line1 = Vec::new();
-    let mut line2 = Vec::new();
-    for (v1, v2) in input
-        .lines()
-        .map(|line| {
-            let mut parts = line.split_whitespace();
-
-            (
-                parts.next().unwrap().parse::<u32>().unwrap(),
-                parts.next().unwrap().parse::<u32>().unwrap(),
-            )
-        })
-    {
-        line1.push(v1).unwrap();
-        line2.push(v2).unwrap();
-    }
+pub fn try_solve_1<const LINES: usize>(input: &str) -> Result<u32, Error> {
+    Ok(Lists::<LINES>::parse(input)?.distance())
+}
 
-    line1.sort_unstable();
-    line2.sort_unstable();
+/// # Errors
+/// See [`Error`].
+#[cfg_attr(target_os = "none", inline(never))]
+pub fn try_solve_2<const LINES: usize>(input: &str) -> Result<u32, Error> {
+    Ok(Lists::<LINES>::parse(input)?.similarity())
+}
 
-    line1
-        .iter()
-        .zip(&line2)
-        .map(|(a, &b)| a.abs_diff(b))
-        .sum()
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> u32 {
+    try_solve_1::<LINES>(input).unwrap()
 }
 
 /// # Panics
-#[cfg_attr(target_os = "none", inline(never))]
+/// Panics if [`try_solve_2`] fails.
 pub fn solve_2(input: &str) -> u32 {
-    let mut line1 = Vec::new();
-    let mut line2 = CountHashMap::new();
-    for (v1, v2) in input
-        .lines()
-        .map(|line| {
-            let mut parts = line.split_whitespace();
-
-            (
-                parts.next().unwrap().parse::<u32>().unwrap(),
-                parts.next().unwrap().parse::<u32>().unwrap(),
-            )
-        })
-    {
-        line1.push(v1).unwrap();
-        line2.add(v2).unwrap();
-    }
-
-    line1
-        .iter()
-        .map(|id| id * line2.get(id).copied().unwrap_or_default())
-        .sum()
+    try_solve_2::<LINES>(input).unwrap()
 }
 
 #[cfg(feature = "input")]
@@ -111,6 +117,39 @@ pub fn part_2() -> u32 {
     solve_2(INPUT)
 }
 
+pub struct Day<const LINES: usize = { crate::LINES }>;
+
+impl<const LINES: usize> solution::Solution for Day<LINES> {
+    const DAY: u32 = 1;
+
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<LINES>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<LINES>(input)
+    }
+}
+
+impl<const LINES: usize> StreamingSolution for Day<LINES> {
+    type State = Lists<LINES>;
+
+    fn push_line(state: &mut Self::State, line: &str) -> Result<(), Error> {
+        state.push(Scanner::new(line))
+    }
+
+    fn try_finish_1(state: &mut Self::State) -> Result<Self::Output1, Error> {
+        Ok(state.distance())
+    }
+
+    fn try_finish_2(state: &mut Self::State) -> Result<Self::Output2, Error> {
+        Ok(state.similarity())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -137,4 +176,53 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(INPUT_2), 31);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            try_solve_1::<LINES>("3   x"),
+            Err(Error::ParseAt { line: 1, column: 5 })
+        );
+        assert_eq!(
+            try_solve_2::<LINES>("3"),
+            Err(Error::ParseAt { line: 1, column: 2 })
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "alloc"))]
+    fn capacity_overflow() {
+        let mut input = collections::String::<{ 1025 * 4 }>::new();
+        for _ in 0..1025 {
+            input.push_str("1 2\n").unwrap();
+        }
+
+        assert_eq!(try_solve_1::<LINES>(&input), Err(Error::Capacity));
+    }
+
+    #[test]
+    fn streaming() {
+        let mut lists = Lists::<LINES>::default();
+        for line in INPUT_1.lines() {
+            Day::<LINES>::push_line(&mut lists, line).unwrap();
+        }
+
+        assert_eq!(Day::<LINES>::try_finish_1(&mut lists), Ok(11));
+        assert_eq!(Day::<LINES>::try_finish_2(&mut lists), Ok(31));
+        assert_eq!(
+            Day::<LINES>::push_line(&mut lists, "3   x"),
+            Err(Error::ParseAt { line: 1, column: 5 })
+        );
+    }
+
+    #[test]
+    fn custom_capacity() {
+        assert_eq!(try_solve_1::<8>(INPUT_1), Ok(11));
+        assert_eq!(try_solve_2::<8>(INPUT_2), Ok(31));
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(try_solve_1::<4>(INPUT_1), Err(Error::Capacity));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -3,13 +3,15 @@
 
 use core::cmp::Ordering;
 
-use heapless::Vec;
+use collections::Vec;
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
 use itertools::Itertools;
 
+use solution::{Error, OrCapacity, StreamingSolution};
+
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
 
@@ -24,24 +26,16 @@ trait SafeReport: Iterator<Item = u32> + Sized {
                 (Ordering::Less, Some(direction)) if *direction == Ordering::Less => {
                     (1..=3).contains(&(b - a))
                 }
-                (Ordering::Less, None) => {
-                    if (1..=3).contains(&(b - a)) {
-                        direction = Some(Ordering::Less);
-                        true
-                    } else {
-                        false
-                    }
+                (Ordering::Less, None) if (1..=3).contains(&(b - a)) => {
+                    direction = Some(Ordering::Less);
+                    true
                 }
                 (Ordering::Greater, Some(direction)) if *direction == Ordering::Greater => {
                     (1..=3).contains(&(a - b))
                 }
-                (Ordering::Greater, None) => {
-                    if (1..=3).contains(&(a - b)) {
-                        direction = Some(Ordering::Greater);
-                        true
-                    } else {
-                        false
-                    }
+                (Ordering::Greater, None) if (1..=3).contains(&(a - b)) => {
+                    direction = Some(Ordering::Greater);
+                    true
                 }
                 _ => false,
             })
@@ -50,52 +44,73 @@ trait SafeReport: Iterator<Item = u32> + Sized {
 
 impl<I: Iterator<Item = u32>> SafeReport for I {}
 
-/// # Panics
+fn parse(line: &str) -> Result<Vec<u32, 16>, Error> {
+    let mut samples = Vec::new();
+    for value in line.split_whitespace() {
+        samples.push(value.parse()?).or_capacity()?;
+    }
+
+    Ok(samples)
+}
+
+/// Whether the report is safe.
+fn safe(line: &str) -> Result<usize, Error> {
+    let samples = parse(line)?;
+    Ok(usize::from(samples.into_iter().safe_report()))
+}
+
+/// Whether the report is safe removing at most one level.
+fn tolerant(line: &str) -> Result<usize, Error> {
+    let samples = parse(line)?;
+    if samples.iter().copied().safe_report() {
+        return Ok(1);
+    }
+
+    Ok(usize::from((0..samples.len()).any(|i| {
+        samples
+            .iter()
+            .enumerate()
+            .filter_map(|(j, sample)| if i == j { None } else { Some(*sample) })
+            .safe_report()
+    })))
+}
+
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> usize {
+pub fn try_solve_1(input: &str) -> Result<usize, Error> {
     #[cfg(feature = "parallel")]
     let lines = input.par_lines();
 
     #[cfg(not(feature = "parallel"))]
     let lines = input.lines();
 
-    lines
-        .filter(|line| {
-            line.split_whitespace()
-                .map(|value| value.parse::<u32>().unwrap())
-                .safe_report()
-        })
-        .count()
+    lines.map(safe).sum()
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> usize {
+pub fn try_solve_2(input: &str) -> Result<usize, Error> {
     #[cfg(feature = "parallel")]
     let lines = input.par_lines();
 
     #[cfg(not(feature = "parallel"))]
     let lines = input.lines();
 
-    lines
-        .filter(|line| {
-            let samples = line
-                .split_whitespace()
-                .map(|value| value.parse::<u32>().unwrap())
-                .collect::<Vec<_, 16>>();
-            if samples.iter().copied().safe_report() {
-                return true;
-            }
-
-            (0..samples.len()).any(|i| {
-                samples
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(j, sample)| if i == j { None } else { Some(*sample) })
-                    .safe_report()
-            })
-        })
-        .count()
+    lines.map(tolerant).sum()
+}
+
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> usize {
+    try_solve_1(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> usize {
+    try_solve_2(input).unwrap()
 }
 
 #[cfg(feature = "input")]
@@ -108,6 +123,48 @@ pub fn part_2() -> usize {
     solve_2(INPUT)
 }
 
+pub struct Day;
+
+impl solution::Solution for Day {
+    const DAY: u32 = 2;
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2(input)
+    }
+}
+
+/// The safe reports of the lines pushed so far, see [`StreamingSolution`].
+#[derive(Default)]
+pub struct Reports {
+    safe: usize,
+    tolerant: usize,
+}
+
+impl StreamingSolution for Day {
+    type State = Reports;
+
+    fn push_line(state: &mut Self::State, line: &str) -> Result<(), Error> {
+        state.safe += safe(line)?;
+        state.tolerant += tolerant(line)?;
+        Ok(())
+    }
+
+    fn try_finish_1(state: &mut Self::State) -> Result<Self::Output1, Error> {
+        Ok(state.safe)
+    }
+
+    fn try_finish_2(state: &mut Self::State) -> Result<Self::Output2, Error> {
+        Ok(state.tolerant)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -134,4 +191,25 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(INPUT_2), 4);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(try_solve_1("1 2 x"), Err(Error::Parse));
+        assert_eq!(try_solve_2("1 2 x"), Err(Error::Parse));
+    }
+
+    #[test]
+    fn streaming() {
+        let mut reports = Reports::default();
+        for line in INPUT_1.lines() {
+            Day::push_line(&mut reports, line).unwrap();
+        }
+
+        assert_eq!(Day::try_finish_1(&mut reports), Ok(2));
+        assert_eq!(Day::try_finish_2(&mut reports), Ok(4));
+        assert_eq!(Day::push_line(&mut reports, "1 2 x"), Err(Error::Parse));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
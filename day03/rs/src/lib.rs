@@ -11,6 +11,8 @@ use nom::{
     IResult,
 };
 
+use parser::Scanner;
+
 enum Either<L, R> {
     Left(L),
     Right(R),
@@ -118,55 +120,16 @@ pub fn solve_2_nom(input: &str) -> u32 {
     acc
 }
 
-fn parse_a(input: &[u8], mut i: usize) -> Result<(usize, u32), usize> {
-    let mut acc = 0;
-    let mut j = 0;
-    while j < 4 {
-        match input.get(i) {
-            Some(c @ (b'0'..=b'9')) => {
-                acc = acc * 10 + u32::from(c - b'0');
-                i += 1;
-                j += 1;
-            }
-            Some(b',') => {
-                if j > 0 {
-                    return Ok((i + 1, acc));
-                }
-
-                return Err(i + 1);
-            }
-            _ => {
-                return Err(i);
-            }
-        }
+/// The operand at `i`, of 1 to 3 digits and followed by `end`, and the index
+/// after `end`; otherwise the index to resume the search from.
+fn operand(input: &[u8], i: usize, end: &str) -> Result<(usize, u32), usize> {
+    let mut scanner = Scanner::from_bytes(&input[i..]);
+    let value = scanner.unsigned::<u32>();
+    let digits = scanner.position();
+    match value {
+        Ok(value) if digits <= 3 && scanner.eat(end) => Ok((i + scanner.position(), value)),
+        _ => Err(i + digits),
     }
-
-    Err(i)
-}
-
-fn parse_b(input: &[u8], mut i: usize) -> Result<(usize, u32), usize> {
-    let mut acc = 0;
-    let mut j = 0;
-    while j < 4 {
-        match input.get(i) {
-            Some(c @ (b'0'..=b'9')) => {
-                acc = acc * 10 + u32::from(c - b'0');
-                i += 1;
-                j += 1;
-            }
-            Some(b')') => {
-                if j > 0 {
-                    return Ok((i + 1, acc));
-                }
-                return Err(i + 1);
-            }
-            _ => {
-                return Err(i);
-            }
-        }
-    }
-
-    Err(i)
 }
 
 /// # Panics
@@ -179,8 +142,8 @@ pub fn solve_1_handmade(input: &str) -> u32 {
     let mut i = 0;
     loop {
         match input.get(i..i + MUL_TOKEN.len()) {
-            Some(MUL_TOKEN) => match parse_a(input, i + MUL_TOKEN.len()) {
-                Ok((j, a)) => match parse_b(input, j) {
+            Some(MUL_TOKEN) => match operand(input, i + MUL_TOKEN.len(), ",") {
+                Ok((j, a)) => match operand(input, j, ")") {
                     Ok((j, b)) => {
                         result += a * b;
                         i = j;
@@ -218,8 +181,9 @@ pub fn solve_2_handmade(input: &str) -> u32 {
     let mut i = 0;
     loop {
         match input.get(i..) {
-            Some(ss) if ss.starts_with(MUL_TOKEN) => match parse_a(input, i + MUL_TOKEN.len()) {
-                Ok((j, a)) => match parse_b(input, j) {
+            Some(ss) if ss.starts_with(MUL_TOKEN) => match operand(input, i + MUL_TOKEN.len(), ",")
+            {
+                Ok((j, a)) => match operand(input, j, ")") {
                     Ok((j, b)) => {
                         if enabled {
                             result += a * b;
@@ -256,6 +220,18 @@ pub fn solve_2_handmade(input: &str) -> u32 {
 
 pub use solve_2_handmade as solve_2;
 
+/// # Errors
+/// Never fails: any input is valid corrupted memory.
+pub fn try_solve_1(input: &str) -> Result<u32, solution::Error> {
+    Ok(solve_1(input))
+}
+
+/// # Errors
+/// Never fails: any input is valid corrupted memory.
+pub fn try_solve_2(input: &str) -> Result<u32, solution::Error> {
+    Ok(solve_2(input))
+}
+
 #[cfg(feature = "input")]
 pub fn part_1() -> u32 {
     solve_1(INPUT)
@@ -266,6 +242,23 @@ pub fn part_2() -> u32 {
     solve_2(INPUT)
 }
 
+pub struct Day;
+
+impl solution::Solution for Day {
+    const DAY: u32 = 3;
+
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, solution::Error> {
+        try_solve_1(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, solution::Error> {
+        try_solve_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -294,4 +287,13 @@ mod tests {
     fn same_results_2_nom() {
         assert_eq!(solve_2_handmade(INPUT_2), 48);
     }
+
+    #[test]
+    fn operands() {
+        let input = "mul(1234,5)mul(12,3)mul(,4)mul(4,)mul(99999999999,1)mul(4,5";
+        assert_eq!(solve_1_handmade(input), 36);
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -4,117 +4,99 @@
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
+use grid::{Dir8, Grid};
+
+use solution::Error;
+
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
 
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = "";
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> usize {
-    const XMAS: &[u8] = b"XMAS";
-
-    let data = input.as_bytes();
-
-    let width = data.iter().position(|&c| c == b'\n').unwrap();
+pub fn try_solve_1(input: &str) -> Result<usize, Error> {
+    let grid = Grid::new(input.as_bytes())?;
 
     #[cfg(feature = "parallel")]
-    let rows = data.par_chunks(width + 1);
+    let rows = grid.as_bytes().par_chunks(grid.stride());
 
     #[cfg(not(feature = "parallel"))]
-    let rows = data.chunks(width + 1);
+    let rows = grid.as_bytes().chunks(grid.stride());
 
-    rows.enumerate()
+    Ok(rows
+        .enumerate()
         .map(|(r, row)| {
-            let mut result = 0;
-            for (c, _) in row.iter().enumerate() {
-                // horizontal
-                if matches!(row.get(c..c + 4), Some(XMAS)) {
-                    result += 1;
-                }
-
-                // horizontal backward
-                if let Some(d) = row.get(c..c + 4) {
-                    if d.iter().rev().zip(XMAS).all(|(a, b)| a == b) {
-                        result += 1;
-                    }
-                }
-
-                // vertical
-                if XMAS.iter().enumerate().all(|(i, tile)| matches!(data.get((r + i) * (width + 1) + c), Some(target) if target == tile)) {
-                    result += 1;
-                }
-
-                // vertical backward
-                if XMAS.iter().rev().enumerate().all(|(i, tile)| matches!(data.get((r + i) * (width + 1) + c), Some(target) if target == tile)) {
-                    result += 1;
-                }
-
-                // diagonal right
-                if XMAS.iter().enumerate().all(|(i, tile)| matches!(data.get((r + i) * (width + 1) + c + i), Some(target) if target == tile)) {
-                    result += 1;
-                }
-
-                // diagonal right backward
-                if XMAS.iter().rev().enumerate().all(|(i, tile)| matches!(data.get((r + i) * (width + 1) + c + i), Some(target) if target == tile)) {
-                    result += 1;
-                }
-
-                // diagonal left
-                if XMAS.iter().enumerate().all(|(i, tile)| c >= i && matches!(data.get((r + i) * (width + 1) + c - i), Some(target) if target == tile)) {
-                    result += 1;
-                }
-
-                // diagonal left backward
-                if XMAS.iter().rev().enumerate().all(|(i, tile)| c >= i && matches!(data.get((r + i) * (width + 1) + c - i), Some(target) if target == tile)) {
-                    result += 1;
-                }
-            }
-
-            result
+            row.iter()
+                .take(grid.width())
+                .enumerate()
+                .filter(|&(_, &tile)| tile == b'X')
+                .map(|(c, _)| {
+                    Dir8::ALL
+                        .into_iter()
+                        .filter(|dir| {
+                            let (dr, dc) = dir.delta();
+                            b"MAS".iter().zip(1..).all(|(&tile, i)| {
+                                grid.get_offset((r, c), (dr * i, dc * i))
+                                    .is_some_and(|(_, target)| target == tile)
+                            })
+                        })
+                        .count()
+                })
+                .sum::<usize>()
         })
-        .sum()
+        .sum())
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> usize {
-    let data = input.as_bytes();
-
-    let width = data.iter().position(|&c| c == b'\n').unwrap();
-    let height = (data.len() + 1) / width;
-
-    let check_ms = |a, b| a != b && (a == b'M' || a == b'S') && (b == b'M' || b == b'S');
+pub fn try_solve_2(input: &str) -> Result<usize, Error> {
+    let grid = Grid::new(input.as_bytes())?;
+
+    let is_ms = |tile| tile == b'M' || tile == b'S';
+    let check_ms = |a: Dir8, b: Dir8, position| match (
+        grid.get_offset(position, a.delta()),
+        grid.get_offset(position, b.delta()),
+    ) {
+        (Some((_, a)), Some((_, b))) => a != b && is_ms(a) && is_ms(b),
+        _ => false,
+    };
 
     #[cfg(feature = "parallel")]
-    let rows = data.par_chunks(width + 1);
+    let rows = grid.as_bytes().par_chunks(grid.stride());
 
     #[cfg(not(feature = "parallel"))]
-    let rows = data.chunks(width + 1);
+    let rows = grid.as_bytes().chunks(grid.stride());
 
-    rows.enumerate()
-        .take(height - 1)
-        .skip(1)
+    Ok(rows
+        .enumerate()
         .map(|(r, row)| {
             row.iter()
+                .take(grid.width())
                 .enumerate()
-                .take(width - 1)
-                .skip(1)
                 .filter(move |&(c, &tile)| {
                     tile == b'A'
-                        && matches!(
-                            (
-                                data.get((r - 1) * (width + 1) + c - 1),
-                                data.get((r + 1) * (width + 1) + c + 1),
-                                data.get((r - 1) * (width + 1) + c + 1),
-                                data.get((r + 1) * (width + 1) + c - 1),
-                            ),
-                            (Some(&ul), Some(&br), Some(&ur), Some(&bl)) if check_ms(ul, br) && check_ms(ur, bl))
+                        && check_ms(Dir8::UpLeft, Dir8::DownRight, (r, c))
+                        && check_ms(Dir8::UpRight, Dir8::DownLeft, (r, c))
                 })
                 .count()
         })
-        .sum()
+        .sum())
+}
+
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> usize {
+    try_solve_1(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> usize {
+    try_solve_2(input).unwrap()
 }
 
 #[cfg(feature = "input")]
@@ -127,6 +109,23 @@ pub fn part_2() -> usize {
     solve_2(INPUT)
 }
 
+pub struct Day;
+
+impl solution::Solution for Day {
+    const DAY: u32 = 4;
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,4 +150,13 @@ MXMXAXMASX";
     fn same_results_2() {
         assert_eq!(solve_2(INPUT), 9);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(try_solve_1("XMAS"), Err(Error::Parse));
+        assert_eq!(try_solve_2("\nXMAS"), Err(Error::Parse));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
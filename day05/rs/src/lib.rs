@@ -1,18 +1,20 @@
 #![no_std]
 #![allow(clippy::must_use_candidate)]
 
-use heapless::{Vec as HLVec, FnvIndexSet};
+use collections::{Vec as HLVec, FnvIndexSet};
+
+use solution::{Error, OrCapacity};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
 type Num = u8;
 
-type Vec<T> = HLVec<T, 32>;
+type Vec<T, const N: usize> = HLVec<T, N>;
 
-type HashSet<T> = FnvIndexSet<T, 2048>;
+type HashSet<T, const N: usize> = FnvIndexSet<T, N>;
 
-type Rules = HashSet<(Num, Num)>;
+type Rules<const N: usize> = HashSet<(Num, Num), N>;
 
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
@@ -20,7 +22,13 @@ pub const INPUT: &str = include_str!("../../input");
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = "";
 
-fn is_valid(rules: &Rules, pages: &[Num]) -> bool {
+/// Default capacity for the ordering rules, must be a power of two.
+pub const RULES: usize = 2048;
+
+/// Default capacity for the pages of an update.
+pub const PAGES: usize = 32;
+
+fn is_valid<const RULES: usize>(rules: &Rules<RULES>, pages: &[Num]) -> bool {
     (0..pages.len() - 1).all(|i| {
         pages
             .iter()
@@ -31,7 +39,7 @@ fn is_valid(rules: &Rules, pages: &[Num]) -> bool {
     })
 }
 
-fn reorder(rules: &Rules, pages: &mut [Num]) {
+fn reorder<const RULES: usize>(rules: &Rules<RULES>, pages: &mut [Num]) {
     for i in 1..pages.len() {
         let (prefix, postfix) = pages.split_at_mut(i);
         let x = &mut prefix[i - 1];
@@ -45,50 +53,48 @@ fn reorder(rules: &Rules, pages: &mut [Num]) {
     debug_assert!(is_valid(rules, pages));
 }
 
-fn solve<F>(input: &str, check: F) -> u32
+fn parse_pages<const PAGES: usize>(line: &str) -> Result<Vec<Num, PAGES>, Error> {
+    let mut pages = Vec::new();
+    for page in line.split(',') {
+        pages.push(page.parse()?).or_capacity()?;
+    }
+
+    Ok(pages)
+}
+
+fn solve<const RULES: usize, const PAGES: usize, F>(
+    input: &str,
+    check: F,
+) -> Result<u32, Error>
 where
-    F: Fn(&Rules, Vec<Num>) -> Option<Num> + Sync + Send,
+    F: Fn(&Rules<RULES>, Vec<Num, PAGES>) -> Option<Num> + Sync + Send,
 {
     let mut parts = input.split("\n\n");
 
     let mut rules = Rules::new();
-    for (k, v) in parts
-        .next()
-        .unwrap()
-        .lines()
-        .map(|line| {
-            let mut parts = line.split('|');
-            (
-                parts.next().unwrap().parse::<Num>().unwrap(),
-                parts.next().unwrap().parse::<Num>().unwrap(),
-            )
-        })
-    {
-        rules.insert((k, v)).unwrap();
+    for line in parts.next().ok_or(Error::Parse)?.lines() {
+        let (k, v) = line.split_once('|').ok_or(Error::Parse)?;
+        rules.insert((k.parse()?, v.parse()?)).or_capacity()?;
     }
 
     #[cfg(feature = "parallel")]
-    let lines = parts.next().unwrap().par_lines();
+    let lines = parts.next().ok_or(Error::Parse)?.par_lines();
 
     #[cfg(not(feature = "parallel"))]
-    let lines = parts.next().unwrap().lines();
+    let lines = parts.next().ok_or(Error::Parse)?.lines();
 
     lines
-        .filter_map(|line| {
-            let pages = line
-                .split(',')
-                .map(|page| page.parse::<Num>().unwrap())
-                .collect::<Vec<_>>();
-
-            check(&rules, pages).map(u32::from)
+        .map(|line| -> Result<u32, Error> {
+            Ok(check(&rules, parse_pages(line)?).map_or(0, u32::from))
         })
         .sum()
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> u32 {
-    solve(input, |rules, pages| {
+pub fn try_solve_1<const RULES: usize, const PAGES: usize>(input: &str) -> Result<u32, Error> {
+    solve::<RULES, PAGES, _>(input, |rules, pages| {
         if is_valid(rules, &pages) {
             Some(pages[pages.len() / 2])
         } else {
@@ -97,10 +103,11 @@ pub fn solve_1(input: &str) -> u32 {
     })
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> u32 {
-    solve(input, |rules, mut pages| {
+pub fn try_solve_2<const RULES: usize, const PAGES: usize>(input: &str) -> Result<u32, Error> {
+    solve::<RULES, PAGES, _>(input, |rules, mut pages| {
         if is_valid(rules, &pages) {
             None
         } else {
@@ -111,6 +118,18 @@ pub fn solve_2(input: &str) -> u32 {
     })
 }
 
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> u32 {
+    try_solve_1::<RULES, PAGES>(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> u32 {
+    try_solve_2::<RULES, PAGES>(input).unwrap()
+}
+
 #[cfg(feature = "input")]
 pub fn part_1() -> u32 {
     solve_1(INPUT)
@@ -121,6 +140,23 @@ pub fn part_2() -> u32 {
     solve_2(INPUT)
 }
 
+pub struct Day<const RULES: usize = { crate::RULES }, const PAGES: usize = { crate::PAGES }>;
+
+impl<const RULES: usize, const PAGES: usize> solution::Solution for Day<RULES, PAGES> {
+    const DAY: u32 = 5;
+
+    type Output1 = u32;
+    type Output2 = u32;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<RULES, PAGES>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<RULES, PAGES>(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -163,4 +199,13 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(INPUT), 123);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(try_solve_1::<RULES, PAGES>("47|53"), Err(Error::Parse));
+        assert_eq!(try_solve_2::<RULES, PAGES>("47-53\n\n47,53"), Err(Error::Parse));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
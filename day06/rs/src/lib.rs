@@ -3,77 +3,88 @@
 
 use bitset::BitSet;
 
+use grid::{Dir4, Grid, Point as Position};
+
+use solution::{Error, OrCapacity};
+
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-const VISITED_SIZE: usize = BitSet::with_capacity(131 * 131);
-const VISITED_STATE_SIZE: usize = BitSet::with_capacity(131 * 131 * 5);
-
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
 
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = "";
 
-/// # Panics
-#[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> usize {
-    let map = input.as_bytes();
-    let width = map.iter().position(|&c| c == b'\n').unwrap();
-    let height = (map.len() + 1) / (width + 1);
+/// Default `BitSet` size for the visited positions.
+pub const VISITED_SIZE: usize = BitSet::with_capacity(131 * 131);
+
+/// Default `BitSet` size for the visited positions and facings.
+pub const VISITED_STATE_SIZE: usize = BitSet::with_capacity(131 * 131 * 5);
+
+/// Returns the map and the position of the guard.
+///
+/// The map must be rectangular, so the walks below cannot go out of bounds.
+fn parse(input: &str) -> Result<(Grid<'_>, Position), Error> {
+    let map = Grid::new(input.as_bytes())?;
 
-    let current_position = map.iter().position(|&c| c == b'^').unwrap();
-    let mut current_position = (
-        current_position / (width + 1),
-        current_position % (width + 1),
-    );
+    let position = map.find(b'^').ok_or(Error::Parse)?;
+
+    Ok((map, position))
+}
+
+/// # Errors
+/// See [`Error`].
+#[cfg_attr(target_os = "none", inline(never))]
+pub fn try_solve_1<const VISITED_SIZE: usize>(input: &str) -> Result<usize, Error> {
+    let (map, mut current_position) = parse(input)?;
+    let (height, width) = (map.height(), map.width());
 
     let mut visited = BitSet::<_, _, VISITED_SIZE>::new(|(r, c)| r * width + c);
 
-    let mut facing = 0;
+    let mut facing = Dir4::Up;
     loop {
-        visited.insert(current_position).unwrap();
+        visited.insert(current_position).or_capacity()?;
         if current_position.0 == 0
             || current_position.1 == 0
             || current_position.0 == width - 1
             || current_position.1 == height - 1
         {
-            return visited.len();
+            return Ok(visited.len());
         }
 
-        (facing, current_position) = [(-1, 0), (0, 1), (1, 0), (0, -1)]
-            .into_iter()
-            .enumerate()
-            .cycle()
-            .skip(facing)
-            .take(4)
-            .find_map(|(facing, (dr, dc))| {
-                let (r, c) = (
-                    current_position.0.checked_add_signed(dr).unwrap(),
-                    current_position.1.checked_add_signed(dc).unwrap(),
-                );
-
-                if map[r * (width + 1) + c] == b'#' {
-                    None
-                } else {
-                    Some((facing, (r, c)))
-                }
-            })
-            .unwrap();
+        (facing, current_position) =
+            step(&map, current_position, facing, None).ok_or(Error::Unsolvable)?;
     }
 }
 
+/// The guard turns right until the tile in front is free, then steps there.
+///
+/// Returns the new facing and position, `None` if the guard is boxed in.
+fn step(
+    map: &Grid,
+    position: Position,
+    facing: Dir4,
+    obstruction: Option<Position>,
+) -> Option<(Dir4, Position)> {
+    facing.clockwise().find_map(|facing| {
+        let (next, tile) = map.get_offset(position, facing.delta())?;
+        (tile != b'#' && Some(next) != obstruction).then_some((facing, next))
+    })
+}
+
 fn is_cycle<const SIZE: usize, K>(
-    map: &[u8],
-    (height, width): (usize, usize),
-    guard_visited: &BitSet<((usize, usize), usize), K, SIZE>,
+    map: &Grid,
+    guard_visited: &BitSet<((usize, usize), Dir4), K, SIZE>,
     (mut r, mut c): (usize, usize),
-    mut facing: usize,
+    mut facing: Dir4,
     obstruction: (usize, usize),
 ) -> bool
 where
-    K: Fn(&((usize, usize), usize)) -> usize + Copy,
+    K: Fn(&((usize, usize), Dir4)) -> usize + Copy,
 {
+    let (height, width) = (map.height(), map.width());
+
     let mut visited = BitSet::<_, _, SIZE>::new(guard_visited.key());
     loop {
         if visited.insert(((r, c), facing)).unwrap() || guard_visited.contains(&((r, c), facing)).unwrap() {
@@ -84,49 +95,41 @@ where
             return false;
         }
 
-        (facing, (r, c)) = [(-1, 0), (0, 1), (1, 0), (0, -1)]
-            .iter()
-            .enumerate()
-            .cycle()
-            .skip(facing)
-            .take(4)
-            .find_map(|(facing, (dr, dc))| {
-                let (r, c) = (
-                    r.checked_add_signed(*dr).unwrap(),
-                    c.checked_add_signed(*dc).unwrap(),
-                );
-
-                if map[r * (width + 1) + c] == b'#' || (r, c) == obstruction {
-                    None
-                } else {
-                    Some((facing, (r, c)))
-                }
-            })
-            .unwrap();
+        // a boxed in guard never leaves the map
+        let Some(next) = step(map, (r, c), facing, Some(obstruction)) else {
+            return true;
+        };
+
+        (facing, (r, c)) = next;
     }
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg(feature = "parallel")]
 #[allow(clippy::large_stack_frames)]
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2_par(input: &str) -> usize {
-    let map = input.as_bytes();
-    let width = map.iter().position(|&c| c == b'\n').unwrap();
-    let height = (map.len() + 1) / (width + 1);
-
-    let current_position = map.iter().position(|&c| c == b'^').unwrap();
-    let mut current_position = (
-        current_position / (width + 1),
-        current_position % (width + 1),
-    );
+pub fn try_solve_2_par<const VISITED_SIZE: usize, const VISITED_STATE_SIZE: usize>(
+    input: &str,
+) -> Result<usize, Error> {
+    let (map, mut current_position) = parse(input)?;
+    let (height, width) = (map.height(), map.width());
+
+    // every state must fit, the walk and the cycle checks cannot fail on insert
+    if BitSet::with_capacity(width * height) > VISITED_SIZE
+        || BitSet::with_capacity(width * height * 4) > VISITED_STATE_SIZE
+    {
+        return Err(Error::Capacity);
+    }
 
     let mut visited = BitSet::<_, _, VISITED_SIZE>::new(|(r, c)| r * width + c);
     let mut visited_pd =
-        BitSet::<_, _, VISITED_STATE_SIZE>::new(|((r, c), f)| r * width + c + f * width * height);
+        BitSet::<_, _, VISITED_STATE_SIZE>::new(|((r, c), f): &(Position, Dir4)| {
+            r * width + c + f.index() * width * height
+        });
 
-    let mut facing = 0;
-    core::iter::from_fn(|| loop {
+    let mut facing = Dir4::Up;
+    Ok(core::iter::from_fn(|| loop {
         if current_position.0 == 0
             || current_position.1 == 0
             || current_position.0 == width - 1
@@ -135,36 +138,18 @@ pub fn solve_2_par(input: &str) -> usize {
             return None;
         }
 
-        visited.insert(current_position).unwrap();
-        visited_pd.insert((current_position, facing)).unwrap();
-
-        let (next_facing, next_position) = [(-1, 0), (0, 1), (1, 0), (0, -1)]
-            .into_iter()
-            .enumerate()
-            .cycle()
-            .skip(facing)
-            .take(4)
-            .find_map(|(facing, (dr, dc))| {
-                let (r, c) = (
-                    current_position.0.checked_add_signed(dr).unwrap(),
-                    current_position.1.checked_add_signed(dc).unwrap(),
-                );
-
-                if map[r * (width + 1) + c] == b'#' {
-                    None
-                } else {
-                    Some((facing, (r, c)))
-                }
-            })
-            .unwrap();
-
-        let result = if visited.contains(&next_position).unwrap() {
+        visited.insert(current_position).ok()?;
+        visited_pd.insert((current_position, facing)).ok()?;
+
+        let (next_facing, next_position) = step(&map, current_position, facing, None)?;
+
+        let result = if visited.contains(&next_position).unwrap_or(false) {
             None
         } else {
             Some((
                 visited_pd.clone(),
                 current_position,
-                (next_facing + 1) % 4,
+                next_facing.turn_right(),
                 next_position,
             ))
         };
@@ -178,76 +163,58 @@ pub fn solve_2_par(input: &str) -> usize {
     .fuse()
     .par_bridge()
     .filter(|(visited, position, facing, obstruction)| {
-        is_cycle(
-            map,
-            (height, width),
-            visited,
-            *position,
-            *facing,
-            *obstruction,
-        )
+        is_cycle(&map, visited, *position, *facing, *obstruction)
     })
-    .count()
+    .count())
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2_sync(input: &str) -> usize {
-    let map = input.as_bytes();
-    let width = map.iter().position(|&c| c == b'\n').unwrap();
-    let height = (map.len() + 1) / (width + 1);
-
-    let current_position = map.iter().position(|&c| c == b'^').unwrap();
-    let mut current_position = (
-        current_position / (width + 1),
-        current_position % (width + 1),
-    );
+pub fn try_solve_2_sync<const VISITED_SIZE: usize, const VISITED_STATE_SIZE: usize>(
+    input: &str,
+) -> Result<usize, Error> {
+    let (map, mut current_position) = parse(input)?;
+    let (height, width) = (map.height(), map.width());
+
+    // every state must fit, the walk and the cycle checks cannot fail on insert
+    if BitSet::with_capacity(width * height) > VISITED_SIZE
+        || BitSet::with_capacity(width * height * 4) > VISITED_STATE_SIZE
+    {
+        return Err(Error::Capacity);
+    }
 
     let mut visited = BitSet::<_, _, VISITED_SIZE>::new(|(r, c)| r * width + c);
     let mut visited_pd =
-        BitSet::<_, _, VISITED_STATE_SIZE>::new(|((r, c), f)| r * width + c + f * width * height);
+        BitSet::<_, _, VISITED_STATE_SIZE>::new(|((r, c), f): &(Position, Dir4)| {
+            r * width + c + f.index() * width * height
+        });
 
     let mut count = 0;
-    let mut facing = 0;
+    let mut facing = Dir4::Up;
     loop {
         if current_position.0 == 0
             || current_position.1 == 0
             || current_position.0 == width - 1
             || current_position.1 == height - 1
         {
-            return count;
+            return Ok(count);
         }
 
-        visited.insert(current_position).unwrap();
-        visited_pd.insert((current_position, facing)).unwrap();
-
-        let (next_facing, next_position) = [(-1, 0), (0, 1), (1, 0), (0, -1)]
-            .into_iter()
-            .enumerate()
-            .cycle()
-            .skip(facing)
-            .take(4)
-            .find_map(|(facing, (dr, dc))| {
-                let (r, c) = (
-                    current_position.0.checked_add_signed(dr).unwrap(),
-                    current_position.1.checked_add_signed(dc).unwrap(),
-                );
-
-                if map[r * (width + 1) + c] == b'#' {
-                    None
-                } else {
-                    Some((facing, (r, c)))
-                }
-            })
-            .unwrap();
-
-        if !visited.contains(&next_position).unwrap()
+        visited.insert(current_position).or_capacity()?;
+        visited_pd
+            .insert((current_position, facing))
+            .or_capacity()?;
+
+        let (next_facing, next_position) =
+            step(&map, current_position, facing, None).ok_or(Error::Unsolvable)?;
+
+        if !visited.contains(&next_position).unwrap_or(false)
             && is_cycle(
-                map,
-                (height, width),
+                &map,
                 &visited_pd,
                 current_position,
-                (next_facing + 1) % 4,
+                next_facing.turn_right(),
                 next_position,
             )
         {
@@ -258,12 +225,37 @@ pub fn solve_2_sync(input: &str) -> usize {
     }
 }
 
+/// # Panics
+/// Panics if [`try_solve_2_par`] fails.
+#[cfg(feature = "parallel")]
+pub fn solve_2_par(input: &str) -> usize {
+    try_solve_2_par::<VISITED_SIZE, VISITED_STATE_SIZE>(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2_sync`] fails.
+pub fn solve_2_sync(input: &str) -> usize {
+    try_solve_2_sync::<VISITED_SIZE, VISITED_STATE_SIZE>(input).unwrap()
+}
+
+#[cfg(not(feature = "parallel"))]
+pub use try_solve_2_sync as try_solve_2;
+
+#[cfg(feature = "parallel")]
+pub use try_solve_2_par as try_solve_2;
+
 #[cfg(not(feature = "parallel"))]
 pub use solve_2_sync as solve_2;
 
 #[cfg(feature = "parallel")]
 pub use solve_2_par as solve_2;
 
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> usize {
+    try_solve_1::<VISITED_SIZE>(input).unwrap()
+}
+
 #[cfg(feature = "input")]
 pub fn part_1() -> usize {
     solve_1(INPUT)
@@ -274,6 +266,28 @@ pub fn part_2() -> usize {
     solve_2(INPUT)
 }
 
+pub struct Day<
+    const VISITED_SIZE: usize = { crate::VISITED_SIZE },
+    const VISITED_STATE_SIZE: usize = { crate::VISITED_STATE_SIZE },
+>;
+
+impl<const VISITED_SIZE: usize, const VISITED_STATE_SIZE: usize> solution::Solution
+    for Day<VISITED_SIZE, VISITED_STATE_SIZE>
+{
+    const DAY: u32 = 6;
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<VISITED_SIZE>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<VISITED_SIZE, VISITED_STATE_SIZE>(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -303,4 +317,29 @@ mod tests {
     fn same_results_2_par() {
         assert_eq!(solve_2_par(INPUT), 6);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(try_solve_1::<VISITED_SIZE>("...\n.#.\n..."), Err(Error::Parse));
+        assert_eq!(
+            try_solve_2::<VISITED_SIZE, VISITED_STATE_SIZE>("...\n.^\n..."),
+            Err(Error::Parse)
+        );
+    }
+
+    #[test]
+    fn custom_capacity() {
+        const SMALL: usize = BitSet::with_capacity(10 * 10);
+        const SMALL_STATE: usize = BitSet::with_capacity(10 * 10 * 4);
+
+        assert_eq!(try_solve_1::<SMALL>(INPUT), Ok(41));
+        assert_eq!(try_solve_2_sync::<SMALL, SMALL_STATE>(INPUT), Ok(6));
+        assert_eq!(
+            try_solve_2_sync::<SMALL, SMALL>(INPUT),
+            Err(Error::Capacity)
+        );
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -3,5 +3,13 @@ fn main() {
     println!("cargo::rerun-if-changed=../input");
 
     #[cfg(feature = "input")]
-    aoc::get_input_info_from_cargo(Some("../input".to_string()));
+    if let Err(err) = aoc::try_get_input_info_from_cargo(Some("../input".to_string())) {
+        println!("cargo::warning=cannot get input, using the empty input: {err}");
+        aoc::write_empty_input("../input").expect("cannot write (default) input file");
+    }
+
+    #[cfg(feature = "input")]
+    if let Err(err) = aoc::try_get_answers_info_from_cargo() {
+        println!("cargo::warning=cannot get answers: {err}");
+    }
 }
@@ -4,7 +4,9 @@
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use heapless::Vec as HLVec;
+use collections::Vec as HLVec;
+
+use solution::{Error, OrCapacity};
 
 type Vec<T> = HLVec<T, 16>;
 type Stack<T> = HLVec<T, 32>;
@@ -29,88 +31,95 @@ impl Part for Part2 {
     const OPS: &[u32] = &[0, 1, 2];
 }
 
-fn solve<P: Part>(input: &str) -> u64 {
-    #[cfg(feature = "parallel")]
-    let lines = input.par_lines();
+/// The target of `line` if it can be made true, 0 otherwise.
+fn calibration<P: Part>(line: &str) -> Result<u64, Error> {
+    let (target, numbers_part) = line.split_once(": ").ok_or(Error::Parse)?;
+    let target = target.parse::<u64>()?;
 
-    #[cfg(not(feature = "parallel"))]
-    let lines = input.lines();
+    let mut numbers = Vec::new();
+    for number in numbers_part.split_whitespace() {
+        numbers.push(number.parse::<u64>()?).or_capacity()?;
+    }
 
-    lines
-        .filter_map(|line| {
-            let mut parts = line.split(": ");
-
-            let target = parts.next().unwrap().parse::<u64>().unwrap();
-            
-            let mut numbers = Vec::new();
-            for v in parts
-                .next()
-                .unwrap()
-                .split_whitespace()
-                .map(|number| number.parse::<u64>().unwrap())
-            {
-                numbers.push(v).unwrap();
-            }
+    let Some((&a, ax)) = numbers.split_first() else {
+        return Ok(0);
+    };
 
-            if let Some((&a, ax)) = numbers.split_first() {
-                let Some((&b, bx)) = ax.split_first() else {
-                    if a == target {
-                        return Some(target);
-                    }
+    let Some((&b, bx)) = ax.split_first() else {
+        return Ok(if a == target { target } else { 0 });
+    };
 
-                    return None;
-                };
+    let mut stack = Stack::new();
+    for op in P::OPS {
+        stack.push((a, b, bx, op)).or_capacity()?;
+    }
 
-                let mut stack = Stack::new();
-                for op in P::OPS {
-                    stack.push((a, b, bx, op)).unwrap();
+    while let Some((a, b, rx, op)) = stack.pop() {
+        let n = match op {
+            0 => a + b,
+            1 => a * b,
+            2 => {
+                let mut bb = b;
+                let mut a = a;
+                while bb > 0 {
+                    bb /= 10;
+                    a *= 10;
                 }
+                a + b
+            }
+            _ => unreachable!(),
+        };
 
-                while let Some((a, b, rx, op)) = stack.pop() {
-                    let n = match op {
-                        0 => a + b,
-                        1 => a * b,
-                        2 => {
-                            let mut bb = b;
-                            let mut a = a;
-                            while bb > 0 {
-                                bb /= 10;
-                                a *= 10;
-                            }
-                            a + b
-                        }
-                        _ => unreachable!(),
-                    };
-
-                    if let Some((&b, bx)) = rx.split_first() {
-                        if n <= target {
-                            for op in P::OPS {
-                                stack.push((n, b, bx, op)).unwrap();
-                            }
-                        }
-                    } else if n == target {
-                        return Some(target);
-                    }
+        if let Some((&b, bx)) = rx.split_first() {
+            if n <= target {
+                for op in P::OPS {
+                    stack.push((n, b, bx, op)).or_capacity()?;
                 }
             }
+        } else if n == target {
+            return Ok(target);
+        }
+    }
 
-            None
-        })
-        .sum()
+    Ok(0)
 }
 
-/// # Panics
+fn solve<P: Part>(input: &str) -> Result<u64, Error> {
+    #[cfg(feature = "parallel")]
+    let lines = input.par_lines();
+
+    #[cfg(not(feature = "parallel"))]
+    let lines = input.lines();
+
+    lines.map(calibration::<P>).sum()
+}
+
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> u64 {
+pub fn try_solve_1(input: &str) -> Result<u64, Error> {
     solve::<Part1>(input)
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> u64 {
+pub fn try_solve_2(input: &str) -> Result<u64, Error> {
     solve::<Part2>(input)
 }
 
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> u64 {
+    try_solve_1(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> u64 {
+    try_solve_2(input).unwrap()
+}
+
 #[cfg(feature = "input")]
 pub fn part_1() -> u64 {
     solve_1(INPUT)
@@ -121,6 +130,23 @@ pub fn part_2() -> u64 {
     solve_2(INPUT)
 }
 
+pub struct Day;
+
+impl solution::Solution for Day {
+    const DAY: u32 = 7;
+
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -144,4 +170,13 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(INPUT), 11387);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(try_solve_1("190 10 19"), Err(Error::Parse));
+        assert_eq!(try_solve_2("190: 10 x"), Err(Error::Parse));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -3,13 +3,16 @@
 
 use bitset::BitSet;
 
-use heapless::{Entry, FnvIndexMap, Vec as HLVec};
+use collections::{Entry, FnvIndexMap, Vec as HLVec};
+
+use grid::{Grid, Point};
+
+use solution::{Error, OrCapacity};
 
 const MAP_SIZE: usize = 64;
-const SET_SIZE: usize = BitSet::with_capacity(MAP_SIZE * MAP_SIZE);
 
-type HashMap<K, V> = FnvIndexMap<K, V, MAP_SIZE>;
-type Vec<T> = HLVec<T, 16>;
+type HashMap<K, V, const N: usize> = FnvIndexMap<K, V, N>;
+type Vec<T, const N: usize> = HLVec<T, N>;
 
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
@@ -17,11 +20,20 @@ pub const INPUT: &str = include_str!("../../input");
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = "";
 
-type Point = (usize, usize);
+/// Default capacity for the antenna frequencies, must be a power of two.
+pub const FREQUENCIES: usize = 64;
+
+/// Default capacity for the antennas of a single frequency.
+pub const ANTENNAS: usize = 16;
 
-struct Antennas(HashMap<u8, Vec<Point>>);
+/// Default `BitSet` size for the antinodes.
+pub const SET_SIZE: usize = BitSet::with_capacity(MAP_SIZE * MAP_SIZE);
 
-impl Antennas {
+struct Antennas<const FREQUENCIES: usize, const ANTENNAS: usize>(
+    HashMap<u8, Vec<Point, ANTENNAS>, FREQUENCIES>,
+);
+
+impl<const FREQUENCIES: usize, const ANTENNAS: usize> Antennas<FREQUENCIES, ANTENNAS> {
     const fn new() -> Self {
         Self(HashMap::new())
     }
@@ -40,8 +52,10 @@ impl Antennas {
     }
 }
 
-impl core::ops::Deref for Antennas {
-    type Target = HashMap<u8, Vec<Point>>;
+impl<const FREQUENCIES: usize, const ANTENNAS: usize> core::ops::Deref
+    for Antennas<FREQUENCIES, ANTENNAS>
+{
+    type Target = HashMap<u8, Vec<Point, ANTENNAS>, FREQUENCIES>;
 
     fn deref(&self) -> &Self::Target {
         &self.0
@@ -83,32 +97,22 @@ fn calculate_antinodes(
     .fuse()
 }
 
-/// # Panics
-pub fn solve<F, I>(input: &str, calculate_antinodes: F) -> usize
+/// # Errors
+/// See [`Error`].
+pub fn solve<const FREQUENCIES: usize, const ANTENNAS: usize, const SET_SIZE: usize, F, I>(
+    input: &str,
+    calculate_antinodes: F,
+) -> Result<usize, Error>
 where
     for<'a> F: Fn(Point, &'a Point, &'a Point) -> I + 'a,
     I: Iterator<Item = Point>,
 {
-    let map = input.as_bytes();
-    let width = map.iter().position(|&c| c == b'\n').unwrap();
-    let height = (map.len() + 1) / (width + 1);
-    
-    let mut antennas = Antennas::new();
-    for (tile, point) in map
-        .chunks(width + 1)
-        .enumerate()
-        .flat_map(|(r, row)| {
-            // taking also the last char ot the row: it is a newline (if exists) :P
-            row.iter().enumerate().filter_map(move |(c, &tile)| {
-                if tile.is_ascii_alphanumeric() {
-                    Some((tile, (r, c)))
-                } else {
-                    None
-                }
-            })
-        })
-    {
-        antennas.add(tile, &point).unwrap();
+    let grid = Grid::new(input.as_bytes())?;
+    let (height, width) = (grid.height(), grid.width());
+
+    let mut antennas = Antennas::<FREQUENCIES, ANTENNAS>::new();
+    for (point, tile) in grid.iter().filter(|(_, tile)| tile.is_ascii_alphanumeric()) {
+        antennas.add(tile, &point).or_capacity()?;
     }
 
     let mut antinodes =
@@ -117,28 +121,46 @@ where
         for (i, a) in nodes.iter().enumerate().take(nodes.len() - 1) {
             for b in nodes.iter().skip(i + 1) {
                 for antinode in calculate_antinodes((height, width), a, b) {
-                    antinodes.insert(antinode).unwrap();
+                    antinodes.insert(antinode).or_capacity()?;
                 }
                 for antinode in calculate_antinodes((height, width), b, a) {
-                    antinodes.insert(antinode).unwrap();
+                    antinodes.insert(antinode).or_capacity()?;
                 }
             }
         }
     }
 
-    antinodes.len()
+    Ok(antinodes.len())
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
+pub fn try_solve_1<const FREQUENCIES: usize, const ANTENNAS: usize, const SET_SIZE: usize>(
+    input: &str,
+) -> Result<usize, Error> {
+    solve::<FREQUENCIES, ANTENNAS, SET_SIZE, _, _>(input, calculate_antinode)
+}
+
+/// # Errors
+/// See [`Error`].
+#[cfg_attr(target_os = "none", inline(never))]
+pub fn try_solve_2<const FREQUENCIES: usize, const ANTENNAS: usize, const SET_SIZE: usize>(
+    input: &str,
+) -> Result<usize, Error> {
+    solve::<FREQUENCIES, ANTENNAS, SET_SIZE, _, _>(input, calculate_antinodes)
+}
+
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
 pub fn solve_1(input: &str) -> usize {
-    solve(input, calculate_antinode)
+    try_solve_1::<FREQUENCIES, ANTENNAS, SET_SIZE>(input).unwrap()
 }
 
 /// # Panics
-#[cfg_attr(target_os = "none", inline(never))]
+/// Panics if [`try_solve_2`] fails.
 pub fn solve_2(input: &str) -> usize {
-    solve(input, calculate_antinodes)
+    try_solve_2::<FREQUENCIES, ANTENNAS, SET_SIZE>(input).unwrap()
 }
 
 #[cfg(feature = "input")]
@@ -151,6 +173,29 @@ pub fn part_2() -> usize {
     solve_2(INPUT)
 }
 
+pub struct Day<
+    const FREQUENCIES: usize = { crate::FREQUENCIES },
+    const ANTENNAS: usize = { crate::ANTENNAS },
+    const SET_SIZE: usize = { crate::SET_SIZE },
+>;
+
+impl<const FREQUENCIES: usize, const ANTENNAS: usize, const SET_SIZE: usize> solution::Solution
+    for Day<FREQUENCIES, ANTENNAS, SET_SIZE>
+{
+    const DAY: u32 = 8;
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<FREQUENCIES, ANTENNAS, SET_SIZE>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<FREQUENCIES, ANTENNAS, SET_SIZE>(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -192,4 +237,42 @@ mod tests {
     fn same_results_2_2() {
         assert_eq!(solve_2(INPUT_1), 34);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(try_solve_1::<FREQUENCIES, ANTENNAS, SET_SIZE>("..a..a"), Err(Error::Parse));
+    }
+
+    #[test]
+    fn capacity_overflow() {
+        let mut input = collections::String::<{ 66 * 65 }>::new();
+        for r in 0..65 {
+            for c in 0..65 {
+                let tile = if r == 64 && (c == 10 || c == 20) {
+                    'a'
+                } else {
+                    '.'
+                };
+                input.push(tile).unwrap();
+            }
+            input.push('\n').unwrap();
+        }
+
+        assert_eq!(
+            try_solve_1::<FREQUENCIES, ANTENNAS, SET_SIZE>(&input),
+            Err(Error::Capacity)
+        );
+    }
+
+    #[test]
+    fn custom_capacity() {
+        const SMALL: usize = BitSet::with_capacity(12 * 12);
+
+        assert_eq!(try_solve_2::<2, 4, SMALL>(INPUT_1), Ok(34));
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(try_solve_2::<2, 3, SMALL>(INPUT_1), Err(Error::Capacity));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -1,12 +1,14 @@
 #![no_std]
 #![allow(clippy::must_use_candidate)]
 
-use heapless::{binary_heap, BinaryHeap as HLBinaryHeap, Deque as HLDeque, Vec as HLVec};
+use collections::{binary_heap, BinaryHeap as HLBinaryHeap, Deque as HLDeque, Vec as HLVec};
 
-type Free<T> = HLDeque<T, { 1024 * 16 }>;
-type Disk<T> = HLDeque<T, { 1024 * 16 }>;
-type Heap<T> = HLVec<T, { 1024 * 16 }>;
-type BinaryHeap<T> = HLBinaryHeap<T, binary_heap::Min, { 1024 * 2 }>;
+use solution::{Error, OrCapacity};
+
+type Free<T, const N: usize> = HLDeque<T, N>;
+type Disk<T, const N: usize> = HLDeque<T, N>;
+type Heap<T, const N: usize> = HLVec<T, N>;
+type BinaryHeap<T, const N: usize> = HLBinaryHeap<T, binary_heap::Min, N>;
 
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
@@ -14,23 +16,36 @@ pub const INPUT: &str = include_str!("../../input");
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = "";
 
-/// # Panics
+/// Default capacity for the blocks on the disk.
+pub const BLOCKS: usize = 1024 * 16;
+
+/// Default capacity for the free spans of every size.
+pub const FREE_SPANS: usize = 1024 * 2;
+
+/// # Errors
+/// See [`Error`].
 #[allow(clippy::cast_possible_truncation)]
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> u64 {
-    let mut occupied = Disk::new();
-    let mut free = Free::new();
+pub fn try_solve_1<const BLOCKS: usize>(input: &str) -> Result<u64, Error> {
+    let mut occupied = Disk::<_, BLOCKS>::new();
+    let mut free = Free::<_, BLOCKS>::new();
     let mut idx = 0u32;
     for (i, size) in input.trim().as_bytes().iter().enumerate() {
+        if !size.is_ascii_digit() {
+            return Err(Error::Parse);
+        }
+
         let size = size - b'0';
         if size == 0 {
             continue;
         }
 
         if i % 2 == 0 {
-            occupied.push_back((i as u16 / 2, idx, size)).unwrap();
+            occupied
+                .push_back((i as u16 / 2, idx, size))
+                .or_capacity()?;
         } else {
-            free.push_back((idx, size)).unwrap();
+            free.push_back((idx, size)).or_capacity()?;
         }
 
         idx += u32::from(size);
@@ -48,7 +63,7 @@ pub fn solve_1(input: &str) -> u64 {
         if free_idx > occupied_idx {
             occupied
                 .push_back((id, occupied_idx, occupied_size))
-                .unwrap();
+                .or_capacity()?;
             break;
         }
 
@@ -58,32 +73,37 @@ pub fn solve_1(input: &str) -> u64 {
         occupied_size -= moveable_size;
         free_size -= moveable_size;
 
-        occupied.push_front((id, free_idx, moveable_size)).unwrap();
+        occupied
+            .push_front((id, free_idx, moveable_size))
+            .or_capacity()?;
 
         if free_size > 0 {
             free.push_front((free_idx + u32::from(moveable_size), free_size))
-                .unwrap();
+                .or_capacity()?;
         }
         if occupied_size > 0 {
             occupied
                 .push_back((id, occupied_idx, occupied_size))
-                .unwrap();
+                .or_capacity()?;
         }
 
         low_mark = free_idx + u32::from(moveable_size);
         high_mark = occupied_idx;
     }
 
-    occupied
+    Ok(occupied
         .iter()
         .flat_map(|(id, idx, size)| (*idx..*idx + u32::from(*size)).map(move |i| u64::from(*id) * u64::from(i)))
-        .sum()
+        .sum())
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[allow(clippy::too_many_lines, clippy::cast_possible_truncation)]
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> u64 {
+pub fn try_solve_2<const BLOCKS: usize, const FREE_SPANS: usize>(
+    input: &str,
+) -> Result<u64, Error> {
     #[derive(Debug)]
     struct Info {
         id: u16,
@@ -91,12 +111,12 @@ pub fn solve_2(input: &str) -> u64 {
         size: u8,
     }
 
-    let mut free_space_heaps = const { [ const { BinaryHeap::new() }; 9] };
-    let mut occupied_heap = const { Heap::new() };
+    let mut free_space_heaps = const { [ const { BinaryHeap::<_, FREE_SPANS>::new() }; 9] };
+    let mut occupied_heap = const { Heap::<_, BLOCKS>::new() };
 
     let mut index = 0u32;
     for (i, size) in input.trim().chars().enumerate() {
-        let size = (size as u32 - '0' as u32) as u8;
+        let size = size.to_digit(10).ok_or(Error::Parse)? as u8;
         if size == 0 {
             continue;
         }
@@ -108,15 +128,17 @@ pub fn solve_2(input: &str) -> u64 {
                     index,
                     size,
                 })
-                .unwrap();
+                .or_capacity()?;
         } else {
-            free_space_heaps[size as usize - 1].push(index).unwrap();
+            free_space_heaps[size as usize - 1]
+                .push(index)
+                .or_capacity()?;
         }
 
         index += u32::from(size);
     }
 
-    let find_blocks = |free_space_heaps: &[BinaryHeap<u32>], occupied_heap: &[Info]| {
+    let find_blocks = |free_space_heaps: &[BinaryHeap<u32, FREE_SPANS>], occupied_heap: &[Info]| {
         let (mut target_occupied_index, mut target_free_block_index, mut target_free_block_size) =
             (u32::MAX, u32::MAX, u8::MAX);
 
@@ -167,7 +189,7 @@ pub fn solve_2(input: &str) -> u64 {
         None
     };
 
-    let mut moved_blocks = const { Heap::new() };
+    let mut moved_blocks = const { Heap::<_, BLOCKS>::new() };
     while let Some((block_index, free_block_index, free_block_size)) =
         find_blocks(&free_space_heaps, &occupied_heap)
     {
@@ -183,21 +205,33 @@ pub fn solve_2(input: &str) -> u64 {
                 index: free_block_index,
                 size: moved_size,
             })
-            .unwrap();
+            .or_capacity()?;
 
         free_space_heaps[free_block_size as usize - 1].pop();
         if free_block_size > moved_size {
             free_space_heaps[free_block_size as usize - moved_size as usize - 1]
                 .push(free_block_index + u32::from(moved_size))
-                .unwrap();
+                .or_capacity()?;
         }
     }
 
-    moved_blocks
+    Ok(moved_blocks
         .iter()
         .chain(&occupied_heap)
         .flat_map(|Info { id, index, size }| (*index..*index + u32::from(*size)).map(move |i| u64::from(*id) * u64::from(i)))
-        .sum()
+        .sum())
+}
+
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> u64 {
+    try_solve_1::<BLOCKS>(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> u64 {
+    try_solve_2::<BLOCKS, FREE_SPANS>(input).unwrap()
 }
 
 #[cfg(feature = "input")]
@@ -210,6 +244,26 @@ pub fn part_2() -> u64 {
     solve_2(INPUT)
 }
 
+pub struct Day<
+    const BLOCKS: usize = { crate::BLOCKS },
+    const FREE_SPANS: usize = { crate::FREE_SPANS },
+>;
+
+impl<const BLOCKS: usize, const FREE_SPANS: usize> solution::Solution for Day<BLOCKS, FREE_SPANS> {
+    const DAY: u32 = 9;
+
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<BLOCKS>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<BLOCKS, FREE_SPANS>(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -225,4 +279,21 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(INPUT), 2858);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(try_solve_1::<BLOCKS>("2333x"), Err(Error::Parse));
+        assert_eq!(try_solve_2::<BLOCKS, FREE_SPANS>("2333x"), Err(Error::Parse));
+    }
+
+    #[test]
+    fn custom_capacity() {
+        assert_eq!(try_solve_1::<64>(INPUT), Ok(1928));
+        assert_eq!(try_solve_2::<32, 8>(INPUT), Ok(2858));
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(try_solve_2::<8, 8>(INPUT), Err(Error::Capacity));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -4,12 +4,16 @@
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
-use heapless::Deque;
+use collections::Deque;
 
-use bitset::BitSet as VBitSet;
+use bitset::{BitSet as VBitSet, GridIndex};
 
-type VecDeque<T> = Deque<T, 32>;
-type BitSet<T, K> = VBitSet<T, K, { VBitSet::with_capacity(64 * 64) }>;
+use grid::{Grid, Point};
+
+use solution::{Error, OrCapacity};
+
+type VecDeque<T, const N: usize> = Deque<T, N>;
+type BitSet<T, K, const N: usize> = VBitSet<T, K, N>;
 
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
@@ -17,102 +21,108 @@ pub const INPUT: &str = include_str!("../../input");
 #[cfg(not(feature = "input"))]
 pub static INPUT: &'static str = &"";
 
-/// (rows x columns)
-type Point = (usize, usize);
+/// Default capacity for the queue of the positions to visit.
+pub const QUEUE: usize = 32;
 
-/// # Panics
-fn solve<F>(input: &str, find_paths: F) -> usize
+/// Default `BitSet` size for the visited positions.
+pub const VISITED_SIZE: usize = VBitSet::with_capacity(64 * 64);
+
+fn solve<F>(input: &str, find_paths: F) -> Result<usize, Error>
 where
-    F: Fn(&Point, &[u8], Point) -> usize + Copy + Sync + Send,
+    F: Fn(&Grid, Point) -> Result<usize, Error> + Copy + Sync + Send,
 {
-    let map = input.as_bytes();
-    let width = map.iter().position(|&c| c == b'\n').unwrap();
-    let height = (map.len() + 1) / (width + 1);
+    let grid = Grid::new(input.as_bytes())?;
 
     #[cfg(feature = "parallel")]
-    let rows = map.par_chunks(width + 1).take(height);
+    let rows = grid.as_bytes().par_chunks(grid.stride());
 
     #[cfg(not(feature = "parallel"))]
-    let rows = map.chunks(width + 1).take(height);
+    let rows = grid.as_bytes().chunks(grid.stride());
 
     rows.enumerate()
         .map(|(r, row)| {
             row.iter()
-                .take(width)
+                .take(grid.width())
                 .enumerate()
                 .map(move |(c, &tile)| {
                     if tile == b'0' {
-                        find_paths(&(height, width), map, (r, c))
+                        find_paths(&grid, (r, c))
                     } else {
-                        0
+                        Ok(0)
                     }
                 })
-                .sum::<usize>()
+                .sum::<Result<usize, Error>>()
         })
         .sum()
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> usize {
-    solve(input, |&(height, width), map, (r, c)| {
-        let mut count = 0;
-        let mut visited = BitSet::new(|(r, c)| r * (width + 1) + c);
-        visited.insert((r, c)).unwrap();
-
-        let mut queue = VecDeque::new();
-        queue.push_back((r, c)).unwrap();
-        while let Some((r, c)) = queue.pop_front() {
-            let tile = map[r * (width + 1) + c];
-            if tile == b'9' {
-                count += 1;
-            } else {
-                for (dr, dc) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
-                    match (r.checked_add_signed(dr), c.checked_add_signed(dc)) {
-                        (Some(r), Some(c))
-                            if r < width && c < height && map[r * (width + 1) + c] == tile + 1 =>
-                        {
-                            if !visited.insert((r, c)).unwrap() {
-                                queue.push_back((r, c)).unwrap();
-                            }
-                        }
-                        _ => {}
-                    }
-                }
-            }
+pub fn try_solve_1<const QUEUE: usize, const VISITED_SIZE: usize>(
+    input: &str,
+) -> Result<usize, Error> {
+    solve(input, |grid, start| {
+        // the visited keys must fit the `BitSet`
+        if VBitSet::with_capacity(grid.width() * grid.height()) > VISITED_SIZE {
+            return Err(Error::Capacity);
         }
-        count
+
+        let mut visited = BitSet::<_, _, VISITED_SIZE>::from_key(GridIndex::new(grid.width()));
+        search::bfs::<_, _, _, QUEUE>(
+            start,
+            &mut visited,
+            |&position| {
+                let tile = grid[position];
+                grid.neighbours(position)
+                    .filter_map(move |(next, next_tile)| (next_tile == tile + 1).then_some(next))
+            },
+            |_| false,
+        )?;
+
+        Ok(visited
+            .iter()
+            .filter(|&position| grid[position] == b'9')
+            .count())
     })
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> usize {
-    solve(input, |&(height, width), map, (r, c)| {
+pub fn try_solve_2<const QUEUE: usize>(input: &str) -> Result<usize, Error> {
+    solve(input, |grid, start| {
         let mut count = 0;
-        let mut queue = VecDeque::new();
-        queue.push_back((r, c)).unwrap();
-        while let Some((r, c)) = queue.pop_front() {
-            let tile = map[r * (width + 1) + c];
+        let mut queue = VecDeque::<_, QUEUE>::new();
+        queue.push_back(start).or_capacity()?;
+        while let Some(position) = queue.pop_front() {
+            let tile = grid[position];
             if tile == b'9' {
                 count += 1;
             } else {
-                for (dr, dc) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
-                    match (r.checked_add_signed(dr), c.checked_add_signed(dc)) {
-                        (Some(r), Some(c))
-                            if r < width && c < height && map[r * (width + 1) + c] == tile + 1 =>
-                        {
-                            queue.push_back((r, c)).unwrap();
-                        }
-                        _ => {}
+                for (next, next_tile) in grid.neighbours(position) {
+                    if next_tile == tile + 1 {
+                        queue.push_back(next).or_capacity()?;
                     }
                 }
             }
         }
-        count
+        Ok(count)
     })
 }
 
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> usize {
+    try_solve_1::<QUEUE, VISITED_SIZE>(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> usize {
+    try_solve_2::<QUEUE>(input).unwrap()
+}
+
 pub fn part_1() -> usize {
     solve_1(INPUT)
 }
@@ -121,6 +131,28 @@ pub fn part_2() -> usize {
     solve_2(INPUT)
 }
 
+pub struct Day<
+    const QUEUE: usize = { crate::QUEUE },
+    const VISITED_SIZE: usize = { crate::VISITED_SIZE },
+>;
+
+impl<const QUEUE: usize, const VISITED_SIZE: usize> solution::Solution
+    for Day<QUEUE, VISITED_SIZE>
+{
+    const DAY: u32 = 10;
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<QUEUE, VISITED_SIZE>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<QUEUE>(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -152,4 +184,26 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(INPUT_2), 81);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            try_solve_1::<QUEUE, VISITED_SIZE>("0123\n1234\n876"),
+            Err(Error::Parse)
+        );
+        assert_eq!(try_solve_2::<QUEUE>("0123"), Err(Error::Parse));
+    }
+
+    #[test]
+    fn custom_capacity() {
+        const SMALL: usize = VBitSet::with_capacity(9 * 8);
+
+        assert_eq!(try_solve_1::<16, SMALL>(INPUT_2), Ok(36));
+        assert_eq!(try_solve_1::<16, 0>(INPUT_2), Err(Error::Capacity));
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(try_solve_2::<1>(INPUT_2), Err(Error::Capacity));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
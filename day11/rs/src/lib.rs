@@ -1,9 +1,11 @@
 #![no_std]
 #![allow(clippy::must_use_candidate)]
 
-use heapless::{Entry, FnvIndexMap};
+use collections::{Entry, FnvIndexMap};
 
-type Map<K, T> = FnvIndexMap<K, T, 4096>;
+use solution::{Error, OrCapacity};
+
+type Map<K, T, const N: usize> = FnvIndexMap<K, T, N>;
 
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
@@ -11,11 +13,14 @@ pub const INPUT: &str = include_str!("../../input");
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = &"";
 
+/// Default capacity for the distinct stones, must be a power of two.
+pub const STONES: usize = 4096;
+
 type Stone = u64;
 
-struct Stones(Map<Stone, u64>);
+struct Stones<const N: usize>(Map<Stone, u64, N>);
 
-impl Stones {
+impl<const N: usize> Stones<N> {
     const fn new() -> Self {
         Self(Map::new())
     }
@@ -24,15 +29,17 @@ impl Stones {
         self.0.clear();
     }
 
-    fn add(&mut self, stone: Stone, count: u64) {
+    fn add(&mut self, stone: Stone, count: u64) -> Result<(), Error> {
         match self.0.entry(stone) {
             Entry::Vacant(v) => {
-                v.insert(count).unwrap();
+                v.insert(count).or_capacity()?;
             }
             Entry::Occupied(mut v) => {
                 *v.get_mut() += count;
             }
         }
+
+        Ok(())
     }
 
     fn values(&self) -> impl Iterator<Item = &u64> {
@@ -47,7 +54,7 @@ impl Stones {
 fn split_if_even(stone: Stone) -> Option<(Stone, Stone)> {
     stone.checked_ilog10().and_then(|digits| {
         if digits % 2 == 1 {
-            let div = 10_u64.pow((digits + 1) / 2);
+            let div = 10_u64.pow(digits.div_ceil(2));
             Some((stone / div, stone % div))
         } else {
             None
@@ -55,20 +62,16 @@ fn split_if_even(stone: Stone) -> Option<(Stone, Stone)> {
     })
 }
 
-/// # Panics
-fn solve(input: &str, mut blinks: usize) -> u64 {
+fn solve<const STONES: usize>(input: &str, mut blinks: usize) -> Result<u64, Error> {
     let mut stones = const {
         [
-            Stones::new(),
-            Stones::new(),
+            Stones::<STONES>::new(),
+            Stones::<STONES>::new(),
         ]
     };
 
-    for (k, v) in input
-        .split_whitespace()
-        .map(|stone| (stone.parse::<Stone>().unwrap(), 1))
-    {
-        stones[0].add(k, v);
+    for stone in input.split_whitespace() {
+        stones[0].add(stone.parse()?, 1)?;
     }
     
     let mut i = 0;
@@ -86,31 +89,45 @@ fn solve(input: &str, mut blinks: usize) -> u64 {
         new_stones.clear();
         for (&stone, &n) in old_stones.iter() {
             if stone == 0 {
-                new_stones.add(1, n);
+                new_stones.add(1, n)?;
             } else if let Some((a, b)) = split_if_even(stone) {
-                new_stones.add(a, n);
-                new_stones.add(b, n);
+                new_stones.add(a, n)?;
+                new_stones.add(b, n)?;
             } else {
-                new_stones.add(stone * 2024, n);
+                new_stones.add(stone * 2024, n)?;
             }
         }
 
         blinks -= 1;
     }
 
-    stones[i].values().sum()
+    Ok(stones[i].values().sum())
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
+#[cfg_attr(target_os = "none", inline(never))]
+pub fn try_solve_1<const STONES: usize>(input: &str) -> Result<u64, Error> {
+    solve::<STONES>(input, 25)
+}
+
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
+pub fn try_solve_2<const STONES: usize>(input: &str) -> Result<u64, Error> {
+    solve::<STONES>(input, 75)
+}
+
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
 pub fn solve_1(input: &str) -> u64 {
-    solve(input, 25)
+    try_solve_1::<STONES>(input).unwrap()
 }
 
 /// # Panics
-#[cfg_attr(target_os = "none", inline(never))]
+/// Panics if [`try_solve_2`] fails.
 pub fn solve_2(input: &str) -> u64 {
-    solve(input, 75)
+    try_solve_2::<STONES>(input).unwrap()
 }
 
 #[cfg(feature = "input")]
@@ -123,6 +140,23 @@ pub fn part_2() -> u64 {
     solve_2(INPUT)
 }
 
+pub struct Day<const STONES: usize = { crate::STONES }>;
+
+impl<const STONES: usize> solution::Solution for Day<STONES> {
+    const DAY: u32 = 11;
+
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<STONES>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<STONES>(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -142,6 +176,21 @@ mod tests {
 
     #[test]
     fn test_solve() {
-        assert_eq!(solve(INPUT, 25), 55312);
+        assert_eq!(solve::<STONES>(INPUT, 25), Ok(55312));
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(try_solve_1::<STONES>("125 x"), Err(Error::Parse));
+    }
+
+    #[test]
+    fn custom_capacity() {
+        assert_eq!(solve::<16>(INPUT, 6), Ok(22));
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(solve::<8>(INPUT, 6), Err(Error::Capacity));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -1,12 +1,17 @@
 #![no_std]
 #![allow(clippy::must_use_candidate)]
 
-use bitset::BitSet;
-use heapless::{Deque, Entry, FnvIndexMap};
+use bitset::{BitSet, GridIndex};
 
-type VecDeque<T> = Deque<T, 1024>;
-type Set<T, K> = BitSet<T, K, { BitSet::with_capacity(141 * 141) }>;
-type Map<K, V> = FnvIndexMap<K, V, 512>;
+use collections::{Deque, Entry, FnvIndexMap};
+
+use grid::Grid;
+
+use solution::{Error, OrCapacity};
+
+type VecDeque<T, const N: usize> = Deque<T, N>;
+type Set<T, K, const N: usize> = BitSet<T, K, N>;
+type Map<K, V, const N: usize> = FnvIndexMap<K, V, N>;
 
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
@@ -14,72 +19,86 @@ pub const INPUT: &str = include_str!("../../input");
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = "";
 
-fn solve<ACC, A, F>(input: &str, accumulate: A, finalize: F) -> usize
+/// Default capacity for the queue of the plots to visit.
+pub const QUEUE: usize = 1024;
+
+/// Default `BitSet` size for the visited plots.
+pub const SET_SIZE: usize = BitSet::with_capacity(141 * 141);
+
+/// Default capacity for the vertices of a region, must be a power of two.
+pub const VERTICES: usize = 512;
+
+fn solve<const QUEUE: usize, const SET_SIZE: usize, ACC, A, F>(
+    input: &str,
+    accumulate: A,
+    finalize: F,
+) -> Result<usize, Error>
 where
     ACC: Default,
-    A: Fn(&mut ACC, &(usize, usize), &(isize, isize)),
+    A: Fn(&mut ACC, &(usize, usize), &(isize, isize)) -> Result<(), Error>,
     F: Fn(ACC) -> usize,
 {
-    let map = input.as_bytes();
-    let width = map.iter().position(|&c| c == b'\n').unwrap();
-    let height = (map.len() + 1) / (width + 1);
+    let grid = Grid::new(input.as_bytes())?;
 
     let mut total = 0;
 
-    let mut visited = Set::new(|(r, c)| r * (width + 1) + c);
-    for (r, row) in map.chunks(width + 1).enumerate().take(height) {
-        for (c, &plant) in row.iter().take(width).enumerate() {
-            if visited.contains(&(r, c)).unwrap() {
-                continue;
-            }
+    let mut visited = Set::<_, _, SET_SIZE>::from_key(GridIndex::new(grid.width()));
+    for (start, plant) in grid.iter() {
+        if visited.contains(&start).or_capacity()? {
+            continue;
+        }
 
-            let mut accumulator = ACC::default();
+        let mut accumulator = ACC::default();
 
-            let mut region = Set::new(|(r, c)| r * (width + 1) + c);
-            region.insert((r, c)).unwrap();
+        let mut region = Set::<_, _, SET_SIZE>::from_key(GridIndex::new(grid.width()));
+        region.insert(start).or_capacity()?;
 
-            let mut queue = VecDeque::new();
-            queue.push_back((r, c)).unwrap();
+        let mut queue = VecDeque::<_, QUEUE>::new();
+        queue.push_back(start).or_capacity()?;
 
-            while let Some((r, c)) = queue.pop_front() {
-                for (dr, dc) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
-                    match (r.checked_add_signed(dr), c.checked_add_signed(dc)) {
-                        (Some(r), Some(c))
-                            if r < height && c < width && map[r * (width + 1) + c] == plant =>
-                        {
-                            if !region.insert((r, c)).unwrap() {
-                                queue.push_back((r, c)).unwrap();
-                                visited.insert((r, c)).unwrap();
-                            }
+        while let Some(position) = queue.pop_front() {
+            for delta in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
+                match grid.get_offset(position, delta) {
+                    Some((next, tile)) if tile == plant => {
+                        if !region.insert(next).or_capacity()? {
+                            queue.push_back(next).or_capacity()?;
+                            visited.insert(next).or_capacity()?;
                         }
-                        _ => accumulate(&mut accumulator, &(r, c), &(dr, dc)),
                     }
+                    _ => accumulate(&mut accumulator, &position, &delta)?,
                 }
             }
+        }
 
-            let multiplier = finalize(accumulator);
+        let multiplier = finalize(accumulator);
 
-            total += region.len() * multiplier;
-        }
+        total += region.len() * multiplier;
     }
 
-    total
+    Ok(total)
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> usize {
-    solve::<usize, _, _>(
+pub fn try_solve_1<const QUEUE: usize, const SET_SIZE: usize>(input: &str) -> Result<usize, Error> {
+    solve::<QUEUE, SET_SIZE, usize, _, _>(
         input,
-        |perimeter, _, _| *perimeter += 1,
+        |perimeter, _, _| {
+            *perimeter += 1;
+            Ok(())
+        },
         |perimeter| perimeter,
     )
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[allow(clippy::cast_possible_wrap)]
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> usize {
+pub fn try_solve_2<const QUEUE: usize, const SET_SIZE: usize, const VERTICES: usize>(
+    input: &str,
+) -> Result<usize, Error> {
     fn key(g: (isize, isize)) -> usize {
         match g {
             (0, 1) => 0,
@@ -90,7 +109,7 @@ pub fn solve_2(input: &str) -> usize {
         }
     }
 
-    solve::<Map<(isize, isize), u8>, _, _>(
+    solve::<QUEUE, SET_SIZE, Map<(isize, isize), u8, VERTICES>, _, _>(
         input,
         |vertex, &(r, c), &(dr, dc)| {
             let (r, c) = ((r as isize) * 2, (c as isize) * 2);
@@ -100,10 +119,12 @@ pub fn solve_2(input: &str) -> usize {
                         *v.get_mut() |= 1 << key((dr, dc));
                     }
                     Entry::Vacant(v) => {
-                        v.insert(1 << key((dr, dc))).unwrap();
+                        v.insert(1 << key((dr, dc))).or_capacity()?;
                     }
                 }
             }
+
+            Ok(())
         },
         |vertex| {
             vertex
@@ -112,13 +133,25 @@ pub fn solve_2(input: &str) -> usize {
                     1 => 0,
                     2 => 1,
                     4 => 2,
-                    _ => panic!("{v:08b}"),
+                    _ => unreachable!("{v:08b}"),
                 })
                 .sum::<usize>()
         },
     )
 }
 
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> usize {
+    try_solve_1::<QUEUE, SET_SIZE>(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> usize {
+    try_solve_2::<QUEUE, SET_SIZE, VERTICES>(input).unwrap()
+}
+
 #[cfg(feature = "input")]
 pub fn part_1() -> usize {
     solve_1(INPUT)
@@ -129,6 +162,29 @@ pub fn part_2() -> usize {
     solve_2(INPUT)
 }
 
+pub struct Day<
+    const QUEUE: usize = { crate::QUEUE },
+    const SET_SIZE: usize = { crate::SET_SIZE },
+    const VERTICES: usize = { crate::VERTICES },
+>;
+
+impl<const QUEUE: usize, const SET_SIZE: usize, const VERTICES: usize> solution::Solution
+    for Day<QUEUE, SET_SIZE, VERTICES>
+{
+    const DAY: u32 = 12;
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<QUEUE, SET_SIZE>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<QUEUE, SET_SIZE, VERTICES>(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -203,4 +259,29 @@ AAAAAA";
     fn same_results_2_5() {
         assert_eq!(solve_2(INPUT_3), 1206);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            try_solve_1::<QUEUE, SET_SIZE>("AAAA\nBB"),
+            Err(Error::Parse)
+        );
+        assert_eq!(
+            try_solve_2::<QUEUE, SET_SIZE, VERTICES>("AAAA"),
+            Err(Error::Parse)
+        );
+    }
+
+    #[test]
+    fn custom_capacity() {
+        const SMALL: usize = BitSet::with_capacity(5 * 4);
+
+        assert_eq!(try_solve_1::<8, SMALL>(INPUT_1), Ok(140));
+        assert_eq!(try_solve_2::<8, SMALL, 16>(INPUT_1), Ok(80));
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(try_solve_2::<8, SMALL, 4>(INPUT_1), Err(Error::Capacity));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -4,6 +4,10 @@
 
 use core::{cmp, convert, iter, ops};
 
+use parser::Scanner;
+
+use solution::Error;
+
 #[cfg(feature = "simd")]
 pub mod simd;
 
@@ -31,63 +35,44 @@ impl Machine<i64> for Machine1BB {
     const CORRECTION: i64 = 10000000000000;
 }
 
-fn parse<T>(data: &[u8]) -> T
-where
-    T: Default,
-    T: ops::Add<Output = T> + ops::Mul<Output = T>,
-    T: convert::From<u8>,
-{
-    data.iter().fold(T::default(), |acc, digit| {
-        acc * T::from(10) + T::from(digit - b'0')
-    })
-}
-
-fn parse_machine<T>(machine: &str) -> (Coord<T>, Coord<T>, Coord<T>)
+/// Parses the lines `Button A: X+94, Y+34`, `Button B: X+22, Y+67` and
+/// `Prize: X=8400, Y=5400` of a machine.
+#[allow(clippy::type_complexity)]
+fn parse_machine<T>(machine: &str) -> Result<(Coord<T>, Coord<T>, Coord<T>), Error>
 where
     T: Default,
-    T: ops::Add<Output = T> + ops::Mul<Output = T>,
-    T: convert::From<u8>,
+    T: parser::Integer,
 {
     let mut button_a = <(T, T)>::default();
     let mut button_b = <(T, T)>::default();
     let mut prize = <(T, T)>::default();
-    for line in machine.lines() {
-        if line.starts_with("Button") {
-            let mut parts = line.split_whitespace().skip(1);
-
-            let button = parts.next().unwrap().chars().next().unwrap();
-            let x = {
-                let data = parts.next().unwrap().as_bytes();
-                parse(&data[2..data.len() - 1])
-            };
-            let y = parse(&parts.next().unwrap().as_bytes()[2..]);
-
-            if button == 'A' {
-                button_a = (x, y);
-            } else {
-                button_b = (x, y);
+    for mut line in Scanner::new(machine).lines() {
+        if line.eat("Button ") {
+            let button = line.bump();
+            let [x, y] = line.key_values(": X+", ", Y+")?;
+            line.end()?;
+
+            match button {
+                Some(b'A') => button_a = (x, y),
+                Some(b'B') => button_b = (x, y),
+                _ => return Err(Error::Parse),
             }
-        } else if line.starts_with("Prize: ") {
-            let mut parts = line.split_whitespace().skip(1);
-
-            let x = {
-                let data = parts.next().unwrap().as_bytes();
-                parse(&data[2..data.len() - 1])
-            };
-            let y = parse(&parts.next().unwrap().as_bytes()[2..]);
+        } else if line.eat("Prize: ") {
+            let [x, y] = line.key_values("X=", ", Y=")?;
+            line.end()?;
 
             prize = (x, y);
         }
     }
 
-    (button_a, button_b, prize)
+    Ok((button_a, button_b, prize))
 }
 
-fn push_machine<M, T>(machine: &str) -> Option<T>
+fn push_machine<M, T>(machine: &str) -> Result<Option<T>, Error>
 where
     M: Machine<T>,
     T: Default,
-    T: Copy,
+    T: parser::Integer,
     T: convert::From<u8>,
     T: ops::Add<Output = T>
         + ops::Mul<Output = T>
@@ -97,7 +82,7 @@ where
         + ops::Div<Output = T>,
     T: cmp::PartialEq<T>,
 {
-    let ((ax, ay), (bx, by), (px, py)) = parse_machine::<T>(machine);
+    let ((ax, ay), (bx, by), (px, py)) = parse_machine::<T>(machine)?;
 
     let (px, py) = (px + M::CORRECTION, py + M::CORRECTION);
 
@@ -106,30 +91,31 @@ where
 
     let det = ax * by - ay * bx;
     if det == T::default() {
-        return None;
+        return Ok(None);
     }
 
     let num_a = px * by - py * bx;
     if num_a % det != T::default() {
-        return None;
+        return Ok(None);
     }
 
     let num_b = ax * py - ay * px;
     if num_b % det != T::default() {
-        return None;
+        return Ok(None);
     }
 
-    Some(num_a / det * T::from(3) + num_b / det)
+    Ok(Some(num_a / det * T::from(3) + num_b / det))
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[allow(private_bounds)]
-pub fn solve<M, T>(input: &str) -> T
+pub fn solve<M, T>(input: &str) -> Result<T, Error>
 where
     M: Machine<T>,
     T: iter::Sum,
     T: Default,
-    T: Copy,
+    T: parser::Integer,
     T: convert::From<u8>,
     T: ops::Add<Output = T>
         + ops::Mul<Output = T>
@@ -141,23 +127,37 @@ where
 {
     input
         .split("\n\n")
-        .filter_map(|line| push_machine::<M, T>(line))
+        .map(|machine| Ok(push_machine::<M, T>(machine)?.unwrap_or_default()))
         .sum()
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> i32 {
+pub fn try_solve_1(input: &str) -> Result<i32, Error> {
     solve::<Machine0, _>(input)
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[allow(clippy::unreadable_literal)]
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> i64 {
+pub fn try_solve_2(input: &str) -> Result<i64, Error> {
     solve::<Machine1BB, _>(input)
 }
 
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> i32 {
+    try_solve_1(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> i64 {
+    try_solve_2(input).unwrap()
+}
+
 #[cfg(feature = "input")]
 pub fn part_1() -> i32 {
     solve_1(INPUT)
@@ -168,6 +168,23 @@ pub fn part_2() -> i64 {
     solve_2(INPUT)
 }
 
+pub struct Day;
+
+impl solution::Solution for Day {
+    const DAY: u32 = 13;
+
+    type Output1 = i32;
+    type Output2 = i64;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -196,7 +213,7 @@ Prize: X=18641, Y=10279";
 Button B: X+22, Y+67
 Prize: X=8400, Y=5400"
             ),
-            Some(280)
+            Ok(Some(280))
         );
     }
 
@@ -208,7 +225,7 @@ Prize: X=8400, Y=5400"
 Button B: X+67, Y+21
 Prize: X=12748, Y=12176"
             ),
-            None
+            Ok(None)
         );
     }
 
@@ -220,7 +237,7 @@ Prize: X=12748, Y=12176"
 Button B: X+84, Y+37
 Prize: X=7870, Y=6450"
             ),
-            Some(200)
+            Ok(Some(200))
         );
     }
 
@@ -232,7 +249,7 @@ Prize: X=7870, Y=6450"
 Button B: X+27, Y+71
 Prize: X=18641, Y=10279"
             ),
-            None
+            Ok(None)
         );
     }
 
@@ -240,4 +257,25 @@ Prize: X=18641, Y=10279"
     fn same_results_1() {
         assert_eq!(solve_1(INPUT), 480);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            try_solve_1("Button A: X+94, Y+34\nButton B: X+22, Y+x\nPrize: X=8400, Y=5400"),
+            Err(Error::ParseAt {
+                line: 2,
+                column: 19
+            })
+        );
+        assert_eq!(
+            try_solve_2("Button A: X+94\n"),
+            Err(Error::ParseAt {
+                line: 1,
+                column: 15
+            })
+        );
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
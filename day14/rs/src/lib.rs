@@ -1,7 +1,11 @@
 #![no_std]
 #![allow(clippy::must_use_candidate)]
 
-use heapless::{String as HLString, Vec as HLVec};
+use collections::Vec as HLVec;
+
+use parser::Scanner;
+
+use solution::{Error, OrCapacity};
 
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
@@ -12,8 +16,10 @@ pub const INPUT: &str = "";
 pub const WIDTH: i32 = 101;
 pub const HEIGHT: i32 = 103;
 
-type String = HLString<8>;
-type Vec<T> = HLVec<T, 512>;
+/// Default capacity for the robots.
+pub const ROBOTS: usize = 512;
+
+type Vec<T, const N: usize> = HLVec<T, N>;
 
 #[derive(Debug)]
 pub struct Robot {
@@ -27,70 +33,63 @@ impl Robot {
     }
 }
 
-fn parse(data: &str) -> (i32, i32) {
-    let mut parts = data.split(',');
-
-    (
-        parts
-            .next()
-            .unwrap()
-            .chars()
-            .skip(2)
-            .collect::<String>()
-            .parse::<i32>()
-            .unwrap(),
-        parts.next().unwrap().parse::<i32>().unwrap(),
-    )
+/// Parses `p=0,4 v=3,-3`.
+fn parse(mut line: Scanner) -> Result<Robot, Error> {
+    let [px, py] = line.key_values("p=", ",")?;
+    line.skip_spaces();
+    let [vx, vy] = line.key_values("v=", ",")?;
+    line.end()?;
+
+    Ok(Robot::new((px, py), (vx, vy)))
+}
+
+/// # Errors
+/// Every item is [`Error::ParseAt`] for a malformed line.
+pub fn try_robots(input: &str) -> impl Iterator<Item = Result<Robot, Error>> {
+    Scanner::new(input).lines().map(parse)
 }
 
 /// # Panics
+/// Panics on a malformed line.
 pub fn robots(input: &str) -> impl Iterator<Item = Robot> {
-    input.lines().map(|line| {
-        let mut parts = line.split_whitespace().map(parse);
-        let (px, py) = parts.next().unwrap();
-        let (vx, vy) = parts.next().unwrap();
-
-        Robot::new((px, py), (vx, vy))
-    })
+    try_robots(input).map(Result::unwrap)
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[allow(clippy::cast_sign_loss)]
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1<const WIDTH: i32, const HEIGHT: i32>(input: &str) -> usize {
-    robots(input)
-        .fold(
-            [0, 0, 0, 0],
-            |mut acc,
-             Robot {
-                 position: (px, py),
-                 velocity: (vx, vy),
-             }| {
-                let (px, py) = (
-                    (px + vx * 100).rem_euclid(WIDTH),
-                    (py + vy * 100).rem_euclid(HEIGHT),
-                );
-
-                if px != WIDTH / 2 && py != HEIGHT / 2 {
-                    let (px, py) = (2 * px / WIDTH, 2 * py / HEIGHT);
-                    acc[px as usize * 2 + py as usize] += 1;
-                }
+pub fn try_solve_1<const WIDTH: i32, const HEIGHT: i32>(input: &str) -> Result<usize, Error> {
+    let mut acc = [0, 0, 0, 0];
+    for robot in try_robots(input) {
+        let Robot {
+            position: (px, py),
+            velocity: (vx, vy),
+        } = robot?;
+
+        let (px, py) = (
+            (px + vx * 100).rem_euclid(WIDTH),
+            (py + vy * 100).rem_euclid(HEIGHT),
+        );
+
+        if px != WIDTH / 2 && py != HEIGHT / 2 {
+            let (px, py) = (2 * px / WIDTH, 2 * py / HEIGHT);
+            acc[px as usize * 2 + py as usize] += 1;
+        }
+    }
 
-                acc
-            },
-        )
-        .iter()
-        .product()
+    Ok(acc.iter().product())
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> usize {
+pub fn try_solve_2<const ROBOTS: usize>(input: &str) -> Result<usize, Error> {
     const TARGET: &[u8] = b"**********";
 
-    let mut robots = Vec::new();
-    for robot in crate::robots(input) {
-        robots.push(robot).unwrap();
+    let mut robots = Vec::<_, ROBOTS>::new();
+    for robot in crate::try_robots(input) {
+        robots.push(robot?).or_capacity()?;
     }
 
     (0..10000)
@@ -111,7 +110,19 @@ pub fn solve_2(input: &str) -> usize {
             map.iter()
                 .any(|row| (0..row.len() - TARGET.len()).any(|i| row[i..].starts_with(TARGET)))
         })
-        .unwrap()
+        .ok_or(Error::Unsolvable)
+}
+
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1<const WIDTH: i32, const HEIGHT: i32>(input: &str) -> usize {
+    try_solve_1::<WIDTH, HEIGHT>(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> usize {
+    try_solve_2::<ROBOTS>(input).unwrap()
 }
 
 #[cfg(feature = "input")]
@@ -124,6 +135,23 @@ pub fn part_2() -> usize {
     solve_2(INPUT)
 }
 
+pub struct Day<const ROBOTS: usize = { crate::ROBOTS }>;
+
+impl<const ROBOTS: usize> solution::Solution for Day<ROBOTS> {
+    const DAY: u32 = 14;
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<WIDTH, HEIGHT>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<ROBOTS>(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -145,4 +173,28 @@ p=9,5 v=-3,-3";
     fn same_results_1() {
         assert_eq!(solve_1::<11, 7>(INPUT), 12);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            try_solve_1::<11, 7>("p=0,4 v=3"),
+            Err(Error::ParseAt {
+                line: 1,
+                column: 10
+            })
+        );
+        assert_eq!(
+            try_solve_2::<ROBOTS>("p=0,4"),
+            Err(Error::ParseAt { line: 1, column: 6 })
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "alloc"))]
+    fn custom_capacity() {
+        assert_eq!(try_solve_2::<8>(INPUT), Err(Error::Capacity));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
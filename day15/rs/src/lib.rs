@@ -1,11 +1,13 @@
 #![no_std]
 #![allow(clippy::must_use_candidate)]
 
-use heapless::Vec as HLVec;
+use collections::Vec as HLVec;
 
-type Position = (usize, usize);
+use grid::{Dir4, Grid, GridMut, Point as Position};
 
-type Vec<T> = HLVec<T, { 64 * 128 }>;
+use solution::{Error, OrCapacity};
+
+type Vec<T, const N: usize> = HLVec<T, N>;
 
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
@@ -13,151 +15,97 @@ pub const INPUT: &str = include_str!("../../input");
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = "";
 
-fn gps_score<const TARGET: u8>(map: &[u8], &(height, width): &Position) -> usize {
-    map.chunks(width + 1)
-        .take(height)
-        .enumerate()
-        .flat_map(|(r, row)| {
-            row.iter()
-                .take(width)
-                .enumerate()
-                .filter_map(move |(c, tile)| {
-                    if *tile == TARGET {
-                        Some(r * 100 + c)
-                    } else {
-                        None
-                    }
-                })
-        })
+/// Default capacity for the map, once widened.
+pub const MAP: usize = 64 * 128;
+
+fn gps_score<const TARGET: u8>(map: &Grid) -> usize {
+    map.iter()
+        .filter_map(|((r, c), tile)| (tile == TARGET).then_some(r * 100 + c))
         .sum()
 }
 
-fn push_up_or_left<const DR: usize, const DC: usize>(
-    map: &mut [u8],
-    &(_, width): &Position,
-    &(r, c): &Position,
-) -> Position {
-    let (mut er, mut ec) = (r, c);
-    while er > 0 && ec > 0 {
-        (er, ec) = (er - DR, ec - DC);
-        if matches!(map[er * (width + 1) + ec], b'#' | b'.') {
-            break;
-        }
-    }
-
-    if map[er * (width + 1) + ec] == b'#' {
-        (r, c)
-    } else {
-        if DC == 1 {
-            for i in ec..c {
-                map[r * (width + 1) + i] = map[r * (width + 1) + (i + 1)];
-            }
-        } else {
-            for i in er..r {
-                map[i * (width + 1) + c] = map[(i + 1) * (width + 1) + c];
-            }
-        }
+/// Moves the robot a step towards `direction`, shifting the boxes in front
+/// of it if there is a free tile before the next wall.
+fn push(map: &mut GridMut, &(r, c): &Position, direction: Dir4) -> Position {
+    // the walls around the map stop the scan before its border
+    let step = |(r, c): Position, (dr, dc): (isize, isize)| {
+        (r.wrapping_add_signed(dr), c.wrapping_add_signed(dc))
+    };
 
-        (r - DR, c - DC)
+    let mut end = step((r, c), direction.delta());
+    while !matches!(map[end], b'#' | b'.') {
+        end = step(end, direction.delta());
     }
-}
 
-fn push_down_or_right<const DR: usize, const DC: usize>(
-    map: &mut [u8],
-    &(height, width): &Position,
-    &(r, c): &Position,
-) -> Position {
-    let (mut er, mut ec) = (r, c);
-    while er < height - 1 && ec < width - 1 {
-        (er, ec) = (er + DR, ec + DC);
-        if matches!(map[er * (width + 1) + ec], b'#' | b'.') {
-            break;
-        }
+    if map[end] == b'#' {
+        return (r, c);
     }
 
-    if map[er * (width + 1) + ec] == b'#' {
-        (r, c)
-    } else {
-        if DC == 1 {
-            for i in (c..ec).rev() {
-                map[r * (width + 1) + i + 1] = map[r * (width + 1) + i];
-            }
-        } else {
-            for i in (r..er).rev() {
-                map[(i + 1) * (width + 1) + c] = map[i * (width + 1) + c];
-            }
-        }
-
-        (r + DR, c + DC)
+    // the tile of the robot is free, it ends up in front of it
+    while end != (r, c) {
+        let previous = step(end, direction.opposite().delta());
+        map[end] = map[previous];
+        end = previous;
     }
+
+    step((r, c), direction.delta())
 }
 
 #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
 fn large_push_up_down_rec<const DIRECTION: isize>(
-    map: &mut [u8],
-    &(height, width): &Position,
+    map: &mut GridMut,
     &(r, c): &Position,
 ) -> Position {
-    fn can_push_box<const DIRECTION: isize>(
-        map: &[u8],
-        dim: &Position,
-        &(r, c): &Position,
-    ) -> bool {
+    fn can_push_box<const DIRECTION: isize>(map: &GridMut, &(r, c): &Position) -> bool {
         let rn = (r as isize + DIRECTION) as usize;
 
-        let (_, width) = dim;
-
         // [][]
         // .[].
-        match (map[rn * (width + 1) + c], map[rn * (width + 1) + c + 1]) {
+        match (map[(rn, c)], map[(rn, c + 1)]) {
             (b'.', b'.') => true,
-            (b'[', b']') => can_push_box::<DIRECTION>(map, dim, &(rn, c)),
+            (b'[', b']') => can_push_box::<DIRECTION>(map, &(rn, c)),
             (b']', b'[') => {
-                can_push_box::<DIRECTION>(map, dim, &(rn, c - 1))
-                    && can_push_box::<DIRECTION>(map, dim, &(rn, c + 1))
+                can_push_box::<DIRECTION>(map, &(rn, c - 1))
+                    && can_push_box::<DIRECTION>(map, &(rn, c + 1))
             }
-            (b']', b'.') => can_push_box::<DIRECTION>(map, dim, &(rn, c - 1)),
-            (b'.', b'[') => can_push_box::<DIRECTION>(map, dim, &(rn, c + 1)),
+            (b']', b'.') => can_push_box::<DIRECTION>(map, &(rn, c - 1)),
+            (b'.', b'[') => can_push_box::<DIRECTION>(map, &(rn, c + 1)),
             _ => false,
         }
     }
 
-    fn push_box<const DIRECTION: isize>(
-        map: &mut [u8],
-        &(height, width): &Position,
-        &(r, c): &Position,
-    ) {
+    fn push_box<const DIRECTION: isize>(map: &mut GridMut, &(r, c): &Position) {
         let rn = (r as isize + DIRECTION) as usize;
 
         // [][]
         // .[].
-        match (map[rn * (width + 1) + c], map[rn * (width + 1) + c + 1]) {
-            (b'[', b']') => push_box::<DIRECTION>(map, &(height, width), &(rn, c)),
+        match (map[(rn, c)], map[(rn, c + 1)]) {
+            (b'[', b']') => push_box::<DIRECTION>(map, &(rn, c)),
             (b']', b'[') => {
-                push_box::<DIRECTION>(map, &(height, width), &(rn, c - 1));
-                push_box::<DIRECTION>(map, &(height, width), &(rn, c + 1));
+                push_box::<DIRECTION>(map, &(rn, c - 1));
+                push_box::<DIRECTION>(map, &(rn, c + 1));
             }
-            (b']', b'.') => push_box::<DIRECTION>(map, &(height, width), &(rn, c - 1)),
-            (b'.', b'[') => push_box::<DIRECTION>(map, &(height, width), &(rn, c + 1)),
+            (b']', b'.') => push_box::<DIRECTION>(map, &(rn, c - 1)),
+            (b'.', b'[') => push_box::<DIRECTION>(map, &(rn, c + 1)),
             _ => {}
         }
 
-        (map[rn * (width + 1) + c], map[rn * (width + 1) + c + 1]) = (b'[', b']');
+        (map[(rn, c)], map[(rn, c + 1)]) = (b'[', b']');
 
-        (map[r * (width + 1) + c], map[r * (width + 1) + c + 1]) = (b'.', b'.');
+        (map[(r, c)], map[(r, c + 1)]) = (b'.', b'.');
     }
 
     let rn = (r as isize + DIRECTION) as usize;
 
     // last row are walls
-    match map[rn * (width + 1) + c] {
+    match map[(rn, c)] {
         b'.' => (rn, c),
-        b']' if can_push_box::<DIRECTION>(map, &(height, width), &(rn, c - 1)) => {
-            push_box::<DIRECTION>(map, &(height, width), &(rn, c - 1));
+        b']' if can_push_box::<DIRECTION>(map, &(rn, c - 1)) => {
+            push_box::<DIRECTION>(map, &(rn, c - 1));
             (rn, c)
         }
-        b'[' if can_push_box::<DIRECTION>(map, &(height, width), &(rn, c)) => {
-            push_box::<DIRECTION>(map, &(height, width), &(rn, c));
+        b'[' if can_push_box::<DIRECTION>(map, &(rn, c)) => {
+            push_box::<DIRECTION>(map, &(rn, c));
             (rn, c)
         }
         _ => (r, c),
@@ -166,78 +114,84 @@ fn large_push_up_down_rec<const DIRECTION: isize>(
 
 #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
 fn large_push_up_down_bfs<const DIRECTION: isize>(
-    map: &mut [u8],
-    &(_, width): &Position,
+    map: &mut GridMut,
     &(r, c): &Position,
-) -> Position {
-    use heapless::Deque as HLDeque;
+) -> Result<Position, Error> {
+    use collections::Deque as HLDeque;
 
     type Queue<T> = HLDeque<T, 64>;
 
+    let width = map.width();
+
     let mut list = [0_u128; 64];
 
-    let bfs = |map: &[u8], list: &mut [u128], &(r, c): &Position| {
+    let bfs = |map: &GridMut, list: &mut [u128], &(r, c): &Position| {
         let mut max_level = usize::MIN;
 
         let mut queue = Queue::new();
-        queue.push_back(((r, c), 0)).unwrap();
+        queue.push_back(((r, c), 0)).or_capacity()?;
 
         while let Some(((r, c), level)) = queue.pop_back() {
             max_level = max_level.max(level);
 
             list[level] |= 1 << c;
 
+            // a stack of boxes as high as the tracked levels
+            if level + 1 == list.len() {
+                return Err(Error::Capacity);
+            }
+
             let rn = (r as isize + DIRECTION) as usize;
 
             // [][]
             // .[].
-            match (map[rn * (width + 1) + c], map[rn * (width + 1) + c + 1]) {
-                (b'.', b'.') => continue,
+            match (map[(rn, c)], map[(rn, c + 1)]) {
+                (b'.', b'.') => {}
                 (b'[', b']') => {
                     if list[level + 1] & 1 << c == 0 {
                         list[level + 1] |= 1 << c;
-                        queue.push_back(((rn, c), level + 1)).unwrap();
+                        queue.push_back(((rn, c), level + 1)).or_capacity()?;
                     }
                 }
                 (b']', b'[') => {
                     if list[level + 1] & 1 << (c - 1) == 0 {
                         list[level + 1] |= 1 << (c - 1);
-                        queue.push_back(((rn, c - 1), level + 1)).unwrap();
+                        queue.push_back(((rn, c - 1), level + 1)).or_capacity()?;
                     }
                     if list[level + 1] & 1 << (c + 1) == 0 {
                         list[level + 1] |= 1 << (c + 1);
-                        queue.push_back(((rn, c + 1), level + 1)).unwrap();
+                        queue.push_back(((rn, c + 1), level + 1)).or_capacity()?;
                     }
                 }
                 (b']', b'.') => {
                     if list[level + 1] & 1 << (c - 1) == 0 {
                         list[level + 1] |= 1 << (c - 1);
-                        queue.push_back(((rn, c - 1), level + 1)).unwrap();
+                        queue.push_back(((rn, c - 1), level + 1)).or_capacity()?;
                     }
                 }
                 (b'.', b'[') => {
                     if list[level + 1] & 1 << (c + 1) == 0 {
                         list[level + 1] |= 1 << (c + 1);
-                        queue.push_back(((rn, c + 1), level + 1)).unwrap();
+                        queue.push_back(((rn, c + 1), level + 1)).or_capacity()?;
                     }
                 }
-                _ => return None,
+                _ => return Ok(None),
             }
         }
 
-        Some(max_level + 1)
+        Ok(Some(max_level + 1))
     };
 
-    let push = |map: &mut [u8], list: &[u128], r| {
+    let push = |map: &mut GridMut, list: &[u128], r| {
         for (i, set) in list.iter().enumerate().rev() {
             for c in 0..width {
                 if set & (1 << c) != 0 {
                     let rd = (r as isize + DIRECTION * (i as isize + 1)) as usize;
                     let rs = (r as isize + DIRECTION * i as isize) as usize;
 
-                    (map[rd * (width + 1) + c], map[rd * (width + 1) + c + 1]) = (b'[', b']');
+                    (map[(rd, c)], map[(rd, c + 1)]) = (b'[', b']');
 
-                    (map[rs * (width + 1) + c], map[rs * (width + 1) + c + 1]) = (b'.', b'.');
+                    (map[(rs, c)], map[(rs, c + 1)]) = (b'.', b'.');
                 }
             }
         }
@@ -246,10 +200,10 @@ fn large_push_up_down_bfs<const DIRECTION: isize>(
     let rn = (r as isize + DIRECTION) as usize;
 
     // last row are walls
-    match map[rn * (width + 1) + c] {
+    Ok(match map[(rn, c)] {
         b'.' => (rn, c),
         b']' => {
-            if let Some(level) = bfs(map, &mut list, &(rn, c - 1)) {
+            if let Some(level) = bfs(map, &mut list, &(rn, c - 1))? {
                 push(map, &list[0..level], rn);
 
                 (rn, c)
@@ -258,7 +212,7 @@ fn large_push_up_down_bfs<const DIRECTION: isize>(
             }
         }
         b'[' => {
-            if let Some(level) = bfs(map, &mut list, &(rn, c)) {
+            if let Some(level) = bfs(map, &mut list, &(rn, c))? {
                 push(map, &list[0..level], rn);
 
                 (rn, c)
@@ -267,114 +221,155 @@ fn large_push_up_down_bfs<const DIRECTION: isize>(
             }
         }
         _ => (r, c),
-    }
+    })
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> usize {
-    solve::<b'O'>(
+pub fn try_solve_1<const MAP: usize>(input: &str) -> Result<usize, Error> {
+    solve::<b'O', MAP>(
         input,
-        |data| data.iter().copied().collect::<Vec<_>>(),
-        push_up_or_left::<1, 0>,
-        push_down_or_right::<1, 0>,
+        |data, map| map.extend_from_slice(data).or_capacity(),
+        |map, position| Ok(push(map, position, Dir4::Up)),
+        |map, position| Ok(push(map, position, Dir4::Down)),
     )
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2_rec(input: &str) -> usize {
-    solve::<b'['>(
+pub fn try_solve_2_rec<const MAP: usize>(input: &str) -> Result<usize, Error> {
+    solve::<b'[', MAP>(
         input,
-        |data| {
-            data.iter()
-                .flat_map(|&tile| match tile {
-                    b'#' => [b'#', b'#'].as_slice(),
-                    b'O' => [b'[', b']'].as_slice(),
-                    b'.' => [b'.', b'.'].as_slice(),
-                    b'@' => [b'@', b'.'].as_slice(),
-                    b'\n' => [b'\n'].as_slice(),
-                    _ => panic!("invalid char: {tile}"),
-                })
-                .copied()
-                .collect::<Vec<_>>()
-        },
-        large_push_up_down_rec::<-1>,
-        large_push_up_down_rec::<1>,
+        widen::<MAP>,
+        |map, position| Ok(large_push_up_down_rec::<-1>(map, position)),
+        |map, position| Ok(large_push_up_down_rec::<1>(map, position)),
     )
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2_bfs(input: &str) -> usize {
-    solve::<b'['>(
+pub fn try_solve_2_bfs<const MAP: usize>(input: &str) -> Result<usize, Error> {
+    solve::<b'[', MAP>(
         input,
-        |data| {
-            data.iter()
-                .flat_map(|&tile| match tile {
-                    b'#' => [b'#', b'#'].as_slice(),
-                    b'O' => [b'[', b']'].as_slice(),
-                    b'.' => [b'.', b'.'].as_slice(),
-                    b'@' => [b'@', b'.'].as_slice(),
-                    b'\n' => [b'\n'].as_slice(),
-                    _ => panic!("invalid char: {tile}"),
-                })
-                .copied()
-                .collect::<Vec<_>>()
-        },
+        widen::<MAP>,
         large_push_up_down_bfs::<-1>,
         large_push_up_down_bfs::<1>,
     )
 }
 
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> usize {
+    try_solve_1::<MAP>(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2_rec`] fails.
+pub fn solve_2_rec(input: &str) -> usize {
+    try_solve_2_rec::<MAP>(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2_bfs`] fails.
+pub fn solve_2_bfs(input: &str) -> usize {
+    try_solve_2_bfs::<MAP>(input).unwrap()
+}
+
+// #[cfg(target_os = "none")]
+// pub use try_solve_2_bfs as try_solve_2;
+
+// #[cfg(not(target_os = "none"))]
+pub use try_solve_2_rec as try_solve_2;
+
 // #[cfg(target_os = "none")]
 // pub use solve_2_bfs as solve_2;
 
 // #[cfg(not(target_os = "none"))]
 pub use solve_2_rec as solve_2;
 
-/// # Panics
-fn solve<const TARGET: u8>(
+fn widen<const MAP: usize>(data: &[u8], map: &mut Vec<u8, MAP>) -> Result<(), Error> {
+    for &tile in data {
+        let tiles = match tile {
+            b'#' => [b'#', b'#'].as_slice(),
+            b'O' => [b'[', b']'].as_slice(),
+            b'.' => [b'.', b'.'].as_slice(),
+            b'@' => [b'@', b'.'].as_slice(),
+            b'\n' => [b'\n'].as_slice(),
+            _ => return Err(Error::Parse),
+        };
+        map.extend_from_slice(tiles).or_capacity()?;
+    }
+
+    Ok(())
+}
+
+/// Checks that the map is enclosed by walls and contains only known tiles,
+/// so the pushes never leave it.
+fn check_map(map: &Grid) -> Result<(), Error> {
+    let (height, width) = (map.height(), map.width());
+
+    if width < 2 || height < 2 {
+        return Err(Error::Parse);
+    }
+
+    // the bfs tracks boxes of a row in a `u128`
+    if width > 128 {
+        return Err(Error::Capacity);
+    }
+
+    for ((r, c), tile) in map.iter() {
+        let border = r == 0 || c == 0 || r == height - 1 || c == width - 1;
+        match tile {
+            b'#' => {}
+            b'.' | b'O' | b'[' | b']' | b'@' if !border => {}
+            _ => return Err(Error::Parse),
+        }
+    }
+
+    Ok(())
+}
+
+fn solve<const TARGET: u8, const MAP: usize>(
     input: &str,
-    make_map: impl Fn(&[u8]) -> Vec<u8>,
-    push_up: impl Fn(&mut [u8], &Position, &Position) -> Position,
-    push_down: impl Fn(&mut [u8], &Position, &Position) -> Position,
-) -> usize {
+    make_map: impl Fn(&[u8], &mut Vec<u8, MAP>) -> Result<(), Error>,
+    push_up: impl Fn(&mut GridMut, &Position) -> Result<Position, Error>,
+    push_down: impl Fn(&mut GridMut, &Position) -> Result<Position, Error>,
+) -> Result<usize, Error> {
     let mut parts = input.split("\n\n");
 
-    let mut map = make_map(parts.next().unwrap().as_bytes());
-
-    let width = map.iter().position(|&c| c == b'\n').unwrap();
-    let height = (map.len() + 1) / (width + 1);
-
-    let (mut r, mut c) = map
-        .chunks(width + 1)
-        .take(height)
-        .enumerate()
-        .find_map(|(r, row)| {
-            row.iter().take(width).enumerate().find_map(|(c, tile)| {
-                if *tile == b'@' {
-                    Some((r, c))
-                } else {
-                    None
-                }
-            })
-        })
-        .unwrap();
+    let mut map = Vec::new();
+    make_map(
+        parts.next().ok_or(Error::Parse)?.trim_end().as_bytes(),
+        &mut map,
+    )?;
+
+    let mut map = GridMut::new(&mut map)?;
+
+    check_map(&map.as_grid())?;
 
-    map[r * (width + 1) + c] = b'.';
+    let (mut r, mut c) = map.as_grid().find(b'@').ok_or(Error::Parse)?;
 
-    for m in parts.next().unwrap().lines().flat_map(|line| line.chars()) {
+    map[(r, c)] = b'.';
+
+    for m in parts
+        .next()
+        .ok_or(Error::Parse)?
+        .lines()
+        .flat_map(|line| line.chars())
+    {
         match m {
-            '^' => (r, c) = push_up(&mut map, &(height, width), &(r, c)),
-            '<' => (r, c) = push_up_or_left::<0, 1>(&mut map, &(height, width), &(r, c)),
-            '>' => (r, c) = push_down_or_right::<0, 1>(&mut map, &(height, width), &(r, c)),
-            'v' => (r, c) = push_down(&mut map, &(height, width), &(r, c)),
-            _ => panic!("invalid move {m}"),
+            '^' => (r, c) = push_up(&mut map, &(r, c))?,
+            '<' => (r, c) = push(&mut map, &(r, c), Dir4::Left),
+            '>' => (r, c) = push(&mut map, &(r, c), Dir4::Right),
+            'v' => (r, c) = push_down(&mut map, &(r, c))?,
+            _ => return Err(Error::Parse),
         }
     }
 
-    gps_score::<TARGET>(&map, &(height, width))
+    Ok(gps_score::<TARGET>(&map.as_grid()))
 }
 
 #[cfg(feature = "input")]
@@ -387,6 +382,23 @@ pub fn part_2() -> usize {
     solve_2(INPUT)
 }
 
+pub struct Day<const MAP: usize = { crate::MAP }>;
+
+impl<const MAP: usize> solution::Solution for Day<MAP> {
+    const DAY: u32 = 15;
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<MAP>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<MAP>(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -416,7 +428,7 @@ mod tests {
 #OO....OO#
 ##########";
 
-        assert_eq!(gps_score::<b'O'>(input.as_slice(), &(10, 10)), 10092);
+        assert_eq!(gps_score::<b'O'>(&Grid::new(input).unwrap()), 10092);
     }
 
     #[test]
@@ -433,7 +445,7 @@ mod tests {
         let mut input = target.to_vec();
 
         assert_eq!(
-            push_up_or_left::<1, 0>(&mut input, &(8, 8), &(2, 2)),
+            push(&mut GridMut::new(&mut input).unwrap(), &(2, 2), Dir4::Up),
             (1, 2)
         );
         assert_eq!(
@@ -458,7 +470,7 @@ mod tests {
         let mut input = target.to_vec();
 
         assert_eq!(
-            push_down_or_right::<1, 0>(&mut input, &(8, 8), &(2, 2)),
+            push(&mut GridMut::new(&mut input).unwrap(), &(2, 2), Dir4::Down),
             (3, 2)
         );
         assert_eq!(
@@ -483,7 +495,7 @@ mod tests {
         let mut input = target.to_vec();
 
         assert_eq!(
-            push_up_or_left::<0, 1>(&mut input, &(8, 8), &(2, 2)),
+            push(&mut GridMut::new(&mut input).unwrap(), &(2, 2), Dir4::Left),
             (2, 2)
         );
         assert_eq!(
@@ -517,7 +529,7 @@ mod tests {
 ########";
 
         assert_eq!(
-            push_down_or_right::<0, 1>(&mut input, &(8, 8), &(2, 2)),
+            push(&mut GridMut::new(&mut input).unwrap(), &(2, 2), Dir4::Right),
             (2, 3)
         );
         assert_eq!(
@@ -551,6 +563,51 @@ mod tests {
     #[cfg(feature = "input")]
     #[test]
     fn same_results_2() {
+        if answers::is_locked(INPUT) {
+            return;
+        }
+
         assert_eq!(solve_2_rec(INPUT), solve_2_bfs(INPUT));
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            try_solve_1::<MAP>("####\n#@.#\n####\n\n<x"),
+            Err(Error::Parse)
+        );
+        assert_eq!(
+            try_solve_2::<MAP>("####\n#@.\n####\n\n<"),
+            Err(Error::Parse)
+        );
+        assert_eq!(try_solve_2::<MAP>("####\n#@.#\n####"), Err(Error::Parse));
+    }
+
+    #[test]
+    fn custom_capacity() {
+        assert_eq!(try_solve_1::<{ 10 * 11 }>(INPUT_2), Ok(10092));
+        assert_eq!(try_solve_2_bfs::<{ 10 * 21 }>(INPUT_2), Ok(9021));
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(
+            try_solve_2_rec::<{ 10 * 11 }>(INPUT_2),
+            Err(Error::Capacity)
+        );
+    }
+
+    #[test]
+    fn tall_stack() {
+        // more boxes in a column than the levels tracked by the bfs
+        let mut input = collections::String::<1024>::new();
+        input.push_str("#####\n").unwrap();
+        for _ in 0..70 {
+            input.push_str("#.O.#\n").unwrap();
+        }
+        input.push_str("#.@.#\n#####\n\n^").unwrap();
+
+        assert_eq!(try_solve_2_bfs::<MAP>(&input), Err(Error::Capacity));
+        assert!(try_solve_2_rec::<MAP>(&input).is_ok());
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
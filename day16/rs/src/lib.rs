@@ -1,11 +1,13 @@
 #![no_std]
 #![allow(clippy::must_use_candidate)]
 
-use core::cmp;
+use bitset::BitSet;
 
-use heapless::{binary_heap, BinaryHeap as HLBinaryHeap, Vec as HLVec};
+use grid::{BoundedPoint, Dir4, Grid, Point as Position};
 
-use bitset::BitSet;
+use search::{Distances, Table, Visited};
+
+use solution::{Error, OrCapacity};
 
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
@@ -13,315 +15,194 @@ pub const INPUT: &str = include_str!("../../input");
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = "";
 
-const DIRECTIONS: [(i8, i8); 4] = [(0, 1), (0, -1), (1, 0), (-1, 0)];
+/// The side of the puzzle maze, walls included.
+const PUZZLE_SIZE: usize = 141;
 
-const WIDTH: usize = 140;
-const HEIGHT: usize = 140;
-const DIMENSION: usize = 5;
+/// Default capacity for the dijkstra queue.
+pub const QUEUE: usize = 1024 * 4;
 
-type Queue<T> = HLBinaryHeap<T, binary_heap::Max, { 1024 * 4 }>;
-type Stack<T> = HLVec<T, 512>;
+/// Default capacity for the stack of the walk back.
+pub const STACK: usize = 512;
 
-#[derive(Debug, Eq, PartialEq)]
-struct Node {
-    cost: u32,
-    position: (u8, u8),
-    direction: (i8, i8),
-}
+/// Default size of the costs table, four directions for every tile inside
+/// the walls of the puzzle maze.
+pub const COSTS: usize = (PUZZLE_SIZE - 2) * (PUZZLE_SIZE - 2) * 4;
 
-impl Ord for Node {
-    fn cmp(&self, other: &Self) -> cmp::Ordering {
-        other.cost.cmp(&self.cost)
-    }
-}
+/// Default `BitSet` size for the tiles on the best paths, one for every tile
+/// inside the walls of the puzzle maze.
+pub const VISITED_SIZE: usize = BitSet::with_capacity((PUZZLE_SIZE - 2) * (PUZZLE_SIZE - 2));
+
+/// A tile and the direction the reindeer is facing.
+type State = ((u8, u8), Dir4);
+
+/// The tiles of the states walked back from `E`.
+///
+/// The states are not pruned: the costs strictly decrease on the walk back, so
+/// it ends anyway, and a tile crossed in two directions has two ways back.
+struct Tiles<K, const SIZE: usize>(BitSet<State, K, SIZE>);
 
-impl PartialOrd for Node {
-    fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
-        Some(self.cmp(other))
+impl<K: Fn(&State) -> usize, const SIZE: usize> Visited<State> for Tiles<K, SIZE> {
+    fn visit(&mut self, state: State) -> Result<bool, Error> {
+        self.0.insert(state).or_capacity()?;
+        Ok(true)
     }
 }
 
 #[allow(clippy::cast_sign_loss, clippy::cast_possible_wrap)]
 #[inline]
-fn costs_key((r, c): (u8, u8), (dr, dc): (i8, i8)) -> usize {
-    let d = match (dr, dc) {
-        (-1, 0) => 0,
-        (1, 0) => 1,
-        (0, 1) => 2,
-        (0, -1) => 3,
-        _ => unreachable!(),
-    };
-
-    ((((r - 1) as isize * WIDTH as isize + c as isize - 1) << 2) + d) as usize
+fn costs_key(width: usize, (r, c): (u8, u8), direction: Dir4) -> usize {
+    ((((r - 1) as isize * (width - 2) as isize + c as isize - 1) << Dir4::BITS)
+        + direction.index() as isize) as usize
 }
 
-/// # Panics
-#[allow(clippy::cast_possible_truncation)]
-fn dijkstra_first(
-    costs: &mut [u32],
-    maze: &[u8],
-    &(height, width): &(usize, usize),
-    (start_r, start_c): (u8, u8),
-) -> Option<(u32, (u8, u8))> {
-    let mut queue = Queue::new();
-    queue
-        .push(Node {
-            position: (start_r, start_c),
-            direction: (0, 1),
-            cost: 0,
-        })
-        .unwrap();
-
-    while let Some(Node {
-        position: (r, c),
-        direction: (dr, dc),
-        cost,
-    }) = queue.pop()
-    {
-        if cost > costs[costs_key((r, c), (dr, dc))] {
-            continue;
-        }
-
-        if maze[r as usize * (width + 1) + c as usize] == b'E' {
-            return Some((cost, (r, c)));
-        }
-
-        for (ndr, ndc) in DIRECTIONS {
-            match (r.checked_add_signed(ndr), c.checked_add_signed(ndc)) {
-                (Some(r), Some(c))
-                    if (r as usize) < height
-                        && (c as usize) < width
-                        && maze[r as usize * (width + 1) + c as usize] != b'#' =>
-                {
-                    let neighbor_cost = if (ndr, ndc) == (dr, dc) {
-                        0
-                    } else if ndr == -dr || ndc == -dc {
-                        continue;
-                    } else {
-                        1000
-                    } + cost
-                        + 1;
-
-                    let e = &mut costs[costs_key((r, c), (ndr, ndc))];
-                    if *e > neighbor_cost {
-                        // I need only the first best
-                        *e = neighbor_cost;
-                        queue
-                            .push(Node {
-                                position: (r, c),
-                                direction: (ndr, ndc),
-                                cost: neighbor_cost,
-                            })
-                            .unwrap();
-                    }
-                }
-                _ => {}
-            }
-        }
+/// The cost of a step facing `to` after facing `from`, the reindeer cannot
+/// turn back.
+fn step_cost(from: Dir4, to: Dir4) -> Option<u32> {
+    if to == from {
+        Some(1)
+    } else if to == from.opposite() {
+        None
+    } else {
+        Some(1001)
     }
+}
 
-    None
+#[allow(clippy::cast_possible_truncation)]
+fn moves<'a>(
+    maze: &Grid<'a>,
+    ((r, c), direction): State,
+) -> impl Iterator<Item = (State, u32)> + use<'a> {
+    let maze = *maze;
+    Dir4::ALL.into_iter().filter_map(move |next_direction| {
+        let cost = step_cost(direction, next_direction)?;
+        let ((r, c), tile) = maze.get_offset((r.into(), c.into()), next_direction.delta())?;
+        (tile != b'#').then_some((((r as u8, c as u8), next_direction), cost))
+    })
 }
 
-/// # Panics
 #[allow(clippy::cast_possible_truncation)]
-#[allow(dead_code)]
-fn dijkstra_full(
+fn moves_back(
+    (height, width): (usize, usize),
+    ((r, c), direction): State,
+) -> impl Iterator<Item = (State, u32)> {
+    let position = BoundedPoint::new((r.into(), c.into()), (height, width));
+    Dir4::ALL.into_iter().filter_map(move |previous_direction| {
+        let cost = step_cost(previous_direction, direction)?;
+        let (r, c) = direction.opposite().checked_step(position?)?.point();
+        Some((((r as u8, c as u8), previous_direction), cost))
+    })
+}
+
+#[allow(clippy::cast_possible_truncation)]
+fn dijkstra<const QUEUE: usize>(
     costs: &mut [u32],
-    maze: &[u8],
-    &(height, width): &(usize, usize),
-    (start_r, start_c): (u8, u8),
-) -> Option<(u32, (u8, u8))> {
-    let mut result = None;
-
-    let mut queue = Queue::new();
-    queue
-        .push(Node {
-            position: (start_r, start_c),
-            direction: (0, 1),
-            cost: 0,
-        })
-        .unwrap();
-
-    while let Some(Node {
-        position: (r, c),
-        direction: (dr, dc),
-        cost,
-    }) = queue.pop()
-    {
-        if cost > costs[costs_key((r, c), (dr, dc))] {
-            continue;
-        }
+    maze: &Grid,
+    start: (u8, u8),
+) -> Result<(u32, (u8, u8)), Error> {
+    let mut costs = Table::new(costs, |&(position, direction): &State| {
+        costs_key(maze.width(), position, direction)
+    });
+
+    let ((end, _), cost) = search::dijkstra::<_, _, _, _, QUEUE>(
+        (start, Dir4::Right),
+        &mut costs,
+        |&state| moves(maze, state),
+        |&((r, c), _)| maze[(r.into(), c.into())] == b'E',
+    )?
+    .ok_or(Error::Unsolvable)?;
 
-        if maze[r as usize * (width + 1) + c as usize] == b'E' {
-            if result.is_none() {
-                result = Some((cost, (r, c)));
-            }
-            continue;
-        }
+    Ok((cost, end))
+}
+
+/// Returns the maze and the position of `S`.
+///
+/// The maze must be enclosed by walls and fit the costs table, so the
+/// searches below cannot go out of bounds.
+fn parse<const COSTS: usize>(input: &str) -> Result<(Grid<'_>, Position), Error> {
+    let maze = Grid::new(input.as_bytes())?;
+    let (height, width) = (maze.height(), maze.width());
+
+    if width.saturating_sub(2) * height.saturating_sub(2) * 4 > COSTS {
+        return Err(Error::Capacity);
+    }
 
-        for (ndr, ndc) in DIRECTIONS {
-            match (r.checked_add_signed(ndr), c.checked_add_signed(ndc)) {
-                (Some(r), Some(c))
-                    if (r as usize) < height
-                        && (c as usize) < width
-                        && maze[r as usize * (width + 1) + c as usize] != b'#' =>
-                {
-                    let neighbor_cost = if (ndr, ndc) == (dr, dc) {
-                        0
-                    } else if ndr == -dr || ndc == -dc {
-                        continue;
-                    } else {
-                        1000
-                    } + cost
-                        + 1;
-
-                    let e = &mut costs[costs_key((r, c), (ndr, ndc))];
-                    if *e >= neighbor_cost {
-                        *e = neighbor_cost;
-                        queue
-                            .push(Node {
-                                position: (r, c),
-                                direction: (ndr, ndc),
-                                cost: neighbor_cost,
-                            })
-                            .unwrap();
-                    }
-                }
-                _ => {}
-            }
+    let mut start = None;
+    for ((r, c), tile) in maze.iter() {
+        let border = r == 0 || c == 0 || r == height - 1 || c == width - 1;
+        match tile {
+            b'#' => {}
+            b'S' if !border => start = Some((r, c)),
+            b'.' | b'E' if !border => {}
+            _ => return Err(Error::Parse),
         }
     }
 
-    result
+    Ok((maze, start.ok_or(Error::Parse)?))
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
 #[allow(clippy::cast_possible_truncation)]
-pub fn solve_1(input: &str) -> u32 {
-    let maze = input.as_bytes();
-    let width = maze.iter().position(|&c| c == b'\n').unwrap();
-    let height = (maze.len() + 1) / (width + 1);
-
-    let (start_r, start_c) = maze
-        .chunks(width + 1)
-        .take(height)
-        .enumerate()
-        .find_map(|(r, row)| {
-            row.iter()
-                .take(width)
-                .position(|&tile| tile == b'S')
-                .map(|c| (r, c))
-        })
-        .expect("invalid maze: cannot find `S`");
-
-    let mut costs = [u32::MAX; { WIDTH * HEIGHT * DIMENSION }];
-    costs[costs_key((start_r as u8, start_c as u8), (0i8, 1i8))] = 0u32;
-
-    let (cost, _) = dijkstra_first(
-        &mut costs,
-        maze,
-        &(height, width),
-        (start_r as u8, start_c as u8),
-    )
-    .unwrap();
+pub fn try_solve_1<const QUEUE: usize, const COSTS: usize>(input: &str) -> Result<u32, Error> {
+    let (maze, (start_r, start_c)) = parse::<COSTS>(input)?;
+
+    let mut costs = [u32::MAX; COSTS];
+
+    let (cost, _) = dijkstra::<QUEUE>(&mut costs, &maze, (start_r as u8, start_c as u8))?;
 
-    cost
+    Ok(cost)
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
 #[allow(clippy::cast_possible_truncation)]
-pub fn solve_2(input: &str) -> usize {
-    let maze = input.as_bytes();
-    let width = maze.iter().position(|&c| c == b'\n').unwrap();
-    let height = (maze.len() + 1) / (width + 1);
-
-    let (start_r, start_c) = maze
-        .chunks(width + 1)
-        .take(height)
-        .enumerate()
-        .find_map(|(r, row)| {
-            row.iter()
-                .take(width)
-                .position(|&tile| tile == b'S')
-                .map(|c| (r, c))
-        })
-        .expect("invalid maze: cannot find `S`");
-
-    let mut costs = [u32::MAX; { WIDTH * HEIGHT * DIMENSION }];
-    costs[costs_key((start_r as u8, start_c as u8), (0i8, 1i8))] = 0u32;
-
-    let (best_cost, (end_r, end_c)) = dijkstra_first(
-        &mut costs,
-        maze,
-        &(height, width),
-        (start_r as u8, start_c as u8),
-    )
-    .unwrap();
-
-    visit_back(
+pub fn try_solve_2<
+    const QUEUE: usize,
+    const STACK: usize,
+    const COSTS: usize,
+    const VISITED_SIZE: usize,
+>(
+    input: &str,
+) -> Result<usize, Error> {
+    let (maze, (start_r, start_c)) = parse::<COSTS>(input)?;
+
+    let mut costs = [u32::MAX; COSTS];
+
+    let (best_cost, end) = dijkstra::<QUEUE>(&mut costs, &maze, (start_r as u8, start_c as u8))?;
+
+    let (height, width) = (maze.height(), maze.width());
+    let key = |&(position, direction): &State| costs_key(width, position, direction);
+
+    let costs = Table::new(&mut costs[..], key);
+    let mut tiles = Tiles(BitSet::<State, _, VISITED_SIZE>::new(|state: &State| {
+        key(state) >> Dir4::BITS
+    }));
+
+    search::backtrack::<_, _, _, _, _, STACK>(
+        Dir4::ALL
+            .into_iter()
+            .map(|direction| (end, direction))
+            .filter(|state| costs.get(state) == Some(best_cost)),
         &costs,
-        &(height, width),
-        (end_r, end_c),
-        best_cost,
-        (start_r as u8, start_c as u8),
-    )
-}
+        &mut tiles,
+        |&state| moves_back((height, width), state),
+    )?;
 
-fn visit_back(
-    costs: &[u32],
-    &(_, width): &(usize, usize),
-    (end_r, end_c): (u8, u8),
-    best_cost: u32,
-    (start_r, start_c): (u8, u8),
-) -> usize {
-    let mut set =
-        BitSet::<(u8, u8), _, { BitSet::with_capacity(WIDTH * HEIGHT) }>::new(|&(r, c)| {
-            (r - 1) as usize * width + (c - 1) as usize
-        });
-
-    let mut stack = Stack::new();
-    for (dr, dc) in DIRECTIONS {
-        if costs[costs_key((end_r, end_c), (dr, dc))] == best_cost {
-            stack.push(((end_r, end_c), (dr, dc), best_cost)).unwrap();
-        }
-    }
-
-    while let Some(((r, c), (dr, dc), cost)) = stack.pop() {
-        set.insert((r, c)).unwrap();
+    Ok(tiles.0.len())
+}
 
-        let (r, c) = (
-            r.checked_add_signed(-dr).unwrap(),
-            c.checked_add_signed(-dc).unwrap(),
-        );
-        for (ndr, ndc) in DIRECTIONS {
-            let Some(cost) = cost
-                .checked_sub(if (ndr, ndc) == (dr, dc) {
-                    0
-                } else if ndr == -dr || ndc == -dc {
-                    continue;
-                } else {
-                    1000
-                })
-                .and_then(|value| value.checked_sub(1))
-            else {
-                continue;
-            };
-
-            if (r, c) == (start_r, start_c) {
-                set.insert((r, c)).unwrap();
-                continue;
-            }
-
-            if costs[costs_key((r, c), (ndr, ndc))] == cost {
-                stack.push(((r, c), (ndr, ndc), cost)).unwrap();
-            }
-        }
-    }
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> u32 {
+    try_solve_1::<QUEUE, COSTS>(input).unwrap()
+}
 
-    set.len()
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> usize {
+    try_solve_2::<QUEUE, STACK, COSTS, VISITED_SIZE>(input).unwrap()
 }
 
 #[cfg(feature = "input")]
@@ -334,6 +215,30 @@ pub fn part_2() -> usize {
     solve_2(INPUT)
 }
 
+pub struct Day<
+    const QUEUE: usize = { crate::QUEUE },
+    const STACK: usize = { crate::STACK },
+    const COSTS: usize = { crate::COSTS },
+    const VISITED_SIZE: usize = { crate::VISITED_SIZE },
+>;
+
+impl<const QUEUE: usize, const STACK: usize, const COSTS: usize, const VISITED_SIZE: usize>
+    solution::Solution for Day<QUEUE, STACK, COSTS, VISITED_SIZE>
+{
+    const DAY: u32 = 16;
+
+    type Output1 = u32;
+    type Output2 = usize;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<QUEUE, COSTS>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<QUEUE, STACK, COSTS, VISITED_SIZE>(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -390,4 +295,42 @@ mod tests {
     fn same_results_2_2() {
         assert_eq!(solve_2(INPUT_2), 64);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            try_solve_1::<QUEUE, COSTS>("####\n#SE#\n###"),
+            Err(Error::Parse)
+        );
+        assert_eq!(
+            try_solve_2::<QUEUE, STACK, COSTS, VISITED_SIZE>("####\n#.E#\n####"),
+            Err(Error::Parse)
+        );
+    }
+
+    #[test]
+    fn unsolvable() {
+        assert_eq!(
+            try_solve_1::<QUEUE, COSTS>("#####\n#S#E#\n#####"),
+            Err(Error::Unsolvable)
+        );
+    }
+
+    #[test]
+    fn custom_capacity() {
+        const SMALL: usize = BitSet::with_capacity(15 * 15);
+
+        assert_eq!(try_solve_1::<64, { 13 * 13 * 4 }>(INPUT_1), Ok(7036));
+        assert_eq!(
+            try_solve_2::<64, 16, { 13 * 13 * 4 }, SMALL>(INPUT_1),
+            Ok(45)
+        );
+        assert_eq!(
+            try_solve_1::<64, { 12 * 12 * 4 }>(INPUT_1),
+            Err(Error::Capacity)
+        );
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
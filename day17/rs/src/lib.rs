@@ -3,7 +3,11 @@
 
 use core::fmt::Write;
 
-use heapless::{String as HLString, Vec as HLVec};
+use collections::{String as HLString, Vec as HLVec};
+
+use parser::Scanner;
+
+use solution::{Error, OrCapacity};
 
 pub type String = HLString<32>;
 type Vec<T> = HLVec<T, 128>;
@@ -20,137 +24,173 @@ const A: usize = 0;
 const B: usize = 1;
 const C: usize = 2;
 
+/// Runs the program up to its next output, `None` once it halts: like the
+/// opcode, an operand past the end of the program halts it.
+///
+/// # Errors
+/// [`Error::Parse`] for the reserved combo operand `7`.
 #[allow(clippy::cast_possible_truncation)]
-fn run_to_out(pc: &mut usize, register: &mut [Integer], istructions: &[u8]) -> Option<Integer> {
+fn run_to_out(
+    pc: &mut usize,
+    register: &mut [Integer],
+    istructions: &[u8],
+) -> Result<Option<Integer>, Error> {
     let combo = |register: &[Integer], operand| match operand {
-        0..=3 => Integer::from(operand),
-        4 => register[A],
-        5 => register[B],
-        6 => register[C],
-        _ => panic!("invalid operand"),
+        0..=3 => Ok(Integer::from(operand)),
+        4 => Ok(register[A]),
+        5 => Ok(register[B]),
+        6 => Ok(register[C]),
+        _ => Err(Error::Parse),
     };
 
     let literal = |operand| Integer::from(operand);
 
-    while *pc < istructions.len() {
-        match istructions[*pc] {
+    // the divisor is larger than any register from a shift of 64
+    let divide = |numerator: Integer, shift: Integer| {
+        u32::try_from(shift)
+            .ok()
+            .and_then(|shift| Integer::checked_shl(1, shift))
+            .map_or(0, |denominator| numerator / denominator)
+    };
+
+    while let (Some(&opcode), Some(&operand)) = (istructions.get(*pc), istructions.get(*pc + 1)) {
+        match opcode {
             0 => {
                 // adv
-                register[A] /= 1 << combo(register, istructions[*pc + 1]);
-                *pc += 2;
+                register[A] = divide(register[A], combo(register, operand)?);
             }
             1 => {
                 // bxl
-                register[B] ^= literal(istructions[*pc + 1]);
-                *pc += 2;
+                register[B] ^= literal(operand);
             }
             2 => {
                 // bst
-                register[B] = combo(register, istructions[*pc + 1]) & 0b111;
-                *pc += 2;
+                register[B] = combo(register, operand)? & 0b111;
             }
             3 => {
                 // jnz
-                if register[A] == 0 {
-                    *pc += 2;
-                } else {
-                    *pc = literal(istructions[*pc + 1]) as usize;
+                if register[A] != 0 {
+                    *pc = literal(operand) as usize;
+                    continue;
                 }
             }
             4 => {
                 // bxc
                 register[B] ^= register[C];
-                *pc += 2;
             }
             5 => {
                 // out
-                let result = combo(register, istructions[*pc + 1]) % 8;
+                let result = combo(register, operand)? % 8;
                 *pc += 2;
 
-                return Some(result);
+                return Ok(Some(result));
             }
             6 => {
                 // bdv
-                register[B] = register[A] / (1 << combo(register, istructions[*pc + 1]));
-                *pc += 2;
+                register[B] = divide(register[A], combo(register, operand)?);
             }
             7 => {
                 // cdv
-                register[C] = register[A] / (1 << combo(register, istructions[*pc + 1]));
-                *pc += 2;
+                register[C] = divide(register[A], combo(register, operand)?);
             }
-            op => unreachable!("invalid instruction {op}"),
+            _ => return Err(Error::Parse),
         }
+
+        *pc += 2;
     }
 
-    None
+    Ok(None)
 }
 
-/// # Panics
+/// Parses the program, rejecting the reserved combo operand `7` up front.
+fn parse_program(line: &str) -> Result<Vec<u8>, Error> {
+    let line = line
+        .trim_end()
+        .strip_prefix("Program: ")
+        .ok_or(Error::Parse)?;
+
+    let mut istructions = Vec::new();
+    for n in line.split(',') {
+        match n.as_bytes() {
+            &[n @ b'0'..=b'7'] => istructions.push(n - b'0').or_capacity()?,
+            _ => return Err(Error::Parse),
+        }
+    }
+
+    if istructions.len() % 2 != 0
+        || istructions
+            .chunks(2)
+            .any(|op| matches!(op, [0 | 2 | 5 | 6 | 7, 7]))
+    {
+        return Err(Error::Parse);
+    }
+
+    Ok(istructions)
+}
+
+/// # Errors
+/// See [`Error`].
 #[allow(clippy::cast_possible_truncation)]
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> String {
+pub fn try_solve_1(input: &str) -> Result<String, Error> {
     let mut parts = input.split("\n\n");
 
     let mut register = [0; 3];
-    for line in parts.next().unwrap().lines() {
-        let line = line.as_bytes();
-
-        register[line[b"Register ".len()] as usize - b'A' as usize] = line
-            .iter()
-            .skip("Register A: ".len())
-            .fold(0, |acc, digit| acc * 10 + Integer::from(digit - b'0'));
+    for mut line in Scanner::new(parts.next().ok_or(Error::Parse)?).lines() {
+        line.tag("Register ")?;
+
+        let index = match line.bump() {
+            Some(b'A') => A,
+            Some(b'B') => B,
+            Some(b'C') => C,
+            _ => return Err(Error::Parse),
+        };
+
+        line.tag(": ")?;
+        register[index] = line.unsigned()?;
+        line.end()?;
     }
 
-    let istructions = {
-        let line = parts.next().unwrap();
-        let line = line.as_bytes();
-        line["Program: ".len()..]
-            .split(|&c| c == b',')
-            .map(|n| n[0] - b'0')
-            .collect::<Vec<_>>()
-    };
+    let istructions = parse_program(parts.next().ok_or(Error::Parse)?)?;
 
     let mut out = Vec::new();
     let mut pc = 0;
-    while let Some(value) = run_to_out(&mut pc, &mut register[..], &istructions) {
-        out.push(value).unwrap();
+    while let Some(value) = run_to_out(&mut pc, &mut register[..], &istructions)? {
+        out.push(value).or_capacity()?;
     }
 
     let mut result = String::new();
     for (i, value) in out.iter().enumerate() {
-        result.write_char(char::from(*value as u8 + b'0')).unwrap();
+        result
+            .write_char(char::from(*value as u8 + b'0'))
+            .or_capacity()?;
         if i != out.len() - 1 {
-            result.write_char(',').unwrap();
+            result.write_char(',').or_capacity()?;
         }
     }
 
-    result
+    Ok(result)
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> Integer {
+pub fn try_solve_2(input: &str) -> Result<Integer, Error> {
     let mut parts = input.split("\n\n").skip(1);
 
-    let istructions = {
-        let line = parts.next().unwrap();
-        let line = line.as_bytes();
-        line["Program: ".len()..]
-            .split(|&c| c == b',')
-            .map(|n| n[0] - b'0')
-            .collect::<Vec<_>>()
-    };
+    let istructions = parse_program(parts.next().ok_or(Error::Parse)?)?;
 
     let mut buffers = [Vec::new(), Vec::new()];
     buffers[0]
-        .push(Integer::from(istructions.last().copied().unwrap()))
-        .unwrap();
+        .push(Integer::from(
+            istructions.last().copied().ok_or(Error::Parse)?,
+        ))
+        .or_capacity()?;
 
     let buffer = istructions
         .iter()
         .rev()
-        .fold(0, |current_buffer_index, &istruction| {
+        .try_fold(0, |current_buffer_index, &istruction| {
             let (prev, next) = {
                 let (a, b) = buffers.split_at_mut(1);
                 if current_buffer_index == 0 {
@@ -167,18 +207,34 @@ pub fn solve_2(input: &str) -> Integer {
 
                     let mut pc = 0;
                     let mut register = [a, 0, 0];
-                    if let Some(out) = run_to_out(&mut pc, &mut register[..], &istructions) {
-                        if out == istruction.into() {
-                            next.push(a).unwrap();
-                        }
+                    if run_to_out(&mut pc, &mut register[..], &istructions)?
+                        .is_some_and(|out| out == istruction.into())
+                    {
+                        next.push(a).or_capacity()?;
                     }
                 }
             }
 
-            (current_buffer_index + 1) % 2
-        });
+            Ok::<_, Error>((current_buffer_index + 1) % 2)
+        })?;
+
+    buffers[buffer]
+        .iter()
+        .min()
+        .copied()
+        .ok_or(Error::Unsolvable)
+}
+
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> String {
+    try_solve_1(input).unwrap()
+}
 
-    buffers[buffer].iter().min().copied().unwrap()
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> Integer {
+    try_solve_2(input).unwrap()
 }
 
 #[cfg(feature = "input")]
@@ -206,6 +262,23 @@ pub fn part_2() -> Integer {
     solve_2(INPUT)
 }
 
+pub struct Day;
+
+impl solution::Solution for Day {
+    const DAY: u32 = 17;
+
+    type Output1 = String;
+    type Output2 = Integer;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -234,4 +307,38 @@ Program: 0,3,5,4,3,0";
     fn same_results_2() {
         assert_eq!(solve_2(INPUT_2), 117440);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            try_solve_1("Register A: x\n\nProgram: 0,1"),
+            Err(Error::ParseAt {
+                line: 1,
+                column: 13
+            })
+        );
+        assert_eq!(
+            try_solve_1("Register A: 1\n\nProgram: 0,7"),
+            Err(Error::Parse)
+        );
+        assert_eq!(
+            try_solve_2("Register A: 1\n\nProgram: 0,"),
+            Err(Error::Parse)
+        );
+    }
+
+    #[test]
+    fn no_panics() {
+        assert_eq!(
+            try_solve_1("Register A: 1000\n\nProgram: 0,4,5,4").as_deref(),
+            Ok("0")
+        );
+        assert_eq!(
+            try_solve_1("Register A: 1\n\nProgram: 3,1,5,4").as_deref(),
+            Ok("")
+        );
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -10,6 +10,7 @@ use day18::{
     PUZZLE_WIDTH,
     PUZZLE_HEIGHT,
     BITSET_SIZE,
+    DROPS,
 };
 
 const INPUT_213X213: &str = include_str!("../../input-213x213");
@@ -20,7 +21,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("part 2 1024");
     group.bench_function("bs/bfs", |b| {
         b.iter(|| {
-            solve_2_bs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE, 1024>(
+            solve_2_bs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE, 1024, DROPS>(
                 day18::INPUT,
                 bfs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE>,
             )
@@ -28,7 +29,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     });
     group.bench_function("bs/dfs", |b| {
         b.iter(|| {
-            solve_2_bs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE, 1024>(
+            solve_2_bs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE, 1024, DROPS>(
                 INPUT,
                 dfs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE>,
             )
@@ -39,7 +40,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("part 2 0");
     group.bench_function("bs/bfs", |b| {
         b.iter(|| {
-            solve_2_bs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE, 0>(
+            solve_2_bs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE, 0, DROPS>(
                 INPUT,
                 bfs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE>,
             )
@@ -47,7 +48,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     });
     group.bench_function("bs/dfs", |b| {
         b.iter(|| {
-            solve_2_bs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE, 0>(
+            solve_2_bs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE, 0, DROPS>(
                 INPUT,
                 dfs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE>,
             )
@@ -57,7 +58,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
     
     c.bench_function("bru", |b| b.iter(|| solve_2_bru::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE>(INPUT)));
 
-    {
+    if !INPUT_213X213.is_empty() {
         const BITSET_SIZE: usize = bitset::BitSet::with_capacity(213 * 213);
         
         let mut group = c.benchmark_group("part 2 213x213");
@@ -70,7 +71,7 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         });
         group.bench_function("bs/bfs", |b| {
             b.iter(|| {
-                solve_2_bs::<213, 213, BITSET_SIZE, 0>(
+                solve_2_bs::<213, 213, BITSET_SIZE, 0, { 213 * 213 }>(
                     INPUT_213X213,
                     bfs::<213, 213, BITSET_SIZE>,
                 )
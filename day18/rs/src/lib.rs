@@ -3,10 +3,14 @@
 
 use core::fmt::Write;
 
-use heapless::{Deque as HLDeque, String as HLString, Vec as HLVec};
+use collections::{Deque as HLDeque, String as HLString, Vec as HLVec};
 
 use bitset::BitSet;
 
+use grid::{BoundedPoint, Dir4, Dir8};
+
+use solution::{Error, OrCapacity};
+
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
 
@@ -16,13 +20,51 @@ pub const INPUT: &str = "";
 pub const PUZZLE_WIDTH: usize = 71;
 pub const PUZZLE_HEIGHT: usize = 71;
 pub const BITSET_SIZE: usize = BitSet::with_capacity(PUZZLE_WIDTH * PUZZLE_HEIGHT);
+pub const DROPS: usize = 32000;
+
+const QUEUE: usize = PUZZLE_WIDTH * 4;
+const STACK: usize = PUZZLE_WIDTH * PUZZLE_HEIGHT;
 
-type Deque<T> = HLDeque<T, { PUZZLE_WIDTH * 4 }>;
-type Vec<T> = HLVec<T, { PUZZLE_WIDTH * PUZZLE_HEIGHT }>;
-type Drops<T> = HLVec<T, 32000>;
+type Deque<T> = HLDeque<T, QUEUE>;
+type Drops<T, const N: usize> = HLVec<T, N>;
 type String = HLString<16>;
 
-/// # Panics
+fn parse_drop<const WIDTH: usize, const HEIGHT: usize>(
+    line: &str,
+) -> Result<(usize, usize), Error> {
+    let (x, y) = line.split_once(',').ok_or(Error::Parse)?;
+    let (x, y) = (x.parse::<usize>()?, y.parse::<usize>()?);
+    if x < WIDTH && y < HEIGHT {
+        Ok((x, y))
+    } else {
+        Err(Error::Parse)
+    }
+}
+
+fn neighbours<const WIDTH: usize, const HEIGHT: usize>(
+    map: &[[u8; WIDTH]; HEIGHT],
+    (x, y): (usize, usize),
+) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let position = BoundedPoint::new((y, x), (HEIGHT, WIDTH));
+    Dir4::ALL.into_iter().filter_map(move |direction| {
+        let (y, x) = direction.checked_step(position?)?.point();
+        (map[y][x] == b'.').then_some((x, y))
+    })
+}
+
+/// The tiles around `(x, y)`, diagonals included.
+fn around<const WIDTH: usize, const HEIGHT: usize>(
+    (x, y): (usize, usize),
+) -> impl Iterator<Item = (usize, usize)> {
+    let position = BoundedPoint::new((y, x), (HEIGHT, WIDTH));
+    Dir8::ALL.into_iter().filter_map(move |direction| {
+        let (y, x) = direction.checked_step(position?)?.point();
+        Some((x, y))
+    })
+}
+
+/// # Errors
+/// See [`Error`].
 pub fn solve_1_bfs<
     const WIDTH: usize,
     const HEIGHT: usize,
@@ -30,134 +72,84 @@ pub fn solve_1_bfs<
     const SIZE: usize,
 >(
     input: &str,
-) -> usize {
+) -> Result<usize, Error> {
     let mut map = [[b'.'; WIDTH]; HEIGHT];
-    for (x, y) in input
-        .lines()
-        .map(|line| {
-            let (x, y) = line.split_once(',').unwrap();
-            (x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap())
-        })
-        .take(TAKE)
-    {
+    for line in input.lines().take(TAKE) {
+        let (x, y) = parse_drop::<WIDTH, HEIGHT>(line)?;
         map[y][x] = b'#';
     }
 
-    let mut visited = BitSet::<_, _, SIZE>::new(|(x, y)| y * WIDTH + x);
-    visited.insert((0, 0)).unwrap();
-
-    let mut queue = Deque::new();
-    queue.push_back(((0, 0), 0)).unwrap();
-
-    while let Some(((x, y), steps)) = queue.pop_front() {
-        if x == WIDTH - 1 && y == HEIGHT - 1 {
-            return steps;
-        }
-
-        for (dx, dy) in [(0, 1), (0, -1), (1, 0), (-1, 0)] {
-            match (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
-                (Some(x), Some(y))
-                    if matches!(map.get(y).and_then(|row| row.get(x)), Some(b'.')) =>
-                {
-                    if !visited.insert((x, y)).unwrap() {
-                        queue.push_back(((x, y), steps + 1)).unwrap();
-                    }
-                }
-                _ => {}
-            }
-        }
-    }
-
-    unreachable!()
+    let mut visited = BitSet::<_, _, SIZE>::new(|(x, y): &(usize, usize)| y * WIDTH + x);
+    search::bfs::<_, _, _, QUEUE>(
+        (0, 0),
+        &mut visited,
+        |&position| neighbours(&map, position),
+        |&(x, y)| x == WIDTH - 1 && y == HEIGHT - 1,
+    )?
+    .map(|(_, steps)| steps)
+    .ok_or(Error::Unsolvable)
 }
 
-/// # Panics
+/// # Errors
+/// Returns [`Error::Capacity`] if the search outgrows its fixed capacity.
 pub fn bfs<const WIDTH: usize, const HEIGHT: usize, const SIZE: usize>(
     map: &[[u8; WIDTH]; HEIGHT],
-) -> bool {
-    let key = |(x, y): &(usize, usize)| y * WIDTH + x;
-
-    let mut visited = BitSet::<_, _, SIZE>::new(key);
-    visited.insert((0, 0)).unwrap();
-
-    let mut queue = Deque::new();
-    queue.push_back((0usize, 0usize)).unwrap();
-
-    while let Some((x, y)) = queue.pop_front() {
-        for (dx, dy) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
-            match (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
-                (Some(x), Some(y))
-                    if matches!(map.get(y).and_then(|row| row.get(x)), Some(b'.')) =>
-                {
-                    if x == WIDTH - 1 && y == HEIGHT - 1 {
-                        return true;
-                    }
-
-                    if !visited.insert((x, y)).unwrap() {
-                        queue.push_back((x, y)).unwrap();
-                    }
-                }
-                _ => {}
-            }
-        }
-    }
-
-    false
+) -> Result<bool, Error> {
+    let mut visited = BitSet::<_, _, SIZE>::new(|(x, y): &(usize, usize)| y * WIDTH + x);
+    search::bfs::<_, _, _, QUEUE>(
+        (0, 0),
+        &mut visited,
+        |&position| neighbours(map, position),
+        |&(x, y)| x == WIDTH - 1 && y == HEIGHT - 1,
+    )
+    .map(|found| found.is_some())
 }
 
-/// # Panics
+/// # Errors
+/// Returns [`Error::Capacity`] if the search outgrows its fixed capacity.
 pub fn dfs<const WIDTH: usize, const HEIGHT: usize, const SIZE: usize>(
     map: &[[u8; WIDTH]; HEIGHT],
-) -> bool {
-    let key = |(x, y): &(usize, usize)| y * WIDTH + x;
-
-    let mut visited = BitSet::<_, _, SIZE>::new(key);
-    visited.insert((0, 0)).unwrap();
-
-    let mut queue = Vec::new();
-    queue.push((0usize, 0usize)).unwrap();
-
-    while let Some((x, y)) = queue.pop() {
-        for (dx, dy) in [(0, 1), (1, 0), (0, -1), (-1, 0)] {
-            match (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
-                (Some(x), Some(y))
-                    if matches!(map.get(y).and_then(|row| row.get(x)), Some(b'.')) =>
-                {
-                    if x == WIDTH - 1 && y == HEIGHT - 1 {
-                        return true;
-                    }
-
-                    if !visited.insert((x, y)).unwrap() {
-                        queue.push((x, y)).unwrap();
-                    }
-                }
-                _ => {}
-            }
-        }
-    }
-
-    false
+) -> Result<bool, Error> {
+    let mut visited = BitSet::<_, _, SIZE>::new(|(x, y): &(usize, usize)| y * WIDTH + x);
+    search::dfs::<_, _, _, STACK>(
+        (0, 0),
+        &mut visited,
+        |&position| neighbours(map, position),
+        |&(x, y)| x == WIDTH - 1 && y == HEIGHT - 1,
+    )
+    .map(|found| found.is_some())
 }
 
-/// # Panics
-pub fn solve_2_bs<const WIDTH: usize, const HEIGHT: usize, const SIZE: usize, const CUT: usize>(
+/// # Errors
+/// See [`Error`].
+pub fn solve_2_bs<
+    const WIDTH: usize,
+    const HEIGHT: usize,
+    const SIZE: usize,
+    const CUT: usize,
+    const DROPS: usize,
+>(
     input: &str,
-    search: impl Fn(&[[u8; WIDTH]; HEIGHT]) -> bool,
-) -> String {
-    let mut drops = Drops::new();
-    for drop in input
-        .lines()
-        .map(|line| {
-            let (x, y) = line.split_once(',').unwrap();
-            (x.parse::<u8>().unwrap(), y.parse::<u8>().unwrap())
-        })
-    {
-        drops.push(drop).unwrap();
+    search: impl Fn(&[[u8; WIDTH]; HEIGHT]) -> Result<bool, Error>,
+) -> Result<String, Error> {
+    let mut drops = Drops::<_, DROPS>::new();
+    for line in input.lines() {
+        let (x, y) = parse_drop::<WIDTH, HEIGHT>(line)?;
+        drops
+            .push((
+                u8::try_from(x).or_capacity()?,
+                u8::try_from(y).or_capacity()?,
+            ))
+            .or_capacity()?;
+    }
+
+    if drops.len() <= CUT {
+        return Err(Error::Unsolvable);
     }
 
     let mut min = CUT;
     let mut max = drops.len();
-    let mut middle = (min + max) / 2;
+    let mut middle = min.midpoint(max);
 
     let mut grid = [[b'.'; WIDTH]; HEIGHT];
     for (x, y) in drops.iter().take(middle).copied() {
@@ -165,15 +157,15 @@ pub fn solve_2_bs<const WIDTH: usize, const HEIGHT: usize, const SIZE: usize, co
     }
 
     while min != middle {
-        if search(&grid) {
-            let new_middle = (middle + max) / 2;
+        if search(&grid)? {
+            let new_middle = middle.midpoint(max);
             for (x, y) in drops.iter().skip(middle).take(new_middle - middle).copied() {
                 grid[y as usize][x as usize] = b'#';
             }
             min = middle;
             middle = new_middle;
         } else {
-            let new_middle = (min + middle) / 2;
+            let new_middle = min.midpoint(middle);
             for (x, y) in drops
                 .iter()
                 .skip(new_middle)
@@ -190,15 +182,19 @@ pub fn solve_2_bs<const WIDTH: usize, const HEIGHT: usize, const SIZE: usize, co
     let (x, y) = drops[middle];
 
     let mut result = String::new();
-    write!(&mut result, "{x},{y}").unwrap();
+    write!(&mut result, "{x},{y}").or_capacity()?;
 
-    result
+    Ok(result)
 }
 
+/// # Errors
+/// See [`Error`].
+///
 /// # Panics
+/// Panics if `SIZE` cannot hold a `WIDTH` x `HEIGHT` grid.
 pub fn solve_2_bru<const WIDTH: usize, const HEIGHT: usize, const SIZE: usize>(
     input: &str,
-) -> String {
+) -> Result<String, Error> {
     let key = |&(x, y): &(usize, usize)| y * WIDTH + x;
 
     let mut blue = BitSet::<(usize, usize), _, SIZE>::new(key);
@@ -209,63 +205,33 @@ pub fn solve_2_bru<const WIDTH: usize, const HEIGHT: usize, const SIZE: usize>(
                colored: &mut BitSet<_, _, SIZE>,
                &(x, y): &(usize, usize)| {
         let mut queue = Deque::new();
-        queue.push_back((x, y)).unwrap();
+        queue.push_back((x, y)).or_capacity()?;
 
         while let Some((x, y)) = queue.pop_front() {
             colored.insert((x, y)).unwrap();
 
-            for (dx, dy) in [
-                (-1, -1),
-                (-1, 0),
-                (-1, 1),
-                (0, -1),
-                (0, 1),
-                (1, -1),
-                (1, 0),
-                (1, 1),
-            ] {
-                match (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
-                    (Some(x), Some(y)) if x < WIDTH && y < HEIGHT => {
-                        if uncolored.remove(&(x, y)).unwrap() && !colored.insert((x, y)).unwrap() {
-                            queue.push_back((x, y)).unwrap();
-                        }
-                    }
-                    _ => {}
+            for (x, y) in around::<WIDTH, HEIGHT>((x, y)) {
+                if uncolored.remove(&(x, y)).unwrap() && !colored.insert((x, y)).unwrap() {
+                    queue.push_back((x, y)).or_capacity()?;
                 }
             }
         }
+
+        Ok::<_, Error>(())
     };
 
     let check = |colored: &BitSet<_, _, SIZE>, &(x, y): &(usize, usize)| {
-        [
-            (-1, -1),
-            (-1, 0),
-            (-1, 1),
-            (0, -1),
-            (0, 1),
-            (1, -1),
-            (1, 0),
-            (1, 1),
-        ]
-        .into_iter()
-        .any(
-            |(dx, dy)| match (x.checked_add_signed(dx), y.checked_add_signed(dy)) {
-                (Some(x), Some(y)) if x < WIDTH && y < HEIGHT => colored.contains(&(x, y)).unwrap(),
-                _ => false,
-            },
-        )
+        around::<WIDTH, HEIGHT>((x, y)).any(|(x, y)| colored.contains(&(x, y)).unwrap())
     };
 
     let make_result = |&(x, y): &(usize, usize)| {
         let mut result = String::new();
-        write!(&mut result, "{x},{y}").unwrap();
-        result
+        write!(&mut result, "{x},{y}").or_capacity()?;
+        Ok(result)
     };
 
-    for (x, y) in input.lines().map(|line| {
-        let (x, y) = line.split_once(',').unwrap();
-        (x.parse::<usize>().unwrap(), y.parse::<usize>().unwrap())
-    }) {
+    for line in input.lines() {
+        let (x, y) = parse_drop::<WIDTH, HEIGHT>(line)?;
         debug_assert!((x, y) != (0, 0) && (x, y) != (WIDTH - 1, HEIGHT - 1));
 
         if x == 0 || y == HEIGHT - 1 {
@@ -273,7 +239,7 @@ pub fn solve_2_bru<const WIDTH: usize, const HEIGHT: usize, const SIZE: usize>(
                 return make_result(&(x, y));
             }
 
-            bfs(&mut uncolored, &mut blue, &(x, y));
+            bfs(&mut uncolored, &mut blue, &(x, y))?;
 
             continue;
         }
@@ -283,7 +249,7 @@ pub fn solve_2_bru<const WIDTH: usize, const HEIGHT: usize, const SIZE: usize>(
                 return make_result(&(x, y));
             }
 
-            bfs(&mut uncolored, &mut red, &(x, y));
+            bfs(&mut uncolored, &mut red, &(x, y))?;
 
             continue;
         }
@@ -293,10 +259,10 @@ pub fn solve_2_bru<const WIDTH: usize, const HEIGHT: usize, const SIZE: usize>(
                 return make_result(&(x, y));
             }
             (true, false) => {
-                bfs(&mut uncolored, &mut blue, &(x, y));
+                bfs(&mut uncolored, &mut blue, &(x, y))?;
             }
             (false, true) => {
-                bfs(&mut uncolored, &mut red, &(x, y));
+                bfs(&mut uncolored, &mut red, &(x, y))?;
             }
             (false, false) => {
                 uncolored.insert((x, y)).unwrap();
@@ -304,22 +270,38 @@ pub fn solve_2_bru<const WIDTH: usize, const HEIGHT: usize, const SIZE: usize>(
         }
     }
 
-    unreachable!()
+    Err(Error::Unsolvable)
 }
 
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> usize {
+pub fn try_solve_1(input: &str) -> Result<usize, Error> {
     solve_1_bfs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, 1024, BITSET_SIZE>(input)
 }
 
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> String {
-    solve_2_bs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE, 1024>(
+pub fn try_solve_2<const DROPS: usize>(input: &str) -> Result<String, Error> {
+    solve_2_bs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE, 1024, DROPS>(
         input,
         dfs::<PUZZLE_WIDTH, PUZZLE_HEIGHT, BITSET_SIZE>,
     )
 }
 
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> usize {
+    try_solve_1(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> String {
+    try_solve_2::<DROPS>(input).unwrap()
+}
+
 #[cfg(feature = "input")]
 pub fn part_1() -> usize {
     solve_1(INPUT)
@@ -330,6 +312,23 @@ pub fn part_2() -> String {
     solve_2(INPUT)
 }
 
+pub struct Day<const DROPS: usize = { crate::DROPS }>;
+
+impl<const DROPS: usize> solution::Solution for Day<DROPS> {
+    const DAY: u32 = 18;
+
+    type Output1 = usize;
+    type Output2 = String;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<DROPS>(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -364,17 +363,18 @@ mod tests {
     fn same_results_1() {
         assert_eq!(
             solve_1_bfs::<7, 7, 12, { BitSet::with_capacity(7 * 7) }>(INPUT),
-            22
+            Ok(22)
         );
     }
 
     #[test]
     fn same_results_2_bs_dfs() {
         assert_eq!(
-            &solve_2_bs::<7, 7, { BitSet::with_capacity(7 * 7) }, 0>(
+            &solve_2_bs::<7, 7, { BitSet::with_capacity(7 * 7) }, 0, 32>(
                 INPUT,
                 dfs::<7, 7, { BitSet::with_capacity(7 * 7) }>
-            ),
+            )
+            .unwrap(),
             &"6,1"
         );
     }
@@ -382,10 +382,11 @@ mod tests {
     #[test]
     fn same_results_2_bs_bfs() {
         assert_eq!(
-            &solve_2_bs::<7, 7, { BitSet::with_capacity(7 * 7) }, 0>(
+            &solve_2_bs::<7, 7, { BitSet::with_capacity(7 * 7) }, 0, 32>(
                 INPUT,
                 bfs::<7, 7, { BitSet::with_capacity(7 * 7) }>
-            ),
+            )
+            .unwrap(),
             &"6,1"
         );
     }
@@ -393,8 +394,33 @@ mod tests {
     #[test]
     fn same_results_2_bru() {
         assert_eq!(
-            &solve_2_bru::<7, 7, { BitSet::with_capacity(7 * 7) }>(INPUT),
+            &solve_2_bru::<7, 7, { BitSet::with_capacity(7 * 7) }>(INPUT).unwrap(),
             &"6,1"
         );
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(try_solve_1("1,2\n3"), Err(Error::Parse));
+        assert_eq!(try_solve_2::<DROPS>("1,2\n3,71"), Err(Error::Parse));
+        assert_eq!(
+            solve_2_bru::<7, 7, { BitSet::with_capacity(7 * 7) }>("1,x"),
+            Err(Error::Parse)
+        );
+    }
+
+    #[test]
+    #[cfg(not(feature = "alloc"))]
+    fn custom_capacity() {
+        assert_eq!(
+            solve_2_bs::<7, 7, { BitSet::with_capacity(7 * 7) }, 0, 16>(
+                INPUT,
+                bfs::<7, 7, { BitSet::with_capacity(7 * 7) }>
+            ),
+            Err(Error::Capacity)
+        );
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -1,14 +1,16 @@
 #![no_std]
 #![allow(clippy::must_use_candidate)]
 
-use heapless::{FnvIndexMap, FnvIndexSet, Vec as HLVec};
+use collections::{FnvIndexMap, FnvIndexSet, Vec as HLVec};
+
+use solution::{Error, OrCapacity};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
 type Set<T> = FnvIndexSet<T, 64>;
 type Map<K, V> = FnvIndexMap<K, V, 64>;
-type Vec<T> = HLVec<T, 4096>;
+type Vec<T, const N: usize> = HLVec<T, N>;
 
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
@@ -16,26 +18,65 @@ pub const INPUT: &str = include_str!("../../input");
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = "";
 
-/// Panics
+/// Default capacity for the towel patterns.
+pub const PATTERNS: usize = 4096;
+
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> usize {
-    solve_1_r(input)
+pub fn try_solve_1<const PATTERNS: usize>(input: &str) -> Result<usize, Error> {
+    solve_1_r::<PATTERNS>(input)
 }
 
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> u64 {
-    solve_2_r(input)
+pub fn try_solve_2<const PATTERNS: usize>(input: &str) -> Result<u64, Error> {
+    solve_2_r::<PATTERNS>(input)
 }
 
 /// # Panics
-pub fn solve_1_dp(input: &str) -> usize {
-    let (ps, designs) = input.split_once("\n\n").unwrap();
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> usize {
+    try_solve_1::<PATTERNS>(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> u64 {
+    try_solve_2::<PATTERNS>(input).unwrap()
+}
+
+/// Collects the towel patterns and returns the designs.
+///
+/// Patterns must not be empty and designs must be shorter than the
+/// 64 entries of the options tables and memoization maps.
+fn parse<'a, const PATTERNS: usize>(
+    input: &'a str,
+    patterns: &mut Vec<&'a str, PATTERNS>,
+) -> Result<&'a str, Error> {
+    let (ps, designs) = input.split_once("\n\n").ok_or(Error::Parse)?;
 
-    let mut patterns = Vec::new();
     for pattern in ps.split(", ") {
-        patterns.push(pattern).unwrap();
+        if pattern.is_empty() {
+            return Err(Error::Parse);
+        }
+        patterns.push(pattern).or_capacity()?;
+    }
+
+    if designs.lines().any(|design| design.len() >= 64) {
+        return Err(Error::Capacity);
     }
 
+    Ok(designs)
+}
+
+/// # Errors
+/// See [`Error`].
+pub fn solve_1_dp<const PATTERNS: usize>(input: &str) -> Result<usize, Error> {
+    let mut patterns = Vec::<_, PATTERNS>::new();
+    let designs = parse(input, &mut patterns)?;
+
     let have_options = |design: &str| {
         let mut options = [0u64; 64];
         for pattern in &patterns {
@@ -74,42 +115,43 @@ pub fn solve_1_dp(input: &str) -> usize {
     #[cfg(not(feature = "parallel"))]
     let lines = designs.lines();
 
-    lines.filter(|&design| have_options(design)).count()
+    Ok(lines.filter(|&design| have_options(design)).count())
 }
 
-/// # Panics
-pub fn solve_1_r(input: &str) -> usize {
-    fn is_match<'a>(memoize: &mut Set<&'a str>, patterns: &[&str], design: &'a str) -> bool {
+/// # Errors
+/// See [`Error`].
+pub fn solve_1_r<const PATTERNS: usize>(input: &str) -> Result<usize, Error> {
+    fn is_match<'a>(
+        memoize: &mut Set<&'a str>,
+        patterns: &[&str],
+        design: &'a str,
+    ) -> Result<bool, Error> {
         if design.is_empty() {
-            return true;
+            return Ok(true);
         }
 
         if memoize.contains(design) {
-            return false;
+            return Ok(false);
         }
 
-        let result = patterns.iter().any(|pattern| {
-            design
-                .strip_prefix(pattern)
-                .is_some_and(|design| is_match(memoize, patterns, design))
-        });
-
-        if result {
-            true
-        } else {
-            memoize.insert(design).unwrap();
+        for pattern in patterns {
+            let Some(design) = design.strip_prefix(pattern) else {
+                continue;
+            };
 
-            false
+            if is_match(memoize, patterns, design)? {
+                return Ok(true);
+            }
         }
-    }
 
-    let (ps, designs) = input.split_once("\n\n").unwrap();
+        memoize.insert(design).or_capacity()?;
 
-    let mut patterns = Vec::new();
-    for pattern in ps.split(", ") {
-        patterns.push(pattern).unwrap();
+        Ok(false)
     }
 
+    let mut patterns = Vec::<_, PATTERNS>::new();
+    let designs = parse(input, &mut patterns)?;
+
     #[cfg(feature = "parallel")]
     let lines = designs.par_lines();
 
@@ -117,15 +159,15 @@ pub fn solve_1_r(input: &str) -> usize {
     let lines = designs.lines();
 
     lines
-        .filter(|&design| is_match(&mut Set::new(), &patterns, design))
-        .count()
+        .map(|design| is_match(&mut Set::new(), &patterns, design).map(usize::from))
+        .sum()
 }
 
-/// # Panics
-pub fn solve_2_r(input: &str) -> u64 {
-    let (patterns, designs) = input.split_once("\n\n").unwrap();
-
-    let patterns = patterns.split(", ").collect::<Vec<_>>();
+/// # Errors
+/// See [`Error`].
+pub fn solve_2_r<const PATTERNS: usize>(input: &str) -> Result<u64, Error> {
+    let mut patterns = Vec::<_, PATTERNS>::new();
+    let designs = parse(input, &mut patterns)?;
 
     let count_options = |design: &str| {
         let mut options = [0u64; 64];
@@ -158,37 +200,39 @@ pub fn solve_2_r(input: &str) -> u64 {
     #[cfg(not(feature = "parallel"))]
     let lines = designs.lines();
 
-    lines.map(count_options).sum()
+    Ok(lines.map(count_options).sum())
 }
 
-/// # Panics
-pub fn solve_2_dp(input: &str) -> u64 {
-    fn ways<'a>(memoize: &mut Map<&'a str, u64>, patterns: &[&str], design: &'a str) -> u64 {
+/// # Errors
+/// See [`Error`].
+pub fn solve_2_dp<const PATTERNS: usize>(input: &str) -> Result<u64, Error> {
+    fn ways<'a>(
+        memoize: &mut Map<&'a str, u64>,
+        patterns: &[&str],
+        design: &'a str,
+    ) -> Result<u64, Error> {
         if design.is_empty() {
-            return 1;
+            return Ok(1);
         }
 
         if let Some(&count) = memoize.get(design) {
-            return count;
+            return Ok(count);
         }
 
-        let count = patterns
-            .iter()
-            .filter_map(|pattern| {
-                design
-                    .strip_prefix(pattern)
-                    .map(|design| ways(memoize, patterns, design))
-            })
-            .sum();
+        let mut count = 0;
+        for pattern in patterns {
+            if let Some(design) = design.strip_prefix(pattern) {
+                count += ways(memoize, patterns, design)?;
+            }
+        }
 
-        memoize.insert(design, count).unwrap();
+        memoize.insert(design, count).or_capacity()?;
 
-        count
+        Ok(count)
     }
 
-    let (patterns, designs) = input.split_once("\n\n").unwrap();
-
-    let patterns = patterns.split(", ").collect::<Vec<_>>();
+    let mut patterns = Vec::<_, PATTERNS>::new();
+    let designs = parse(input, &mut patterns)?;
 
     #[cfg(feature = "parallel")]
     let lines = designs.par_lines();
@@ -211,6 +255,23 @@ pub fn part_2() -> u64 {
     solve_2(INPUT)
 }
 
+pub struct Day<const PATTERNS: usize = { crate::PATTERNS }>;
+
+impl<const PATTERNS: usize> solution::Solution for Day<PATTERNS> {
+    const DAY: u32 = 19;
+
+    type Output1 = usize;
+    type Output2 = u64;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<PATTERNS>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<PATTERNS>(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,33 +289,63 @@ bbrgwb";
 
     #[test]
     fn same_results_1_r() {
-        assert_eq!(solve_1_r(INPUT), 6);
+        assert_eq!(solve_1_r::<PATTERNS>(INPUT), Ok(6));
     }
 
     #[test]
     fn same_results_1_dp() {
-        assert_eq!(solve_1_dp(INPUT), 6);
+        assert_eq!(solve_1_dp::<PATTERNS>(INPUT), Ok(6));
     }
 
     #[cfg(feature = "input")]
     #[test]
     fn same_results_1_r_vs_dp() {
-        assert_eq!(solve_1_r(super::INPUT), solve_1_dp(super::INPUT));
+        if answers::is_locked(super::INPUT) {
+            return;
+        }
+
+        assert_eq!(
+            solve_1_r::<PATTERNS>(super::INPUT),
+            solve_1_dp::<PATTERNS>(super::INPUT)
+        );
     }
 
     #[test]
     fn same_results_2_r() {
-        assert_eq!(solve_2_r(INPUT), 16);
+        assert_eq!(solve_2_r::<PATTERNS>(INPUT), Ok(16));
     }
 
     #[test]
     fn same_results_2_dp() {
-        assert_eq!(solve_2_dp(INPUT), 16);
+        assert_eq!(solve_2_dp::<PATTERNS>(INPUT), Ok(16));
     }
 
     #[cfg(feature = "input")]
     #[test]
     fn same_results_2_r_vs_dp() {
-        assert_eq!(solve_2_r(super::INPUT), solve_2_dp(super::INPUT));
+        if answers::is_locked(super::INPUT) {
+            return;
+        }
+
+        assert_eq!(
+            solve_2_r::<PATTERNS>(super::INPUT),
+            solve_2_dp::<PATTERNS>(super::INPUT)
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(try_solve_1::<PATTERNS>("r, wr"), Err(Error::Parse));
+        assert_eq!(try_solve_2::<PATTERNS>("r, , b\n\nrrb"), Err(Error::Parse));
+    }
+
+    #[test]
+    fn custom_capacity() {
+        assert_eq!(solve_2_dp::<8>(INPUT), Ok(16));
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(solve_2_r::<4>(INPUT), Err(Error::Capacity));
     }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -1,7 +1,11 @@
 #![no_std]
 #![allow(clippy::must_use_candidate)]
 
-use bitset::BitSet;
+use bitset::{BitSet, GridIndex};
+
+use grid::{Grid, Point as Position};
+
+use solution::{Error, OrCapacity};
 
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
@@ -12,33 +16,64 @@ pub const INPUT: &str = include_str!("../../input");
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = "";
 
-use heapless::Vec as HLVec;
+use collections::Vec as HLVec;
 
-type Vec<T> = HLVec<T, { 4096 * 4 }>;
+type Vec<T, const N: usize> = HLVec<T, N>;
 
-/// # Panics
-#[allow(clippy::cast_possible_truncation)]
-pub fn solve_v<const MIN_SAVING: usize, const CHEAT_LEN: usize>(input: &str) -> usize {
-    let map = input.as_bytes();
-    let width = map.iter().position(|&tile| tile == b'\n').unwrap();
-    let height = (map.len() + 1) / (width + 1);
-
-    let (start_r, start_c) = map
-        .chunks(width + 1)
-        .take(height)
-        .enumerate()
-        .find_map(|(r, row)| {
-            row.iter()
-                .take(width)
-                .position(|&tile| tile == b'S')
-                .map(|c| (r, c))
-        })
-        .unwrap();
+/// Default capacity for the path from `S` to `E`.
+pub const PATH: usize = 4096 * 4;
+
+/// Default `BitSet` size for the visited positions.
+pub const VISITED_SIZE: usize = BitSet::with_capacity(141 * 142);
+
+/// Returns the track and the position of `S`.
+///
+/// The track must be enclosed by walls, contain an `E` and fit the
+/// `BitSet`.
+fn parse<const VISITED_SIZE: usize>(input: &str) -> Result<(Grid<'_>, Position), Error> {
+    let map = Grid::new(input.as_bytes())?;
+    let (height, width) = (map.height(), map.width());
+
+    // positions are stored as `u8`s
+    if width > 256 || height > 256 || BitSet::with_capacity(height * width) > VISITED_SIZE {
+        return Err(Error::Capacity);
+    }
 
-    let mut visited = BitSet::<_, _, { BitSet::with_capacity(141 * 142) }>::new(|(r, c)| {
-        *r as usize * (width + 1) + *c as usize
-    });
-    visited.insert((start_r as u8, start_c as u8)).unwrap();
+    let (mut start, mut end) = (None, false);
+    for ((r, c), tile) in map.iter() {
+        let border = r == 0 || c == 0 || r == height - 1 || c == width - 1;
+        match tile {
+            b'#' => {}
+            b'S' if !border => start = Some((r, c)),
+            b'E' if !border => end = true,
+            b'.' if !border => {}
+            _ => return Err(Error::Parse),
+        }
+    }
+
+    match (start, end) {
+        (Some(start), true) => Ok((map, start)),
+        _ => Err(Error::Parse),
+    }
+}
+
+/// # Errors
+/// See [`Error`].
+#[allow(clippy::cast_possible_truncation)]
+pub fn solve_v<
+    const MIN_SAVING: usize,
+    const CHEAT_LEN: usize,
+    const PATH: usize,
+    const VISITED_SIZE: usize,
+>(
+    input: &str,
+) -> Result<usize, Error> {
+    let (map, (start_r, start_c)) = parse::<VISITED_SIZE>(input)?;
+
+    let mut visited = BitSet::<_, _, VISITED_SIZE>::from_key(GridIndex::new(map.width()));
+    visited
+        .insert((start_r as u8, start_c as u8))
+        .or_capacity()?;
 
     let path_iter = (0u16..).scan(
         ((start_r as u8, start_c as u8), false),
@@ -47,40 +82,34 @@ pub fn solve_v<const MIN_SAVING: usize, const CHEAT_LEN: usize>(input: &str) ->
                 return None;
             }
 
-            if map[*r as usize * (width + 1) + *c as usize] == b'E' {
+            if map[(*r as usize, *c as usize)] == b'E' {
                 *done = true;
-                Some(((*r, *c), i))
+                Some(Ok(((*r, *c), i)))
             } else {
                 let res = ((*r, *c), i);
 
-                (*r, *c) = [(0, 1), (0, -1), (1, 0), (-1, 0)]
-                    .into_iter()
-                    .find_map(|(dr, dc)| {
-                        match (r.checked_add_signed(dr), c.checked_add_signed(dc)) {
-                            (Some(nr), Some(nc))
-                                if (nr as usize) < height
-                                    && (nc as usize) < width
-                                    && map[nr as usize * (width + 1) + nc as usize] != b'#' =>
-                            {
-                                if visited.insert((nr, nc)).unwrap() {
-                                    None
-                                } else {
-                                    Some((nr, nc))
-                                }
-                            }
-                            _ => None,
-                        }
-                    })
-                    .unwrap();
+                // a dead end never reaches `E`
+                let Some(next) =
+                    map.neighbours((*r as usize, *c as usize))
+                        .find_map(|((nr, nc), tile)| {
+                            let next = (nr as u8, nc as u8);
+                            (tile != b'#' && !visited.insert(next).unwrap_or(true)).then_some(next)
+                        })
+                else {
+                    *done = true;
+                    return Some(Err(Error::Unsolvable));
+                };
+
+                (*r, *c) = next;
 
-                Some(res)
+                Some(Ok(res))
             }
         },
     );
 
-    let mut main_path = Vec::new();
+    let mut main_path = Vec::<_, PATH>::new();
     for v in path_iter {
-        main_path.push(v).unwrap();
+        main_path.push(v?).or_capacity()?;
     }
 
     let path_len = main_path.len();
@@ -91,7 +120,7 @@ pub fn solve_v<const MIN_SAVING: usize, const CHEAT_LEN: usize>(input: &str) ->
     #[cfg(not(feature = "parallel"))]
     let steps = main_path.iter();
 
-    steps
+    Ok(steps
         .map(|((r, c), i)| {
             main_path
                 .iter()
@@ -112,32 +141,32 @@ pub fn solve_v<const MIN_SAVING: usize, const CHEAT_LEN: usize>(input: &str) ->
                 })
                 .count()
         })
-        .sum()
+        .sum())
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[allow(clippy::cast_possible_truncation)]
-pub fn solve_m<const MIN_SAVING: usize, const CHEAT_LEN: usize>(input: &str) -> usize {
-    let map = input.as_bytes();
-    let width = map.iter().position(|&tile| tile == b'\n').unwrap();
-    let height = (map.len() + 1) / (width + 1);
-
-    let (start_r, start_c) = map
-        .chunks(width + 1)
-        .take(height)
-        .enumerate()
-        .find_map(|(r, row)| {
-            row.iter()
-                .take(width)
-                .position(|&tile| tile == b'S')
-                .map(|c| (r, c))
-        })
-        .unwrap();
+pub fn solve_m<
+    const MIN_SAVING: usize,
+    const CHEAT_LEN: usize,
+    const PATH: usize,
+    const VISITED_SIZE: usize,
+>(
+    input: &str,
+) -> Result<usize, Error> {
+    let (map, (start_r, start_c)) = parse::<VISITED_SIZE>(input)?;
+    let (height, width) = (map.height(), map.width());
+
+    // the distances table is sized for the puzzle input
+    if width > 141 || height > 142 {
+        return Err(Error::Capacity);
+    }
 
-    let mut visited = BitSet::<_, _, { BitSet::with_capacity(141 * 142) }>::new(|(r, c)| {
-        *r as usize * (width + 1) + *c as usize
-    });
-    visited.insert((start_r as u8, start_c as u8)).unwrap();
+    let mut visited = BitSet::<_, _, VISITED_SIZE>::from_key(GridIndex::new(width));
+    visited
+        .insert((start_r as u8, start_c as u8))
+        .or_capacity()?;
 
     let path_iter = (0u16..).scan(
         ((start_r as u8, start_c as u8), false),
@@ -146,42 +175,37 @@ pub fn solve_m<const MIN_SAVING: usize, const CHEAT_LEN: usize>(input: &str) ->
                 return None;
             }
 
-            if map[*r as usize * (width + 1) + *c as usize] == b'E' {
+            if map[(*r as usize, *c as usize)] == b'E' {
                 *done = true;
-                Some(((*r, *c), i))
+                Some(Ok(((*r, *c), i)))
             } else {
                 let res = ((*r, *c), i);
 
-                (*r, *c) = [(0, 1), (0, -1), (1, 0), (-1, 0)]
-                    .into_iter()
-                    .find_map(|(dr, dc)| {
-                        match (r.checked_add_signed(dr), c.checked_add_signed(dc)) {
-                            (Some(nr), Some(nc))
-                                if (nr as usize) < height
-                                    && (nc as usize) < width
-                                    && map[nr as usize * (width + 1) + nc as usize] != b'#' =>
-                            {
-                                if visited.insert((nr, nc)).unwrap() {
-                                    None
-                                } else {
-                                    Some((nr, nc))
-                                }
-                            }
-                            _ => None,
-                        }
-                    })
-                    .unwrap();
+                // a dead end never reaches `E`
+                let Some(next) =
+                    map.neighbours((*r as usize, *c as usize))
+                        .find_map(|((nr, nc), tile)| {
+                            let next = (nr as u8, nc as u8);
+                            (tile != b'#' && !visited.insert(next).unwrap_or(true)).then_some(next)
+                        })
+                else {
+                    *done = true;
+                    return Some(Err(Error::Unsolvable));
+                };
 
-                Some(res)
+                (*r, *c) = next;
+
+                Some(Ok(res))
             }
         },
     );
 
-    let mut path = Vec::new();
+    let mut path = Vec::<_, PATH>::new();
     let mut main_path = [[0u16; 141]; 142];
-    for ((r, c), v) in path_iter {
+    for v in path_iter {
+        let ((r, c), v) = v?;
         main_path[r as usize][c as usize] = v;
-        path.push(((r, c), v)).unwrap();
+        path.push(((r, c), v)).or_capacity()?;
     }
 
     let path_len = path.len();
@@ -193,7 +217,7 @@ pub fn solve_m<const MIN_SAVING: usize, const CHEAT_LEN: usize>(input: &str) ->
     let steps = path.iter();
 
     let main_path = &main_path; // borrow checker :PPP
-    steps
+    Ok(steps
         .map(|((r, c), i)| {
             ((*r as usize).saturating_sub(CHEAT_LEN)..(*r as usize + CHEAT_LEN + 1).min(height))
                 .flat_map(move |tr| {
@@ -205,7 +229,7 @@ pub fn solve_m<const MIN_SAVING: usize, const CHEAT_LEN: usize>(input: &str) ->
                             }
 
                             let distance = (*r as usize).abs_diff(tr) + (*c as usize).abs_diff(tc);
-                            if distance <= CHEAT_LEN && map[tr * (width + 1) + tc] != b'#' {
+                            if distance <= CHEAT_LEN && map[(tr, tc)] != b'#' {
                                 let cheat_path_len =
                                     path_len - main_path[tr][tc] as usize + *i as usize + distance;
                                 let saving = path_len.saturating_sub(cheat_path_len);
@@ -219,19 +243,37 @@ pub fn solve_m<const MIN_SAVING: usize, const CHEAT_LEN: usize>(input: &str) ->
                 })
                 .count()
         })
-        .sum()
+        .sum())
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
+#[cfg_attr(target_os = "none", inline(never))]
+pub fn try_solve_1<const PATH: usize, const VISITED_SIZE: usize>(
+    input: &str,
+) -> Result<usize, Error> {
+    solve_m::<100, 2, PATH, VISITED_SIZE>(input)
+}
+
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
+pub fn try_solve_2<const PATH: usize, const VISITED_SIZE: usize>(
+    input: &str,
+) -> Result<usize, Error> {
+    solve_v::<100, 20, PATH, VISITED_SIZE>(input)
+}
+
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
 pub fn solve_1(input: &str) -> usize {
-    solve_m::<100, 2>(input)
+    try_solve_1::<PATH, VISITED_SIZE>(input).unwrap()
 }
 
 /// # Panics
-#[cfg_attr(target_os = "none", inline(never))]
+/// Panics if [`try_solve_2`] fails.
 pub fn solve_2(input: &str) -> usize {
-    solve_v::<100, 20>(input)
+    try_solve_2::<PATH, VISITED_SIZE>(input).unwrap()
 }
 
 #[cfg(feature = "input")]
@@ -244,6 +286,26 @@ pub fn part_2() -> usize {
     solve_2(INPUT)
 }
 
+pub struct Day<
+    const PATH: usize = { crate::PATH },
+    const VISITED_SIZE: usize = { crate::VISITED_SIZE },
+>;
+
+impl<const PATH: usize, const VISITED_SIZE: usize> solution::Solution for Day<PATH, VISITED_SIZE> {
+    const DAY: u32 = 20;
+
+    type Output1 = usize;
+    type Output2 = usize;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<PATH, VISITED_SIZE>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<PATH, VISITED_SIZE>(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -266,39 +328,86 @@ mod tests {
 
     #[test]
     fn same_results_1_m() {
-        assert_eq!(solve_m::<12, 2>(INPUT), 3 + 1 + 1 + 1 + 1 + 1);
+        assert_eq!(
+            solve_m::<12, 2, PATH, VISITED_SIZE>(INPUT),
+            Ok(3 + 1 + 1 + 1 + 1 + 1)
+        );
     }
 
     #[test]
     fn same_results_2_m() {
-        assert_eq!(solve_m::<50, 20>(INPUT), 285);
+        assert_eq!(solve_m::<50, 20, PATH, VISITED_SIZE>(INPUT), Ok(285));
     }
 
     #[test]
     fn same_results_1_v() {
-        assert_eq!(solve_v::<12, 2>(INPUT), 3 + 1 + 1 + 1 + 1 + 1);
+        assert_eq!(
+            solve_v::<12, 2, PATH, VISITED_SIZE>(INPUT),
+            Ok(3 + 1 + 1 + 1 + 1 + 1)
+        );
     }
 
     #[test]
     fn same_results_2_v() {
-        assert_eq!(solve_v::<50, 20>(INPUT), 285);
+        assert_eq!(solve_v::<50, 20, PATH, VISITED_SIZE>(INPUT), Ok(285));
     }
 
     #[cfg(feature = "input")]
     #[test]
     fn same_results_1_v_vs_m() {
+        if answers::is_locked(super::INPUT) {
+            return;
+        }
+
         assert_eq!(
-            solve_v::<100, 2>(super::INPUT),
-            solve_m::<100, 2>(super::INPUT)
+            solve_v::<100, 2, PATH, VISITED_SIZE>(super::INPUT),
+            solve_m::<100, 2, PATH, VISITED_SIZE>(super::INPUT)
         );
     }
 
     #[cfg(feature = "input")]
     #[test]
     fn same_results_2_v_vs_m() {
+        if answers::is_locked(super::INPUT) {
+            return;
+        }
+
+        assert_eq!(
+            solve_v::<100, 20, PATH, VISITED_SIZE>(super::INPUT),
+            solve_m::<100, 20, PATH, VISITED_SIZE>(super::INPUT)
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
         assert_eq!(
-            solve_v::<100, 20>(super::INPUT),
-            solve_m::<100, 20>(super::INPUT)
+            try_solve_1::<PATH, VISITED_SIZE>("#####\n#S.E#\n####"),
+            Err(Error::Parse)
+        );
+        assert_eq!(
+            try_solve_2::<PATH, VISITED_SIZE>("#####\n#S..#\n#####"),
+            Err(Error::Parse)
         );
     }
+
+    #[test]
+    fn unsolvable() {
+        assert_eq!(
+            try_solve_1::<PATH, VISITED_SIZE>("#####\n#S#E#\n#####"),
+            Err(Error::Unsolvable)
+        );
+    }
+
+    #[test]
+    fn custom_capacity() {
+        const SMALL: usize = BitSet::with_capacity(15 * 16);
+
+        assert_eq!(solve_v::<50, 20, 128, SMALL>(INPUT), Ok(285));
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(solve_m::<50, 20, 64, SMALL>(INPUT), Err(Error::Capacity));
+        assert_eq!(solve_v::<50, 20, 128, 1>(INPUT), Err(Error::Capacity));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -3,6 +3,10 @@
 
 use core::iter;
 
+use grid::{BoundedPoint, Dir4};
+
+use solution::Error;
+
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
 
@@ -11,9 +15,6 @@ pub const INPUT: &str = "";
 
 // mod consts;
 
-const DIRECTIONS: &[((isize, isize), usize)] =
-    &[((-1, 0), U), ((0, -1), L), ((0, 1), R), ((1, 0), D)];
-
 const PAD_POS_SYMBOLS: &[u8] = b"A<>^v";
 const PAD_NUM_SYMBOLS: &[u8] = b"0123456789A";
 
@@ -61,6 +62,16 @@ const R: usize = symbol(PAD_POS_SYMBOLS, b'>');
 const U: usize = symbol(PAD_POS_SYMBOLS, b'^');
 const D: usize = symbol(PAD_POS_SYMBOLS, b'v');
 
+/// The directional pad button moving towards `direction`.
+const fn button(direction: Dir4) -> usize {
+    match direction {
+        Dir4::Up => U,
+        Dir4::Right => R,
+        Dir4::Down => D,
+        Dir4::Left => L,
+    }
+}
+
 const NUM_A: usize = symbol(PAD_NUM_SYMBOLS, b'A');
 
 const PAD_POS: [(usize, usize); PAD_POS_SYMBOLS.len()] = {
@@ -187,30 +198,36 @@ const fn pad_cost(
 
     let mut cost = u64::MAX;
 
+    let Some(start) = BoundedPoint::new((start_r, start_c), (HEIGHT, WIDTH)) else {
+        panic!("start out of the pad");
+    };
+
     let mut i = 0;
-    while i < DIRECTIONS.len() {
-        let ((dr, dc), direction) = DIRECTIONS[i];
+    while i < Dir4::ALL.len() {
+        let direction = Dir4::ALL[i];
         i += 1;
 
-        match (
-            start_r.checked_add_signed(dr),
-            start_c.checked_add_signed(dc),
-        ) {
-            (Some(r), Some(c)) if r < HEIGHT && c < WIDTH && (r != 0 || c != 0) => {
-                let mask = key((r, c));
-                if visited & mask != 0 {
-                    continue;
-                }
-
-                let value = pad_cost(p, (r, c), direction, (end_r, end_c), visited | mask);
-                if value == u64::MAX {
-                    continue;
-                }
-
-                cost = min(cost, value + p[start_direction][direction]);
-            }
-            _ => continue,
+        let Some(next) = direction.checked_step(start) else {
+            continue;
+        };
+
+        // the gap
+        let (r, c) = next.point();
+        if r == 0 && c == 0 {
+            continue;
+        }
+
+        let mask = key((r, c));
+        if visited & mask != 0 {
+            continue;
+        }
+
+        let value = pad_cost(p, (r, c), button(direction), (end_r, end_c), visited | mask);
+        if value == u64::MAX {
+            continue;
         }
+
+        cost = min(cost, value + p[start_direction][button(direction)]);
     }
 
     cost
@@ -232,30 +249,36 @@ const fn num_cost(
 
     let mut cost = u64::MAX;
 
+    let Some(start) = BoundedPoint::new((start_r, start_c), (HEIGHT, WIDTH)) else {
+        panic!("start out of the pad");
+    };
+
     let mut i = 0;
-    while i < DIRECTIONS.len() {
-        let ((dr, dc), direction) = DIRECTIONS[i];
+    while i < Dir4::ALL.len() {
+        let direction = Dir4::ALL[i];
         i += 1;
 
-        match (
-            start_r.checked_add_signed(dr),
-            start_c.checked_add_signed(dc),
-        ) {
-            (Some(r), Some(c)) if r < HEIGHT && c < WIDTH && (r != 3 || c != 0) => {
-                let mask = key((r, c));
-                if visited & mask != 0 {
-                    continue;
-                }
-
-                let value = num_cost(p, (r, c), direction, (end_r, end_c), visited | mask);
-                if value == u64::MAX {
-                    continue;
-                }
-
-                cost = min(cost, value + p[start_direction][direction]);
-            }
-            _ => continue,
+        let Some(next) = direction.checked_step(start) else {
+            continue;
+        };
+
+        // the gap
+        let (r, c) = next.point();
+        if r == 3 && c == 0 {
+            continue;
+        }
+
+        let mask = key((r, c));
+        if visited & mask != 0 {
+            continue;
+        }
+
+        let value = num_cost(p, (r, c), button(direction), (end_r, end_c), visited | mask);
+        if value == u64::MAX {
+            continue;
         }
+
+        cost = min(cost, value + p[start_direction][button(direction)]);
     }
 
     cost
@@ -269,13 +292,18 @@ const fn min(a: u64, b: u64) -> u64 {
     }
 }
 
-fn solve(input: &str, numpad: &PadNum) -> u64 {
+fn solve(input: &str, numpad: &PadNum) -> Result<u64, Error> {
     input
         .lines()
         .map(|line| {
-            assert_eq!(line.chars().last(), Some('A'));
+            if !matches!(
+                line.as_bytes(),
+                [b'0'..=b'9', b'0'..=b'9', b'0'..=b'9', b'A']
+            ) {
+                return Err(Error::Parse);
+            }
 
-            let n = line[..3].parse::<u64>().unwrap();
+            let n = line[..3].parse::<u64>()?;
             let length = iter::once(NUM_A)
                 .chain(line.as_bytes().iter().take(3).map(|d| (d - b'0') as usize))
                 .zip(
@@ -288,23 +316,37 @@ fn solve(input: &str, numpad: &PadNum) -> u64 {
                 .map(|(from, to)| numpad[from][to])
                 .sum::<u64>();
 
-            n * length
+            Ok(n * length)
         })
         .sum()
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> u64 {
+pub fn try_solve_1(input: &str) -> Result<u64, Error> {
     solve(input, &NUM02)
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> u64 {
+pub fn try_solve_2(input: &str) -> Result<u64, Error> {
     solve(input, &NUM25)
 }
 
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> u64 {
+    try_solve_1(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> u64 {
+    try_solve_2(input).unwrap()
+}
+
 #[cfg(feature = "input")]
 pub fn part_1() -> u64 {
     solve_1(INPUT)
@@ -315,6 +357,23 @@ pub fn part_2() -> u64 {
     solve_2(INPUT)
 }
 
+pub struct Day;
+
+impl solution::Solution for Day {
+    const DAY: u32 = 21;
+
+    type Output1 = u64;
+    type Output2 = u64;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -330,4 +389,13 @@ mod tests {
     fn same_results_1() {
         assert_eq!(solve_1(INPUT), 126384);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(try_solve_1("029A\n98A"), Err(Error::Parse));
+        assert_eq!(try_solve_2("0x9A"), Err(Error::Parse));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -5,6 +5,8 @@ use bitset::BitSet;
 
 use itertools::Itertools;
 
+use solution::{Error, OrCapacity};
+
 #[cfg(feature = "parallel")]
 use rayon::prelude::*;
 
@@ -37,9 +39,21 @@ impl Iterator for Generator {
     }
 }
 
-/// # Panics
+/// Parses a secret, which must be below [`DIVISOR`] for the
+/// [`Generator`] steps not to overflow.
+fn parse_secret(line: &str) -> Result<u64, Error> {
+    let secret = line.parse()?;
+    if secret < DIVISOR {
+        Ok(secret)
+    } else {
+        Err(Error::Parse)
+    }
+}
+
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> u64 {
+pub fn try_solve_1(input: &str) -> Result<u64, Error> {
     #[cfg(feature = "parallel")]
     let lines = input.par_lines();
 
@@ -47,7 +61,7 @@ pub fn solve_1(input: &str) -> u64 {
     let lines = input.lines();
 
     lines
-        .map(|line| Generator(line.parse().unwrap()).nth(2000).unwrap())
+        .map(|line| Ok(Generator(parse_secret(line)?).nth(2000).unwrap_or_default()))
         .sum()
 }
 
@@ -59,17 +73,18 @@ fn key((a, b, c, d): &(i32, i32, i32, i32)) -> usize {
         + (d + 9) as usize
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg(feature = "parallel")]
 #[cfg_attr(target_os = "none", inline(never))]
 #[allow(clippy::cast_possible_truncation)]
-pub fn solve_2_par(input: &str) -> u16 {
+pub fn try_solve_2_par(input: &str) -> Result<u16, Error> {
     use core::sync::atomic::{AtomicU16, Ordering};
 
     let map = const { [const { AtomicU16::new(0) }; { SIZE }] };
-    input.par_lines().for_each(|line| {
+    input.par_lines().try_for_each(|line| {
         let mut inserted = BitSet::<_, _, { BitSet::with_capacity(SIZE) }>::new(key);
-        for ((a, b, c, d), bananas) in Generator(line.parse().unwrap())
+        for ((a, b, c, d), bananas) in Generator(parse_secret(line)?)
             .take(2000)
             .map(|secret| secret % 10)
             .tuple_windows()
@@ -77,24 +92,31 @@ pub fn solve_2_par(input: &str) -> u16 {
             .tuple_windows()
             .map(|((_, a), (_, b), (_, c), (bananas, d))| ((a, b, c, d), bananas))
         {
-            if !inserted.insert((a, b, c, d)).unwrap() {
+            if !inserted.insert((a, b, c, d)).or_capacity()? {
                 map[key(&(a, b, c, d))].fetch_add(bananas, Ordering::Relaxed);
             }
         }
-    });
 
-    map.iter().map(|v| v.load(Ordering::Relaxed)).max().unwrap()
+        Ok::<_, Error>(())
+    })?;
+
+    Ok(map
+        .iter()
+        .map(|v| v.load(Ordering::Relaxed))
+        .max()
+        .unwrap_or_default())
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg(not(feature = "parallel"))]
 #[cfg_attr(target_os = "none", inline(never))]
 #[allow(clippy::cast_possible_truncation)]
-pub fn solve_2_seq(input: &str) -> u16 {
+pub fn try_solve_2_seq(input: &str) -> Result<u16, Error> {
     let mut map = [0u16; { SIZE }];
-    input.lines().for_each(|line| {
+    for line in input.lines() {
         let mut inserted = BitSet::<_, _, { BitSet::with_capacity(SIZE) }>::new(key);
-        for ((a, b, c, d), bananas) in Generator(line.parse().unwrap())
+        for ((a, b, c, d), bananas) in Generator(parse_secret(line)?)
             .take(2000)
             .map(|secret| secret % 10)
             .tuple_windows()
@@ -102,15 +124,41 @@ pub fn solve_2_seq(input: &str) -> u16 {
             .tuple_windows()
             .map(|((_, a), (_, b), (_, c), (bananas, d))| ((a, b, c, d), bananas))
         {
-            if !inserted.insert((a, b, c, d)).unwrap() {
+            if !inserted.insert((a, b, c, d)).or_capacity()? {
                 map[key(&(a, b, c, d))] += bananas;
             }
         }
-    });
+    }
+
+    Ok(map.iter().max().copied().unwrap_or_default())
+}
+
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> u64 {
+    try_solve_1(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2_par`] fails.
+#[cfg(feature = "parallel")]
+pub fn solve_2_par(input: &str) -> u16 {
+    try_solve_2_par(input).unwrap()
+}
 
-    map.iter().max().copied().unwrap()
+/// # Panics
+/// Panics if [`try_solve_2_seq`] fails.
+#[cfg(not(feature = "parallel"))]
+pub fn solve_2_seq(input: &str) -> u16 {
+    try_solve_2_seq(input).unwrap()
 }
 
+#[cfg(feature = "parallel")]
+pub use try_solve_2_par as try_solve_2;
+
+#[cfg(not(feature = "parallel"))]
+pub use try_solve_2_seq as try_solve_2;
+
 #[cfg(feature = "parallel")]
 pub use solve_2_par as solve_2;
 
@@ -127,6 +175,23 @@ pub fn part_2() -> u16 {
     solve_2(INPUT)
 }
 
+pub struct Day;
+
+impl solution::Solution for Day {
+    const DAY: u32 = 22;
+
+    type Output1 = u64;
+    type Output2 = u16;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -150,4 +215,13 @@ mod tests {
     fn same_results_2() {
         assert_eq!(solve_2(INPUT_2), 23);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(try_solve_1("1\nx"), Err(Error::Parse));
+        assert_eq!(try_solve_2("1\n16777216"), Err(Error::Parse));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
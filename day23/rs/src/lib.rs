@@ -6,7 +6,9 @@ use rayon::prelude::*;
 
 use bitset::BitSet;
 
-use heapless::{FnvIndexMap, FnvIndexSet, String as HLString, Vec as HLVec};
+use collections::{FnvIndexMap, FnvIndexSet, String as HLString, Vec as HLVec};
+
+use solution::{Error, OrCapacity};
 
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
@@ -14,11 +16,15 @@ pub const INPUT: &str = include_str!("../../input");
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = "";
 
+/// Default capacity for the triangles with a `t` computer, must be a power
+/// of two.
+pub const TRIANGLES: usize = 2048;
+
 const WIDTH: usize = ('z' as u16 - 'a' as u16 + 1) as usize;
 
 type Vec<T> = HLVec<T, 16>;
 type NodeSet<T> = FnvIndexSet<T, { (WIDTH * WIDTH).next_power_of_two() }>;
-type Set<T> = FnvIndexSet<T, 2048>;
+type Set<T, const N: usize> = FnvIndexSet<T, N>;
 type NodeMap<K, V> = FnvIndexMap<K, V, { (WIDTH * WIDTH).next_power_of_two() }>;
 type String = HLString<64>;
 
@@ -28,12 +34,22 @@ fn id(element: &str) -> usize {
     })
 }
 
-/// # Panics
-#[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> usize {
-    fn set(elements: &[usize]) -> u128 {
-        let mut elements = Vec::try_from(elements).unwrap();
+/// Splits a connection into the names of its two computers, which must be
+/// two lowercase letters each to fit the [`id`] space.
+fn parse_edge(line: &str) -> Result<(&str, &str), Error> {
+    let is_name = |name: &str| name.len() == 2 && name.bytes().all(|c| c.is_ascii_lowercase());
 
+    match line.split_once('-') {
+        Some((a, b)) if is_name(a) && is_name(b) => Ok((a, b)),
+        _ => Err(Error::Parse),
+    }
+}
+
+/// # Errors
+/// See [`Error`].
+#[cfg_attr(target_os = "none", inline(never))]
+pub fn try_solve_1<const TRIANGLES: usize>(input: &str) -> Result<usize, Error> {
+    fn set(mut elements: [usize; 3]) -> u128 {
         elements.sort_unstable();
 
         elements.iter().fold(0, |acc, &element| {
@@ -46,27 +62,27 @@ pub fn solve_1(input: &str) -> usize {
             r * WIDTH * WIDTH + c
         });
     let mut ts = BitSet::<_, _, { BitSet::with_capacity(WIDTH * WIDTH) }>::new(|i| *i);
-    for (a, b) in input.lines().map(|line| line.split_once('-').unwrap()) {
+    for line in input.lines() {
+        let (a, b) = parse_edge(line)?;
         let (id_a, id_b) = (id(a), id(b));
 
-        edges.insert((id_a, id_b)).unwrap();
-        edges.insert((id_b, id_a)).unwrap();
+        edges.insert((id_a, id_b)).or_capacity()?;
+        edges.insert((id_b, id_a)).or_capacity()?;
 
         if a.starts_with('t') {
-            ts.insert(id_a).unwrap();
+            ts.insert(id_a).or_capacity()?;
         }
         if b.starts_with('t') {
-            ts.insert(id_b).unwrap();
+            ts.insert(id_b).or_capacity()?;
         }
     }
 
-    let mut result = Set::new();
+    let mut result = Set::<_, TRIANGLES>::new();
     let edges = &edges; // !!! borrow checker + move :'(
-    (0..WIDTH * WIDTH)
-        .filter(|t_id| ts.contains(t_id).unwrap())
+    ts.indices()
         .flat_map(|t_id| {
             (0..WIDTH * WIDTH).filter_map(move |a_id| {
-                if edges.contains(&(t_id, a_id)).unwrap() {
+                if edges.contains(&(t_id, a_id)).unwrap_or(false) {
                     Some((t_id, a_id))
                 } else {
                     None
@@ -75,24 +91,27 @@ pub fn solve_1(input: &str) -> usize {
         })
         .flat_map(|(t_id, a_id)| {
             (0..WIDTH * WIDTH).skip(a_id + 1).filter_map(move |b_id| {
-                if edges.contains(&(t_id, b_id)).unwrap() && edges.contains(&(a_id, b_id)).unwrap()
+                if edges.contains(&(t_id, b_id)).unwrap_or(false)
+                    && edges.contains(&(a_id, b_id)).unwrap_or(false)
                 {
-                    Some(set(&[t_id, a_id, b_id]))
+                    Some(set([t_id, a_id, b_id]))
                 } else {
                     None
                 }
             })
         })
-        .for_each(|set| {
-            result.insert(set).unwrap();
-        });
+        .try_for_each(|set| {
+            result.insert(set).or_capacity()?;
+            Ok::<_, Error>(())
+        })?;
 
-    result.len()
+    Ok(result.len())
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> String {
+pub fn try_solve_2(input: &str) -> Result<String, Error> {
     let mut nodes = NodeSet::new();
     let mut id2node = NodeMap::new();
 
@@ -100,17 +119,18 @@ pub fn solve_2(input: &str) -> String {
         BitSet::<_, _, { BitSet::with_capacity(WIDTH * WIDTH * WIDTH * WIDTH) }>::new(|(r, c)| {
             r * WIDTH * WIDTH + c
         });
-    for (a, b) in input.lines().map(|line| line.split_once('-').unwrap()) {
+    for line in input.lines() {
+        let (a, b) = parse_edge(line)?;
         let (id_a, id_b) = (id(a), id(b));
 
-        edges.insert((id_a, id_b)).unwrap();
-        edges.insert((id_b, id_a)).unwrap();
+        edges.insert((id_a, id_b)).or_capacity()?;
+        edges.insert((id_b, id_a)).or_capacity()?;
 
-        nodes.insert(id_a).unwrap();
-        nodes.insert(id_b).unwrap();
+        nodes.insert(id_a).or_capacity()?;
+        nodes.insert(id_b).or_capacity()?;
 
-        id2node.insert(id_a, a).unwrap();
-        id2node.insert(id_b, b).unwrap();
+        id2node.insert(id_a, a).or_capacity()?;
+        id2node.insert(id_b, b).or_capacity()?;
     }
 
     #[cfg(feature = "parallel")]
@@ -119,39 +139,58 @@ pub fn solve_2(input: &str) -> String {
     #[cfg(not(feature = "parallel"))]
     let nodes = nodes.iter();
 
+    let len =
+        |group: &Result<Vec<usize>, Error>| group.as_ref().map_or(usize::MAX, |group| group.len());
+
     let best_group = nodes
         .map(|&start_id| {
-            let mut group = Vec::try_from([start_id].as_slice()).unwrap();
+            let mut group = Vec::new();
+            group.push(start_id).or_capacity()?;
             for candidate_id in
-                (0..WIDTH * WIDTH).filter(|id| edges.contains(&(start_id, *id)).unwrap())
+                (0..WIDTH * WIDTH).filter(|id| edges.contains(&(start_id, *id)).unwrap_or(false))
             {
                 if group
                     .iter()
-                    .all(|&id| edges.contains(&(id, candidate_id)).unwrap())
+                    .all(|&id| edges.contains(&(id, candidate_id)).unwrap_or(false))
                 {
-                    group.push(candidate_id).unwrap();
+                    group.push(candidate_id).or_capacity()?;
                 }
             }
 
-            group
+            Ok(group)
         })
-        .max_by(|a, b| a.len().cmp(&b.len()))
-        .unwrap();
+        // an overflowing group wins, so that its error is reported
+        .max_by(|a, b| len(a).cmp(&len(b)))
+        .ok_or(Error::Unsolvable)??;
 
     let mut group = Vec::new();
     for node in best_group.into_iter().map(|id| id2node[&id]) {
-        group.push(node).unwrap();
+        group.push(node).or_capacity()?;
     }
     group.sort_unstable();
 
     let mut result = String::new();
     for node in group.iter().take(group.len() - 1) {
-        result.push_str(node).unwrap();
-        result.push(',').unwrap();
+        result.push_str(node).or_capacity()?;
+        result.push(',').or_capacity()?;
     }
-    result.push_str(group.iter().last().unwrap()).unwrap();
-
     result
+        .push_str(group.iter().last().ok_or(Error::Unsolvable)?)
+        .or_capacity()?;
+
+    Ok(result)
+}
+
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> usize {
+    try_solve_1::<TRIANGLES>(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> String {
+    try_solve_2(input).unwrap()
 }
 
 #[cfg(feature = "input")]
@@ -164,6 +203,23 @@ pub fn part_2() -> String {
     solve_2(INPUT)
 }
 
+pub struct Day<const TRIANGLES: usize = { crate::TRIANGLES }>;
+
+impl<const TRIANGLES: usize> solution::Solution for Day<TRIANGLES> {
+    const DAY: u32 = 23;
+
+    type Output1 = usize;
+    type Output2 = String;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<TRIANGLES>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -210,4 +266,20 @@ td-yn";
     fn same_results_2() {
         assert_eq!(&solve_2(INPUT), &"co,de,ka,ta");
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(try_solve_1::<TRIANGLES>("kh-tc\nqp"), Err(Error::Parse));
+        assert_eq!(try_solve_2("kh-Tc"), Err(Error::Parse));
+    }
+
+    #[test]
+    fn custom_capacity() {
+        assert_eq!(try_solve_1::<8>(INPUT), Ok(7));
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(try_solve_1::<4>(INPUT), Err(Error::Capacity));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
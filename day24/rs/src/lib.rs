@@ -3,10 +3,12 @@
 
 use core::ops;
 
-use heapless::{FnvIndexMap, String as HLString, Vec as HLVec};
+use collections::{FnvIndexMap, String as HLString, Vec as HLVec};
 
-type Values<K, V> = FnvIndexMap<K, V, 1024>;
-type Instructions<'a> = HLVec<Instruction<'a>, 512>;
+use solution::{Error, OrCapacity};
+
+type Values<K, V, const N: usize> = FnvIndexMap<K, V, N>;
+type Instructions<'a, const N: usize> = HLVec<Instruction<'a>, N>;
 type Z<'a> = HLVec<&'a str, 64>;
 type Bads<T> = HLVec<T, 8>;
 type String = HLString<64>;
@@ -17,6 +19,12 @@ pub const INPUT: &str = include_str!("../../input");
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = "";
 
+/// Default capacity for the wire values, must be a power of two.
+pub const WIRES: usize = 1024;
+
+/// Default capacity for the gates.
+pub const GATES: usize = 512;
+
 #[derive(Copy, Clone, Debug)]
 enum Gate {
     And,
@@ -47,36 +55,49 @@ struct Instruction<'a> {
     gate: Gate,
 }
 
-fn parse_initial_values<'a>(input: &'a str, initial_values: &mut Values<&'a str, bool>) {
+fn parse_initial_values<'a, const WIRES: usize>(
+    input: &'a str,
+    initial_values: &mut Values<&'a str, bool, WIRES>,
+) -> Result<(), Error> {
     for line in input.lines() {
-        let (line, value) = line.split_once(": ").unwrap();
+        let (line, value) = line.split_once(": ").ok_or(Error::Parse)?;
         let value = match value {
             "1" => true,
             "0" => false,
-            _ => unreachable!("invalid intial values input"),
+            _ => return Err(Error::Parse),
         };
 
-        initial_values.insert(line, value).unwrap();
+        initial_values.insert(line, value).or_capacity()?;
     }
+
+    Ok(())
 }
 
-fn parse_logic<'a>(input: &'a str, instructions: &mut Instructions<'a>, z: &mut Z<'a>) {
+fn parse_logic<'a, const GATES: usize>(
+    input: &'a str,
+    instructions: &mut Instructions<'a, GATES>,
+    z: &mut Z<'a>,
+) -> Result<(), Error> {
     for line in input.lines() {
-        let (gate, output) = line.split_once(" -> ").unwrap();
+        let (gate, output) = line.split_once(" -> ").ok_or(Error::Parse)?;
 
         let mut gate_parts = gate.split(' ');
 
-        let left = gate_parts.next().unwrap();
+        let left = gate_parts.next().ok_or(Error::Parse)?;
         let gate = match gate_parts.next() {
             Some("AND") => Gate::And,
             Some("OR") => Gate::Or,
             Some("XOR") => Gate::Xor,
-            _ => unreachable!("invalid logic input"),
+            _ => return Err(Error::Parse),
         };
-        let right = gate_parts.next().unwrap();
+        let right = gate_parts.next().ok_or(Error::Parse)?;
+
+        if gate_parts.next().is_some() {
+            return Err(Error::Parse);
+        }
 
         if output.starts_with('z') {
-            z.push(output).unwrap();
+            z.push(output).or_capacity()?;
         }
 
         instructions
@@ -86,16 +107,19 @@ fn parse_logic<'a>(input: &'a str, instructions: &mut Instructions<'a>, z: &mut
                 output,
                 gate,
             })
-            .unwrap();
+            .or_capacity()?;
     }
 
     z.sort_unstable();
+
+    Ok(())
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> u64 {
-    fn get_z(z: &[&str], values: &Values<&str, bool>) -> Option<u64> {
+pub fn try_solve_1<const WIRES: usize, const GATES: usize>(input: &str) -> Result<u64, Error> {
+    fn get_z<const WIRES: usize>(z: &[&str], values: &Values<&str, bool, WIRES>) -> Option<u64> {
         z.iter().enumerate().try_fold(0, |acc, (bit, z)| {
             values
                 .get(z)
@@ -103,20 +127,23 @@ pub fn solve_1(input: &str) -> u64 {
         })
     }
 
-    let (initial_values, logic) = input.split_once("\n\n").unwrap();
+    let (initial_values, logic) = input.split_once("\n\n").ok_or(Error::Parse)?;
 
-    let mut values = Values::new();
-    parse_initial_values(initial_values, &mut values);
+    let mut values = Values::<_, _, WIRES>::new();
+    parse_initial_values(initial_values, &mut values)?;
 
-    let mut instructions = Instructions::new();
+    let mut instructions = Instructions::<GATES>::new();
     let mut z = Z::new();
-    parse_logic(logic, &mut instructions, &mut z);
+    parse_logic(logic, &mut instructions, &mut z)?;
 
     loop {
         if let Some(value) = get_z(&z, &values) {
-            return value;
+            return Ok(value);
         }
 
+        // a pass without new wires means some `z` is never driven
+        let known = values.len();
+
         for Instruction {
             left,
             right,
@@ -125,15 +152,20 @@ pub fn solve_1(input: &str) -> u64 {
         } in &instructions
         {
             if let (Some(&a), Some(&b)) = (values.get(left), values.get(right)) {
-                values.insert(output, gate.evaluate(a, b)).unwrap();
+                values.insert(output, gate.evaluate(a, b)).or_capacity()?;
             }
         }
+
+        if values.len() == known {
+            return Err(Error::Unsolvable);
+        }
     }
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(input: &str) -> String {
+pub fn try_solve_2<const GATES: usize>(input: &str) -> Result<String, Error> {
     #[allow(dead_code)]
     #[derive(Debug)]
     enum Bad<'a> {
@@ -180,11 +212,13 @@ pub fn solve_2(input: &str) -> String {
             .any(|instruction| matches!(instruction, Instruction { left, right, gate: Gate::Or, .. } if pin == *left || pin == *right))
     }
 
-    let (_, logic) = input.split_once("\n\n").unwrap();
+    let (_, logic) = input.split_once("\n\n").ok_or(Error::Parse)?;
 
-    let mut instructions = Instructions::new();
+    let mut instructions = Instructions::<GATES>::new();
     let mut z = Z::new();
-    parse_logic(logic, &mut instructions, &mut z);
+    parse_logic(logic, &mut instructions, &mut z)?;
+
+    let last_z = z.iter().last().ok_or(Error::Unsolvable)?;
 
     let mut bads = Bads::new();
     for instruction in &instructions {
@@ -195,21 +229,21 @@ pub fn solve_2(input: &str) -> String {
                 output,
                 gate: Gate::Xor,
             } if is_inner(left) && is_inner(right) && is_inner(output) => {
-                bads.push(Bad::Xor(*instruction, output)).unwrap();
+                bads.push(Bad::Xor(*instruction, output)).or_capacity()?;
             }
             Instruction {
                 output,
                 gate: Gate::And,
                 ..
             } if is_output(output) => {
-                bads.push(Bad::And(*instruction, output)).unwrap();
+                bads.push(Bad::And(*instruction, output)).or_capacity()?;
             }
             Instruction {
                 output,
                 gate: Gate::Or,
                 ..
-            } if is_output(output) && output != z.iter().last().unwrap() => {
-                bads.push(Bad::Or(*instruction, output)).unwrap();
+            } if is_output(output) && output != last_z => {
+                bads.push(Bad::Or(*instruction, output)).or_capacity()?;
             }
             Instruction {
                 left,
@@ -220,32 +254,51 @@ pub fn solve_2(input: &str) -> String {
             } if !is_carry_input(&instructions, output)
                 && (!left.ends_with("00") || !right.ends_with("00")) =>
             {
-                bads.push(Bad::Carry(*instruction, output)).unwrap();
+                bads.push(Bad::Carry(*instruction, output)).or_capacity()?;
             }
             Instruction {
                 output,
                 gate: Gate::Xor,
                 ..
             } if is_inner(output) && !is_s_input(&instructions, output) => {
-                bads.push(Bad::S(*instruction, output)).unwrap();
+                bads.push(Bad::S(*instruction, output)).or_capacity()?;
             }
             _ => {}
         }
     }
 
-    debug_assert!(bads.len() == 8, "bads invalid");
+    if bads.len() != 8 {
+        return Err(Error::Unsolvable);
+    }
 
     let mut v = bads.iter().map(Bad::output).collect::<Bads<_>>();
     v.sort_unstable();
 
+    join(&v)
+}
+
+fn join(names: &[&str]) -> Result<String, Error> {
     let mut result = String::new();
-    for bad in v.iter().take(v.len() - 1) {
-        result.push_str(bad).unwrap();
-        result.push(',').unwrap();
+    for (i, name) in names.iter().enumerate() {
+        if i != 0 {
+            result.push(',').or_capacity()?;
+        }
+        result.push_str(name).or_capacity()?;
     }
-    result.push_str(v.iter().last().unwrap()).unwrap();
 
-    result
+    Ok(result)
+}
+
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> u64 {
+    try_solve_1::<WIRES, GATES>(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> String {
+    try_solve_2::<GATES>(input).unwrap()
 }
 
 #[cfg(feature = "input")]
@@ -258,6 +311,23 @@ pub fn part_2() -> String {
     solve_2(INPUT)
 }
 
+pub struct Day<const WIRES: usize = { crate::WIRES }, const GATES: usize = { crate::GATES }>;
+
+impl<const WIRES: usize, const GATES: usize> solution::Solution for Day<WIRES, GATES> {
+    const DAY: u32 = 24;
+
+    type Output1 = u64;
+    type Output2 = String;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<WIRES, GATES>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2::<GATES>(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -330,4 +400,36 @@ tnw OR pbm -> gnj";
     fn same_results_1_2() {
         assert_eq!(solve_1(INPUT_2), 2024);
     }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(
+            try_solve_1::<WIRES, GATES>("x00: 2\n\nx00 AND x00 -> z00"),
+            Err(Error::Parse)
+        );
+        assert_eq!(
+            try_solve_2::<GATES>("x00: 1\n\nx00 NAND x00 -> z00"),
+            Err(Error::Parse)
+        );
+    }
+
+    #[test]
+    fn unsolvable() {
+        assert_eq!(
+            try_solve_1::<WIRES, GATES>("x00: 1\n\nx00 AND y00 -> z00"),
+            Err(Error::Unsolvable)
+        );
+    }
+
+    #[test]
+    fn custom_capacity() {
+        assert_eq!(try_solve_1::<64, 64>(INPUT_2), Ok(2024));
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(try_solve_1::<32, 64>(INPUT_2), Err(Error::Capacity));
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(try_solve_1::<64, 32>(INPUT_2), Err(Error::Capacity));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -12,12 +12,12 @@ pub fn criterion_benchmark(c: &mut Criterion) {
 
     #[cfg(feature = "simd")]
     {
-        group.bench_function("simd/2", |b| b.iter(|| solve_1_simd::<2>(INPUT)));
-        group.bench_function("simd/4", |b| b.iter(|| solve_1_simd::<4>(INPUT)));
-        group.bench_function("simd/8", |b| b.iter(|| solve_1_simd::<8>(INPUT)));
-        group.bench_function("simd/16", |b| b.iter(|| solve_1_simd::<16>(INPUT)));
-        group.bench_function("simd/32", |b| b.iter(|| solve_1_simd::<32>(INPUT)));
-        group.bench_function("simd/64", |b| b.iter(|| solve_1_simd::<64>(INPUT)));
+        group.bench_function("simd/2", |b| b.iter(|| solve_1_simd::<2, { day25::SCHEMATICS }>(INPUT)));
+        group.bench_function("simd/4", |b| b.iter(|| solve_1_simd::<4, { day25::SCHEMATICS }>(INPUT)));
+        group.bench_function("simd/8", |b| b.iter(|| solve_1_simd::<8, { day25::SCHEMATICS }>(INPUT)));
+        group.bench_function("simd/16", |b| b.iter(|| solve_1_simd::<16, { day25::SCHEMATICS }>(INPUT)));
+        group.bench_function("simd/32", |b| b.iter(|| solve_1_simd::<32, { day25::SCHEMATICS }>(INPUT)));
+        group.bench_function("simd/64", |b| b.iter(|| solve_1_simd::<64, { day25::SCHEMATICS }>(INPUT)));
     }
 
     group.finish();
@@ -8,7 +8,9 @@ use rayon::prelude::*;
 #[cfg(feature = "simd")]
 use core::simd::{prelude::*, LaneCount, SupportedLaneCount};
 
-use heapless::{String as HLString, Vec as HLVec};
+use collections::{String as HLString, Vec as HLVec};
+
+use solution::{Error, OrCapacity};
 
 #[cfg(feature = "input")]
 pub const INPUT: &str = include_str!("../../input");
@@ -16,33 +18,58 @@ pub const INPUT: &str = include_str!("../../input");
 #[cfg(not(feature = "input"))]
 pub const INPUT: &str = "";
 
-type Vec<T> = HLVec<T, 1024>;
+/// Default capacity for the keys and for the locks.
+pub const SCHEMATICS: usize = 1024;
+
+type Vec<T, const N: usize> = HLVec<T, N>;
 type String = HLString<1>;
 
-/// # Panics
-#[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_1(input: &str) -> usize {
-    let (mut keys, mut locks) = const { (Vec::new(), Vec::new()) };
-    for part in input.split("\n\n") {
-        let list = if part.starts_with("#####") {
-            &mut locks
-        } else {
-            &mut keys
-        };
+/// Returns whether the schematic is a lock and its pins as a bit mask.
+fn parse_schematic(part: &str) -> Result<(bool, u32), Error> {
+    let rows = part.trim_end().as_bytes();
+
+    if rows.len() != 7 * 6 - 1
+        || rows.chunks(6).any(|row| {
+            !row[..5].iter().all(|c| matches!(c, b'#' | b'.'))
+                || row.get(5).is_some_and(|&c| c != b'\n')
+        })
+    {
+        return Err(Error::Parse);
+    }
+
+    let is_lock = part.starts_with("#####");
+    if !is_lock && !rows.ends_with(b"#####") {
+        return Err(Error::Parse);
+    }
 
-        let acc = part.as_bytes().chunks(6).skip(1).take(5).enumerate().fold(
-            0u64,
-            |mut acc, (j, row)| {
-                for (i, &c) in row.iter().take(5).enumerate() {
-                    if c == b'#' {
-                        acc |= 1 << (5 * i + j);
-                    }
+    let acc = rows
+        .chunks(6)
+        .skip(1)
+        .take(5)
+        .enumerate()
+        .fold(0, |mut acc, (j, row)| {
+            for (i, &c) in row.iter().take(5).enumerate() {
+                if c == b'#' {
+                    acc |= 1 << (5 * i + j);
                 }
-                acc
-            },
-        );
+            }
+            acc
+        });
+
+    Ok((is_lock, acc))
+}
+
+/// # Errors
+/// See [`Error`].
+#[cfg_attr(target_os = "none", inline(never))]
+pub fn try_solve_1<const SCHEMATICS: usize>(input: &str) -> Result<usize, Error> {
+    let (mut keys, mut locks) =
+        const { (Vec::<_, SCHEMATICS>::new(), Vec::<_, SCHEMATICS>::new()) };
+    for part in input.split("\n\n") {
+        let (is_lock, acc) = parse_schematic(part)?;
+        let list = if is_lock { &mut locks } else { &mut keys };
 
-        list.push(acc).unwrap();
+        list.push(u64::from(acc)).or_capacity()?;
     }
 
     #[cfg(feature = "parallel")]
@@ -51,11 +78,13 @@ pub fn solve_1(input: &str) -> usize {
     #[cfg(not(feature = "parallel"))]
     let keys = keys.iter();
 
-    keys.map(|&key| locks.iter().filter(|&lock| key & lock == 0).count())
-        .sum()
+    Ok(keys
+        .map(|&key| locks.iter().filter(|&lock| key & lock == 0).count())
+        .sum())
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg(feature = "simd")]
 #[cfg_attr(target_os = "none", inline(never))]
 #[allow(
@@ -63,46 +92,32 @@ pub fn solve_1(input: &str) -> usize {
     clippy::cast_possible_wrap,
     clippy::cast_sign_loss
 )]
-pub fn solve_1_simd<const N: usize>(input: &str) -> usize
+pub fn solve_1_simd<const N: usize, const SCHEMATICS: usize>(input: &str) -> Result<usize, Error>
 where
     LaneCount<N>: SupportedLaneCount,
 {
     type Num = u32;
 
     #[repr(align(16))]
-    struct Data {
-        keys: Vec<Num>,
-        locks: Vec<Num>,
+    struct Data<const SCHEMATICS: usize> {
+        keys: Vec<Num, SCHEMATICS>,
+        locks: Vec<Num, SCHEMATICS>,
     }
 
-    let mut data = Data {
+    let mut data = Data::<SCHEMATICS> {
         keys: Vec::new(),
         locks: Vec::new(),
     };
 
     for part in input.split("\n\n") {
-        let list = if part.starts_with("#####") {
+        let (is_lock, acc) = parse_schematic(part)?;
+        let list = if is_lock {
             &mut data.locks
         } else {
             &mut data.keys
         };
 
-        let acc =
-            part.as_bytes()
-                .chunks(6)
-                .skip(1)
-                .take(5)
-                .enumerate()
-                .fold(0, |mut acc, (j, row)| {
-                    for (i, &c) in row.iter().take(5).enumerate() {
-                        if c == b'#' {
-                            acc |= 1 << (5 * i + j);
-                        }
-                    }
-                    acc
-                });
-
-        list.push(acc).unwrap();
+        list.push(acc).or_capacity()?;
     }
 
     #[cfg(feature = "parallel")]
@@ -111,32 +126,46 @@ where
     #[cfg(not(feature = "parallel"))]
     let keys = data.keys.iter();
 
-    keys.map(|&key| {
-        let (locks_prefix, locks, locks_suffix) = data.locks.as_simd::<N>();
-
-        let prefix = locks_prefix.iter().filter(|&lock| lock & key == 0).count();
-        let suffix = locks_suffix.iter().filter(|&lock| lock & key == 0).count();
-
-        let mut total = Simd::<i16, N>::splat(0);
-        total[0] = prefix as i16;
-        total[1] = suffix as i16;
-        for lock in locks {
-            let mask = (lock & Simd::splat(key))
-                .simd_eq(Simd::splat(0))
-                .cast::<i16>();
-            total += mask.select(Simd::splat(1), Simd::splat(0));
-        }
-        total.reduce_sum() as usize
-    })
-    .sum()
+    Ok(keys
+        .map(|&key| {
+            let (locks_prefix, locks, locks_suffix) = data.locks.as_simd::<N>();
+
+            let prefix = locks_prefix.iter().filter(|&lock| lock & key == 0).count();
+            let suffix = locks_suffix.iter().filter(|&lock| lock & key == 0).count();
+
+            let mut total = Simd::<i16, N>::splat(0);
+            total[0] = prefix as i16;
+            total[1] = suffix as i16;
+            for lock in locks {
+                let mask = (lock & Simd::splat(key))
+                    .simd_eq(Simd::splat(0))
+                    .cast::<i16>();
+                total += mask.select(Simd::splat(1), Simd::splat(0));
+            }
+            total.reduce_sum() as usize
+        })
+        .sum())
 }
 
-/// # Panics
+/// # Errors
+/// See [`Error`].
 #[cfg_attr(target_os = "none", inline(never))]
-pub fn solve_2(_input: &str) -> String {
+pub fn try_solve_2(_input: &str) -> Result<String, Error> {
     let mut result = String::new();
-    result.push('*').unwrap();
-    result
+    result.push('*').or_capacity()?;
+    Ok(result)
+}
+
+/// # Panics
+/// Panics if [`try_solve_1`] fails.
+pub fn solve_1(input: &str) -> usize {
+    try_solve_1::<SCHEMATICS>(input).unwrap()
+}
+
+/// # Panics
+/// Panics if [`try_solve_2`] fails.
+pub fn solve_2(input: &str) -> String {
+    try_solve_2(input).unwrap()
 }
 
 #[cfg(feature = "input")]
@@ -149,6 +178,23 @@ pub fn part_2() -> String {
     solve_2(INPUT)
 }
 
+pub struct Day<const SCHEMATICS: usize = { crate::SCHEMATICS }>;
+
+impl<const SCHEMATICS: usize> solution::Solution for Day<SCHEMATICS> {
+    const DAY: u32 = 25;
+
+    type Output1 = usize;
+    type Output2 = String;
+
+    fn try_solve_1(input: &str) -> Result<Self::Output1, Error> {
+        try_solve_1::<SCHEMATICS>(input)
+    }
+
+    fn try_solve_2(input: &str) -> Result<Self::Output2, Error> {
+        try_solve_2(input)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -201,12 +247,34 @@ mod tests {
     #[cfg(feature = "simd")]
     #[test]
     fn same_results_1_simd() {
-        assert_eq!(solve_1_simd::<64>(INPUT), 3);
+        assert_eq!(solve_1_simd::<64, SCHEMATICS>(INPUT), Ok(3));
     }
 
     #[cfg(all(feature = "simd", feature = "input"))]
     #[test]
     fn same_results_1_normal_vs_simd() {
-        assert_eq!(solve_1_simd::<64>(super::INPUT), solve_1(super::INPUT));
+        assert_eq!(
+            solve_1_simd::<64, SCHEMATICS>(super::INPUT),
+            try_solve_1::<SCHEMATICS>(super::INPUT)
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(try_solve_1::<SCHEMATICS>("#####\n.####"), Err(Error::Parse));
+        assert_eq!(
+            try_solve_1::<SCHEMATICS>(".....\n.....\n.....\n.....\n.....\n.....\n....."),
+            Err(Error::Parse)
+        );
     }
+
+    #[test]
+    fn custom_capacity() {
+        assert_eq!(try_solve_1::<3>(INPUT), Ok(3));
+        #[cfg(not(feature = "alloc"))]
+        assert_eq!(try_solve_1::<2>(INPUT), Err(Error::Capacity));
+    }
+
+    #[cfg(feature = "input")]
+    answers::tests!();
 }
@@ -23,36 +23,72 @@ unsafe extern "C" {
     static _stack_start: u32;
 }
 
+/// LED 1 lights up while a day runs and LED 4 while its part 2 runs, LED 2
+/// signals an invalid request or a failed part and LED 3 an unsupported day.
 #[allow(clippy::struct_field_names)]
 struct SimpleHandler<'d> {
     led_run: Output<'d>,
     led_invalid: Output<'d>,
     led_unsupported: Output<'d>,
+    led_part: Output<'d>,
 }
 
-impl embedded_aoc::Handler<u64, 1, 1_000_000> for SimpleHandler<'_> {
-    fn started(&mut self, _: embedded_aoc::Day, _: Instant) {
+impl SimpleHandler<'_> {
+    fn invalid(&mut self) {
+        self.led_run.set_high();
+        self.led_invalid.set_low();
+        self.led_unsupported.set_high();
+        self.led_part.set_high();
+    }
+}
+
+impl embedded_aoc::AsyncHandler<u64, 1, 1_000_000> for SimpleHandler<'_> {
+    async fn started(&mut self, _: embedded_aoc::Day, _: Instant) {
         self.led_run.set_low();
         self.led_invalid.set_high();
         self.led_unsupported.set_high();
+        self.led_part.set_high();
     }
 
-    fn ended(&mut self, _: embedded_aoc::Day, _: Duration, _: &str, _: &str) {
+    async fn ended(&mut self, _: embedded_aoc::Day, _: Duration, _: &str, _: &str) {
         self.led_run.set_high();
         self.led_invalid.set_high();
         self.led_unsupported.set_high();
+        self.led_part.set_high();
+    }
+
+    async fn part_started(&mut self, part: u8, _: Instant) {
+        if part == 2 {
+            self.led_part.set_low();
+        } else {
+            self.led_part.set_high();
+        }
     }
 
-    fn unsupported_day(&mut self) {
+    async fn part_finished(
+        &mut self,
+        _: u8,
+        _: Duration,
+        result: Result<&str, embedded_aoc::PartError>,
+    ) {
+        if result.is_err() {
+            self.invalid();
+        }
+    }
+
+    async fn unsupported_day(&mut self) {
         self.led_run.set_high();
         self.led_invalid.set_high();
         self.led_unsupported.set_low();
+        self.led_part.set_high();
     }
 
-    fn invalid_input(&mut self) {
-        self.led_run.set_high();
-        self.led_invalid.set_low();
-        self.led_unsupported.set_high();
+    async fn invalid_input(&mut self) {
+        self.invalid();
+    }
+
+    async fn error(&mut self, _: embedded_aoc::ErrorKind) {
+        self.invalid();
     }
 }
 
@@ -137,11 +173,13 @@ async fn main(spawner: embassy_executor::Spawner) {
     let led_run = Output::new(peripherals.P0_13, Level::High, OutputDrive::Standard);
     let led_invalid = Output::new(peripherals.P0_14, Level::High, OutputDrive::Standard);
     let led_unsupported = Output::new(peripherals.P0_15, Level::High, OutputDrive::Standard);
+    let led_part = Output::new(peripherals.P0_16, Level::High, OutputDrive::Standard);
 
     let handler = SimpleHandler {
         led_run,
         led_invalid,
         led_unsupported,
+        led_part,
     };
 
     let timer = Now;
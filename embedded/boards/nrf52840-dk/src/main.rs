@@ -22,46 +22,85 @@ unsafe extern "C" {
     static _stack_start: u32;
 }
 
+/// LED 1 lights up while a day runs and LED 4 while its part 2 runs, LED 2
+/// signals an invalid request or a failed part and LED 3 an unsupported day.
 #[allow(clippy::struct_field_names)]
-struct SimpleHandler<P1, P2, P3>
+struct SimpleHandler<P1, P2, P3, P4>
 where
     P1: OutputPin,
     P2: OutputPin,
     P3: OutputPin,
+    P4: OutputPin,
 {
     led_run: P1,
     led_invalid: P2,
     led_unsupported: P3,
+    led_part: P4,
 }
 
-impl<P1, P2, P3> embedded_aoc::Handler<u64, 1, 1_000_000> for SimpleHandler<P1, P2, P3>
+impl<P1, P2, P3, P4> SimpleHandler<P1, P2, P3, P4>
 where
     P1: OutputPin,
     P2: OutputPin,
     P3: OutputPin,
+    P4: OutputPin,
+{
+    fn invalid(&mut self) {
+        self.led_run.set_high().ok();
+        self.led_invalid.set_low().ok();
+        self.led_unsupported.set_high().ok();
+        self.led_part.set_high().ok();
+    }
+}
+
+impl<P1, P2, P3, P4> embedded_aoc::Handler<u64, 1, 1_000_000> for SimpleHandler<P1, P2, P3, P4>
+where
+    P1: OutputPin,
+    P2: OutputPin,
+    P3: OutputPin,
+    P4: OutputPin,
 {
     fn started(&mut self, _: embedded_aoc::Day, _: Instant) {
         self.led_run.set_low().ok();
         self.led_invalid.set_high().ok();
         self.led_unsupported.set_high().ok();
+        self.led_part.set_high().ok();
     }
 
     fn ended(&mut self, _: embedded_aoc::Day, _: Duration, _: &str, _: &str) {
         self.led_run.set_high().ok();
         self.led_invalid.set_high().ok();
         self.led_unsupported.set_high().ok();
+        self.led_part.set_high().ok();
+    }
+
+    fn part_started(&mut self, part: u8, _: Instant) {
+        if part == 2 {
+            self.led_part.set_low().ok();
+        } else {
+            self.led_part.set_high().ok();
+        }
+    }
+
+    fn part_finished(&mut self, _: u8, _: Duration, result: Result<&str, embedded_aoc::PartError>) {
+        if result.is_err() {
+            self.invalid();
+        }
     }
 
     fn unsupported_day(&mut self) {
         self.led_run.set_high().ok();
         self.led_invalid.set_high().ok();
         self.led_unsupported.set_low().ok();
+        self.led_part.set_high().ok();
     }
 
     fn invalid_input(&mut self) {
-        self.led_run.set_high().ok();
-        self.led_invalid.set_low().ok();
-        self.led_unsupported.set_high().ok();
+        self.invalid();
+    }
+
+    fn error(&mut self, _: embedded_aoc::ErrorKind) {
+        self.invalid();
     }
 }
 
@@ -104,11 +143,13 @@ fn main() -> ! {
     let led_run = port0.p0_13.into_push_pull_output(gpio::Level::High);
     let led_invalid = port0.p0_14.into_push_pull_output(gpio::Level::High);
     let led_unsupported = port0.p0_15.into_push_pull_output(gpio::Level::High);
+    let led_part = port0.p0_16.into_push_pull_output(gpio::Level::High);
 
     let handler = SimpleHandler {
         led_run,
         led_invalid,
         led_unsupported,
+        led_part,
     };
 
     let usb_bus =
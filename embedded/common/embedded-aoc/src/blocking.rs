@@ -1,10 +1,15 @@
 use core::ops;
+use core::pin::pin;
+use core::task::{Context, Poll, Waker};
 
 use embedded_io::{Read, Write};
 
+#[cfg(feature = "framed")]
+use crate::framed;
 use crate::{
-    info, trace, warn, Day, Duration, Handler, Instant, PartResult, Timer, END_INPUT_TAG,
-    START_INPUT_TAG,
+    info,
+    text::{Event, Receiver},
+    trace, warn, Duration, ErrorKind, Handler, Instant, PartResult, Timer, BUFFER_SIZE,
 };
 
 /// # Panics
@@ -18,109 +23,200 @@ where
 {
     trace!("run");
 
-    let mut buffer = [0; 25 * 1024];
+    let mut buffer = [0; BUFFER_SIZE];
+    #[cfg(feature = "framed")]
+    let mut response = [0; framed::RESPONSE_SIZE];
+    let mut receiver = Receiver::new();
+    // the bytes of framed requests, kept while the receiver is idle
+    let mut length = 0;
     loop {
-        let mut length = 0;
-        loop {
-            if length >= buffer.len() {
-                warn!("buffer overflow");
-                break;
-            }
-
-            match rx.read(&mut buffer[length..]) {
-                Err(_err) => {
-                    #[cfg(feature = "log")]
-                    warn!("error reading: {_err:?}");
-                }
-                Ok(0) => {
-                    trace!("reading 0 bytes");
-                }
-                Ok(count) => {
-                    debug_assert!(length + count <= buffer.len(), "invalid count");
-
-                    length += count;
-
-                    if let Ok(input) = core::str::from_utf8(&buffer[..length]) {
-                        match (input.find(START_INPUT_TAG), input.find(END_INPUT_TAG)) {
-                            (Some(start_position), Some(end_position)) => {
-                                let Ok(day) =
-                                    input[start_position + START_INPUT_TAG.len()..].parse::<Day>()
-                                else {
-                                    warn!("unsupported day");
-
-                                    handler.unsupported_day();
-
-                                    write!(&mut tx, "unsupported day\r\n").ok();
-
-                                    break;
-                                };
+        #[cfg(feature = "framed")]
+        {
+            length = answer_frames(
+                &mut buffer,
+                length,
+                &mut response,
+                &mut tx,
+                timer,
+                &mut handler,
+            );
+        }
 
-                                let input = input
-                                    [start_position + START_INPUT_TAG.len() + 2..end_position]
-                                    .trim();
+        let filled = length + receiver.len();
+        if filled >= buffer.len() {
+            warn!("buffer overflow");
 
-                                info!("[{}] start working on {}", day, day);
+            handler.error(ErrorKind::Overflow);
 
-                                let mut part_1 = PartResult::new();
-                                let mut part_2 = PartResult::new();
+            #[cfg(feature = "framed")]
+            if length > 0 {
+                tx.write_all(framed::overflow(&mut response)).ok();
+            }
 
-                                let start = timer.now();
+            length = 0;
+            receiver.reset();
+            continue;
+        }
 
-                                handler.started(day, start);
+        match rx.read(&mut buffer[filled..]) {
+            Err(_err) => {
+                #[cfg(feature = "log")]
+                warn!("error reading: {_err:?}");
 
-                                if day.solve_1(&mut part_1, input).is_err() {
-                                    warn!("part_1: buffer overflow");
-                                    break;
-                                }
+                handler.error(ErrorKind::Read);
+            }
+            Ok(0) => {
+                trace!("reading 0 bytes");
+            }
+            Ok(count) => {
+                debug_assert!(filled + count <= buffer.len(), "invalid count");
 
-                                if day.solve_2(&mut part_2, input).is_err() {
-                                    warn!("part_2: buffer overflow");
-                                    break;
-                                }
+                handler.input_received(count);
 
-                                let elapsed = timer.now() - start;
+                #[cfg(feature = "framed")]
+                let count = if receiver.is_idle() && buffer[0] == 0 {
+                    length += count;
 
-                                handler.ended(day, elapsed, part_1.as_str(), part_2.as_str());
+                    // a framed request, answered when it is complete
+                    let Some(stray) = framed::stray(&buffer[..length]) else {
+                        continue;
+                    };
+
+                    // a stray `0x00`, dropped, the rest is text
+                    buffer.copy_within(stray..length, 0);
+                    core::mem::take(&mut length) - stray
+                } else {
+                    count
+                };
+
+                // the text after a framed request is scanned with the new bytes
+                let count = core::mem::take(&mut length) + count;
+                if let Some(event) = receiver.received(&mut buffer, count) {
+                    answer_text(event, &mut tx, timer, &mut handler);
+                }
+            }
+        }
+    }
+}
 
-                                info!("[{}] part 1: {}", day, part_1.as_str());
-                                write!(&mut tx, "[{day}] part 1: {part_1}\r\n").ok();
+/// Answers a request of the text mode.
+// defmt does not support inlined format arguments
+#[allow(clippy::uninlined_format_args)]
+// the stream of a streamed request is consumed
+#[cfg_attr(not(feature = "streaming"), allow(clippy::needless_pass_by_value))]
+fn answer_text<const NOM: u32, const DENOM: u32>(
+    event: Event<'_>,
+    tx: &mut impl Write,
+    timer: &impl Timer<u64, NOM, DENOM>,
+    handler: &mut impl Handler<u64, NOM, DENOM>,
+) where
+    Instant<u64, NOM, DENOM>: ops::Sub<Output = Duration<u64, NOM, DENOM>>,
+{
+    let mut part_1 = PartResult::new();
+    let mut part_2 = PartResult::new();
+
+    let (day, solved) = match event {
+        Event::Request(day, input) => (
+            day,
+            ready(day.solve(input, (&mut part_1, &mut part_2), timer, handler)),
+        ),
+        #[cfg(feature = "streaming")]
+        Event::Streamed(stream) => (
+            stream.day(),
+            ready(stream.solve((&mut part_1, &mut part_2), timer, handler)),
+        ),
+        Event::UnsupportedDay => {
+            warn!("unsupported day");
+
+            handler.unsupported_day();
+
+            write!(tx, "unsupported day\r\n").ok();
+            return;
+        }
+        Event::InvalidInput => {
+            warn!("invalid input");
 
-                                info!("[{}] part 2: {}", day, part_2.as_str());
-                                write!(&mut tx, "[{day}] part 2: {part_2}\r\n").ok();
+            handler.invalid_input();
 
-                                info!(
-                                    "[{}] elapsed: {}ms ({}us)",
-                                    day,
-                                    elapsed.to_millis(),
-                                    elapsed.to_micros()
-                                );
-                                write!(
-                                    &mut tx,
-                                    "[{day}] elapsed: {}ms ({}us)\r\n",
-                                    elapsed.to_millis(),
-                                    elapsed.to_micros()
-                                )
-                                .ok();
+            write!(tx, "invalid input\r\n").ok();
+            return;
+        }
+        Event::InvalidUtf8 => {
+            warn!("invalid utf8 data");
 
-                                break;
-                            }
-                            (None, Some(_)) => {
-                                warn!("invalid input");
+            handler.error(ErrorKind::InvalidUtf8);
+            return;
+        }
+    };
+
+    let elapsed = match solved {
+        Ok(elapsed) => elapsed,
+        Err((part, err)) => {
+            warn!("[{}] part {}: {}", day, part, err);
+            write!(tx, "[{day}] part {part}: error: {err}\r\n").ok();
+            return;
+        }
+    };
+
+    info!("[{}] part 1: {}", day, part_1.as_str());
+    write!(tx, "[{day}] part 1: {part_1}\r\n").ok();
+
+    info!("[{}] part 2: {}", day, part_2.as_str());
+    write!(tx, "[{day}] part 2: {part_2}\r\n").ok();
+
+    info!(
+        "[{}] elapsed: {}ms ({}us)",
+        day,
+        elapsed.to_millis(),
+        elapsed.to_micros()
+    );
+    write!(
+        tx,
+        "[{day}] elapsed: {}ms ({}us)\r\n",
+        elapsed.to_millis(),
+        elapsed.to_micros()
+    )
+    .ok();
+}
 
-                                handler.invalid_input();
+/// Answers the complete frames at the start of `buffer[..length]`, returns
+/// the length of the rest.
+#[cfg(feature = "framed")]
+fn answer_frames<const NOM: u32, const DENOM: u32>(
+    buffer: &mut [u8],
+    mut length: usize,
+    response: &mut [u8; framed::RESPONSE_SIZE],
+    tx: &mut impl Write,
+    timer: &impl Timer<u64, NOM, DENOM>,
+    handler: &mut impl Handler<u64, NOM, DENOM>,
+) -> usize
+where
+    Instant<u64, NOM, DENOM>: ops::Sub<Output = Duration<u64, NOM, DENOM>>,
+{
+    let capacity = buffer.len();
+    while let Some((frame, end)) = framed::find(&buffer[..length]) {
+        let answer = ready(framed::answer(
+            &mut buffer[frame],
+            capacity,
+            response,
+            timer,
+            handler,
+        ));
+        tx.write_all(answer).ok();
+
+        buffer.copy_within(end..length, 0);
+        length -= end;
+    }
 
-                                write!(&mut tx, "invalid input\r\n").ok();
+    length
+}
 
-                                break;
-                            }
-                            _ => {}
-                        }
-                    } else {
-                        warn!("invalid utf8 data");
-                        break;
-                    }
-                }
-            }
-        }
+/// Completes `future` at once: the code shared with the nonblocking run awaits
+/// only the handler, and the [`AsyncHandler`](crate::AsyncHandler) of a
+/// [`Handler`] never waits.
+fn ready<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => unreachable!("a blocking handler waits"),
     }
 }
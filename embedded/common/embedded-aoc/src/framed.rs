@@ -0,0 +1,286 @@
+use core::ops;
+
+use crate::{
+    info,
+    protocol::{self, ErrorCode, Request, Response, SolveError},
+    warn, AsyncHandler, Day, Duration, ErrorKind, Instant, PartError, PartResult, Timer,
+};
+
+/// The size of the buffer of a response frame, enough for two [`PartResult`].
+pub(crate) const RESPONSE_SIZE: usize = 256;
+
+impl From<PartError> for SolveError {
+    fn from(err: PartError) -> Self {
+        match err {
+            PartError::Solve(err) => err.into(),
+            PartError::Overflow => SolveError::Answer,
+        }
+    }
+}
+
+/// The frame at the start of `buffer`, a framed request starts with `0x00` and
+/// [`MAGIC`](protocol::MAGIC).
+///
+/// Returns the range of the data of the first complete frame, after the
+/// leading delimiters, and the end of the frame.
+pub(crate) fn find(buffer: &[u8]) -> Option<(ops::Range<usize>, usize)> {
+    if buffer.first() != Some(&0) {
+        return None;
+    }
+
+    let start = buffer.iter().position(|&b| b != 0)?;
+    let end = start + buffer[start..].iter().position(|&b| b == 0)?;
+
+    Some((start..end, end + 1))
+}
+
+/// The length of the leading `0x00` of `buffer` if they are followed by
+/// another byte than [`MAGIC`](protocol::MAGIC), a stray `0x00` before the
+/// text.
+pub(crate) fn stray(buffer: &[u8]) -> Option<usize> {
+    let start = buffer.iter().position(|&b| b != 0)?;
+
+    (buffer[start] != protocol::MAGIC).then_some(start)
+}
+
+/// Answers the request in `frame`, the data between the delimiters, with a
+/// response frame in `response`.
+///
+/// `capacity` is the size of the receive buffer, reported by
+/// [`Request::Info`].
+// defmt does not support inlined format arguments
+#[allow(clippy::uninlined_format_args)]
+pub(crate) async fn answer<'a, const NOM: u32, const DENOM: u32>(
+    frame: &mut [u8],
+    capacity: usize,
+    response: &'a mut [u8; RESPONSE_SIZE],
+    timer: &impl Timer<u64, NOM, DENOM>,
+    handler: &mut impl AsyncHandler<u64, NOM, DENOM>,
+) -> &'a [u8]
+where
+    Instant<u64, NOM, DENOM>: ops::Sub<Output = Duration<u64, NOM, DENOM>>,
+{
+    let mut part_1 = PartResult::new();
+    let mut part_2 = PartResult::new();
+
+    let (id, message) = match protocol::decode::<Request>(frame) {
+        Ok((id, request)) => (
+            id,
+            match request {
+                Request::Ping => Response::Pong,
+                Request::Info => Response::Info {
+                    version: protocol::VERSION,
+                    capacity: u32::try_from(capacity).unwrap_or(u32::MAX),
+                },
+                Request::ListDays => Response::Days(
+                    (1..=25)
+                        .filter(|&day| Day::from_number(day).is_some())
+                        .fold(0, |days, day| days | 1 << day),
+                ),
+                Request::Solve { day, input } => {
+                    solve(day, input, (&mut part_1, &mut part_2), timer, handler).await
+                }
+            },
+        ),
+        Err(err) => {
+            warn!("invalid frame: {}", err);
+
+            handler.error(ErrorKind::Frame).await;
+
+            (
+                0,
+                Response::Error(match err {
+                    protocol::Error::Version(_) => ErrorCode::Version,
+                    protocol::Error::Message => ErrorCode::Request,
+                    _ => ErrorCode::Frame,
+                }),
+            )
+        }
+    };
+
+    encode(id, &message, response)
+}
+
+/// The error frame of a request that does not fit the receive buffer.
+pub(crate) fn overflow(response: &mut [u8; RESPONSE_SIZE]) -> &[u8] {
+    encode(0, &Response::Error(ErrorCode::Overflow), response)
+}
+
+#[allow(clippy::uninlined_format_args)]
+async fn solve<'a, const NOM: u32, const DENOM: u32>(
+    number: u8,
+    input: &str,
+    (part_1, part_2): (&'a mut PartResult, &'a mut PartResult),
+    timer: &impl Timer<u64, NOM, DENOM>,
+    handler: &mut impl AsyncHandler<u64, NOM, DENOM>,
+) -> Response<'a>
+where
+    Instant<u64, NOM, DENOM>: ops::Sub<Output = Duration<u64, NOM, DENOM>>,
+{
+    let Some(day) = Day::from_number(u32::from(number)) else {
+        warn!("unsupported day");
+
+        handler.unsupported_day().await;
+
+        return Response::Error(ErrorCode::UnsupportedDay);
+    };
+
+    match day
+        .solve(input.trim(), (&mut *part_1, &mut *part_2), timer, handler)
+        .await
+    {
+        Ok(elapsed) => {
+            info!("[{}] elapsed: {}us", day, elapsed.to_micros());
+
+            let (part_1, part_2): (&'a PartResult, &'a PartResult) = (part_1, part_2);
+
+            Response::Solved {
+                day: number,
+                part_1: part_1.as_str(),
+                part_2: part_2.as_str(),
+                elapsed_us: elapsed.to_micros(),
+            }
+        }
+        Err((part, err)) => {
+            warn!("[{}] part {}: {}", day, part, err);
+
+            Response::Error(ErrorCode::Solve {
+                part,
+                error: err.into(),
+            })
+        }
+    }
+}
+
+fn encode<'a>(id: u16, message: &Response, response: &'a mut [u8; RESPONSE_SIZE]) -> &'a [u8] {
+    // every response fits RESPONSE_SIZE, the fallback only guards a too small
+    // buffer
+    let length = match protocol::encode(id, message, response).map(<[u8]>::len) {
+        Ok(length) => length,
+        Err(_) => protocol::encode(id, &Response::Error(ErrorCode::Overflow), response)
+            .map_or(0, <[u8]>::len),
+    };
+
+    &response[..length]
+}
+
+#[cfg(all(test, feature = "day01"))]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use crate::DummyHandler;
+
+    use super::*;
+
+    struct Zero;
+
+    impl Timer<u64, 1, 1_000_000> for Zero {
+        fn now(&self) -> Instant<u64, 1, 1_000_000> {
+            Instant::<u64, 1, 1_000_000>::from_ticks(0)
+        }
+    }
+
+    fn request(id: u16, request: &Request) -> Vec<u8> {
+        let mut buffer = [0; 64];
+        Vec::from(protocol::encode(id, request, &mut buffer).unwrap())
+    }
+
+    fn answer_request(id: u16, message: &Request, check: impl FnOnce(u16, Response)) {
+        let mut buffer = request(id, message);
+        let (frame, end) = find(&buffer).unwrap();
+        assert_eq!(end, buffer.len());
+
+        let mut response = [0; RESPONSE_SIZE];
+        let mut handler = DummyHandler::default();
+        let mut answer = Vec::from(embassy_futures::block_on(answer(
+            &mut buffer[frame],
+            1024,
+            &mut response,
+            &Zero,
+            &mut handler,
+        )));
+
+        let (frame, _) = find(&answer).unwrap();
+        let (id, response) = protocol::decode(&mut answer[frame]).unwrap();
+        check(id, response);
+    }
+
+    #[test]
+    fn requests() {
+        answer_request(1, &Request::Ping, |id, response| {
+            assert_eq!((id, response), (1, Response::Pong));
+        });
+        answer_request(2, &Request::ListDays, |id, response| {
+            assert_eq!(id, 2);
+            assert!(matches!(response, Response::Days(days) if days & 0b10 != 0));
+        });
+        answer_request(
+            3,
+            &Request::Solve {
+                day: 1,
+                input: "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n",
+            },
+            |id, response| {
+                assert_eq!(
+                    (id, response),
+                    (
+                        3,
+                        Response::Solved {
+                            day: 1,
+                            part_1: "11",
+                            part_2: "31",
+                            elapsed_us: 0
+                        }
+                    )
+                );
+            },
+        );
+        answer_request(
+            4,
+            &Request::Solve {
+                day: 1,
+                input: "3 x",
+            },
+            |_, response| {
+                assert_eq!(
+                    response,
+                    Response::Error(ErrorCode::Solve {
+                        part: 1,
+                        error: SolveError::Parse
+                    })
+                );
+            },
+        );
+    }
+
+    #[test]
+    fn frames() {
+        assert_eq!(find(b"START"), None);
+        assert_eq!(find(b"\0\0\x01"), None);
+        assert_eq!(find(b"\0\0\x01\0\x02"), Some((2..3, 4)));
+
+        assert_eq!(stray(b"\0\0"), None);
+        assert_eq!(stray(b"\0\0\xa5"), None);
+        assert_eq!(stray(b"\0\0START"), Some(2));
+
+        let mut buffer = request(1, &Request::Ping);
+        let (frame, _) = find(&buffer).unwrap();
+        buffer[frame.start + 1] ^= 0x40;
+
+        let mut response = [0; RESPONSE_SIZE];
+        let mut answer = Vec::from(embassy_futures::block_on(answer(
+            &mut buffer[frame],
+            1024,
+            &mut response,
+            &Zero,
+            &mut DummyHandler::default(),
+        )));
+        let (frame, _) = find(&answer).unwrap();
+        assert_eq!(
+            protocol::decode(&mut answer[frame]),
+            Ok((0, Response::Error(ErrorCode::Frame)))
+        );
+    }
+}
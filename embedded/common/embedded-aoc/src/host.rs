@@ -0,0 +1,535 @@
+//! Runs [`run`](crate::run) on a host, to test the protocol without a board.
+//!
+//! [`StdIo`] adapts any [`std::io`] stream, for example a `TcpStream` or the
+//! ends of a [`pipe`], and [`StdTimer`] measures with [`std::time::Instant`].
+
+use std::collections::VecDeque;
+use std::io;
+use std::sync::{Arc, Condvar, Mutex, MutexGuard, PoisonError};
+use std::time;
+
+#[cfg(feature = "blocking")]
+use embedded_io::ErrorType;
+#[cfg(all(feature = "nonblocking", not(feature = "blocking")))]
+use embedded_io_async::ErrorType;
+
+use crate::{Instant, Timer};
+
+/// An [`embedded_io`] stream over a [`std::io`] one.
+pub struct StdIo<T>(pub T);
+
+impl<T> ErrorType for StdIo<T> {
+    type Error = io::Error;
+}
+
+#[cfg(feature = "blocking")]
+impl<T: io::Read> embedded_io::Read for StdIo<T> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.0.read(buf)
+    }
+}
+
+#[cfg(feature = "blocking")]
+impl<T: io::Write> embedded_io::Write for StdIo<T> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.0.write(buf)
+    }
+
+    fn flush(&mut self) -> Result<(), Self::Error> {
+        self.0.flush()
+    }
+}
+
+/// The reads and the writes block the executor, enough for a single task.
+#[cfg(feature = "nonblocking")]
+impl<T: io::Read> embedded_io_async::Read for StdIo<T> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        self.0.read(buf)
+    }
+}
+
+#[cfg(feature = "nonblocking")]
+impl<T: io::Write> embedded_io_async::Write for StdIo<T> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        self.0.write(buf)
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        self.0.flush()
+    }
+}
+
+/// A [`Timer`] with a microsecond tick, from its creation.
+pub struct StdTimer(time::Instant);
+
+impl StdTimer {
+    #[must_use]
+    pub fn new() -> Self {
+        Self(time::Instant::now())
+    }
+}
+
+impl Default for StdTimer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Timer<u64, 1, 1_000_000> for StdTimer {
+    fn now(&self) -> Instant<u64, 1, 1_000_000> {
+        Instant::<u64, 1, 1_000_000>::from_ticks(
+            u64::try_from(self.0.elapsed().as_micros()).unwrap_or(u64::MAX),
+        )
+    }
+}
+
+#[derive(Default)]
+struct State {
+    bytes: VecDeque<u8>,
+    /// One of the ends has been dropped.
+    closed: bool,
+}
+
+#[derive(Default)]
+struct Shared {
+    state: Mutex<State>,
+    changed: Condvar,
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn wait<'a>(&self, state: MutexGuard<'a, State>) -> MutexGuard<'a, State> {
+        self.changed
+            .wait(state)
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn close(&self) {
+        self.lock().closed = true;
+        self.changed.notify_all();
+    }
+}
+
+/// The reading end of a [`pipe`].
+pub struct PipeReader(Arc<Shared>);
+
+/// The writing end of a [`pipe`].
+pub struct PipeWriter(Arc<Shared>);
+
+/// An in-memory pipe.
+///
+/// A read waits for some bytes and returns all the available ones that fit,
+/// so the reads see the writes as they are fragmented by
+/// [`flush`](io::Write::flush), that waits until the reader has taken all the
+/// written bytes.
+#[must_use]
+pub fn pipe() -> (PipeReader, PipeWriter) {
+    let shared = Arc::new(Shared::default());
+    (PipeReader(shared.clone()), PipeWriter(shared))
+}
+
+impl io::Read for PipeReader {
+    /// Returns 0 when the writer has been dropped and the pipe is empty.
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if buf.is_empty() {
+            return Ok(0);
+        }
+
+        let mut state = self.0.lock();
+        while state.bytes.is_empty() && !state.closed {
+            state = self.0.wait(state);
+        }
+
+        let count = buf.len().min(state.bytes.len());
+        for (target, byte) in buf.iter_mut().zip(state.bytes.drain(..count)) {
+            *target = byte;
+        }
+        self.0.changed.notify_all();
+
+        Ok(count)
+    }
+}
+
+impl Drop for PipeReader {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+impl io::Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let mut state = self.0.lock();
+        if state.closed {
+            return Err(io::ErrorKind::BrokenPipe.into());
+        }
+
+        state.bytes.extend(buf);
+        self.0.changed.notify_all();
+
+        Ok(buf.len())
+    }
+
+    /// Waits until the reader has taken all the written bytes.
+    fn flush(&mut self) -> io::Result<()> {
+        let mut state = self.0.lock();
+        while !state.bytes.is_empty() {
+            if state.closed {
+                return Err(io::ErrorKind::BrokenPipe.into());
+            }
+
+            state = self.0.wait(state);
+        }
+
+        Ok(())
+    }
+}
+
+impl Drop for PipeWriter {
+    fn drop(&mut self) {
+        self.0.close();
+    }
+}
+
+#[cfg(all(test, feature = "day01"))]
+mod tests {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::string::{String, ToString};
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::OnceLock;
+    use std::thread;
+    use std::vec::Vec;
+
+    use crate::{Day, Duration, ErrorKind, Handler, PartError, BUFFER_SIZE};
+
+    use super::*;
+
+    const INPUT: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    struct Board {
+        tx: PipeWriter,
+        rx: BufReader<PipeReader>,
+    }
+
+    impl Board {
+        /// Writes `bytes` in chunks of `size`, each one read on its own.
+        fn send(&mut self, bytes: &[u8], size: usize) {
+            for chunk in bytes.chunks(size) {
+                self.tx.write_all(chunk).unwrap();
+                self.tx.flush().unwrap();
+            }
+        }
+
+        /// Sends a request of the text mode.
+        fn ask(&mut self, day: &str, input: &str) {
+            let request = std::format!("START INPUT DAY: {day}\n{input}\nEND INPUT\n");
+            self.send(request.as_bytes(), request.len());
+        }
+
+        fn line(&mut self) -> String {
+            let mut line = String::new();
+            self.rx.read_line(&mut line).unwrap();
+            line.trim_end().to_string()
+        }
+
+        /// Checks the answers of the example of day 1.
+        fn solved(&mut self) {
+            assert_eq!(self.line(), "[01] part 1: 11");
+            assert_eq!(self.line(), "[01] part 2: 31");
+            assert!(self.line().starts_with("[01] elapsed: "));
+        }
+    }
+
+    /// The events of the handler of the board, but the received bytes.
+    static EVENTS: Mutex<Vec<String>> = Mutex::new(Vec::new());
+
+    /// The bytes received by the board.
+    static RECEIVED: AtomicUsize = AtomicUsize::new(0);
+
+    struct Recorder;
+
+    impl Recorder {
+        fn record(event: String) {
+            EVENTS
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(event);
+        }
+    }
+
+    impl Handler<u64, 1, 1_000_000> for Recorder {
+        fn started(&mut self, day: Day, _: Instant<u64, 1, 1_000_000>) {
+            Self::record(std::format!("started {day}"));
+        }
+
+        fn ended(&mut self, day: Day, _: Duration<u64, 1, 1_000_000>, part_1: &str, part_2: &str) {
+            Self::record(std::format!("ended {day}: {part_1} {part_2}"));
+        }
+
+        fn part_started(&mut self, part: u8, _: Instant<u64, 1, 1_000_000>) {
+            Self::record(std::format!("part {part}"));
+        }
+
+        fn part_finished(
+            &mut self,
+            part: u8,
+            _: Duration<u64, 1, 1_000_000>,
+            result: Result<&str, PartError>,
+        ) {
+            Self::record(std::format!("part {part}: {result:?}"));
+        }
+
+        fn input_received(&mut self, bytes: usize) {
+            RECEIVED.fetch_add(bytes, Ordering::Relaxed);
+        }
+
+        fn error(&mut self, kind: ErrorKind) {
+            Self::record(std::format!("error {kind:?}"));
+        }
+    }
+
+    /// The board shared by the tests, the nonblocking `run` can be started
+    /// only once.
+    fn board() -> MutexGuard<'static, Board> {
+        static BOARD: OnceLock<Mutex<Board>> = OnceLock::new();
+
+        BOARD
+            .get_or_init(|| {
+                let (rx, host_tx) = pipe();
+                let (host_rx, tx) = pipe();
+
+                thread::spawn(move || {
+                    let io = (StdIo(rx), StdIo(tx));
+                    let handler = Recorder;
+
+                    #[cfg(feature = "blocking")]
+                    crate::run(io, &StdTimer::new(), handler);
+
+                    #[cfg(not(feature = "blocking"))]
+                    embassy_futures::block_on(crate::run(io, &StdTimer::new(), handler));
+                });
+
+                Mutex::new(Board {
+                    tx: host_tx,
+                    rx: BufReader::new(host_rx),
+                })
+            })
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+    }
+
+    #[test]
+    fn pipes() {
+        let (mut rx, mut tx) = pipe();
+
+        tx.write_all(b"hello").unwrap();
+        let mut buffer = [0; 3];
+        assert_eq!(rx.read(&mut buffer).unwrap(), 3);
+        assert_eq!(&buffer, b"hel");
+
+        drop(tx);
+        let mut rest = Vec::new();
+        rx.read_to_end(&mut rest).unwrap();
+        assert_eq!(rest, b"lo");
+    }
+
+    #[test]
+    fn fragmented_reads() {
+        let mut board = board();
+
+        let request = std::format!("START INPUT DAY: 01\n{INPUT}\nEND INPUT\n");
+        for size in [1, 3, request.len()] {
+            board.send(request.as_bytes(), size);
+            board.solved();
+        }
+    }
+
+    #[test]
+    fn overflow() {
+        let mut board = board();
+
+        let mut request = std::format!("START INPUT DAY: 01\n{INPUT}");
+        while request.len() < BUFFER_SIZE {
+            request.push_str(INPUT);
+        }
+        request.truncate(BUFFER_SIZE);
+        board.send(request.as_bytes(), BUFFER_SIZE);
+
+        // the overflow is not answered, the next request starts from scratch
+        board.ask("01", INPUT);
+        board.solved();
+    }
+
+    #[test]
+    fn invalid_utf8() {
+        let mut board = board();
+
+        EVENTS.lock().unwrap().clear();
+
+        board.send(b"START INPUT DAY: 01\n\xff\xfe\nEND INPUT\n", 32);
+
+        // a streamed input is checked a line at a time, by the day
+        #[cfg(feature = "streaming")]
+        assert_eq!(board.line(), "[01] part 1: error: parse error");
+
+        board.ask("01", INPUT);
+        board.solved();
+
+        #[cfg(not(feature = "streaming"))]
+        assert_eq!(
+            EVENTS.lock().unwrap().first().map(String::as_str),
+            Some("error InvalidUtf8")
+        );
+    }
+
+    #[test]
+    fn invalid_requests() {
+        let mut board = board();
+
+        board.ask("26", "#####");
+        assert_eq!(board.line(), "unsupported day");
+
+        board.send(b"1   2\nEND INPUT\n", 32);
+        assert_eq!(board.line(), "invalid input");
+
+        board.ask("01", "3   x");
+        assert_eq!(board.line(), "[01] part 1: error: parse error at 1:5");
+    }
+
+    #[test]
+    fn lifecycle() {
+        let mut board = board();
+
+        EVENTS.lock().unwrap().clear();
+        RECEIVED.store(0, Ordering::Relaxed);
+
+        let overflow = std::format!("START INPUT DAY: 01\n{}", "1".repeat(BUFFER_SIZE));
+        board.send(overflow.as_bytes(), overflow.len());
+
+        board.ask("01", "3   x");
+        assert_eq!(board.line(), "[01] part 1: error: parse error at 1:5");
+
+        board.ask("01", INPUT);
+        board.solved();
+
+        assert_eq!(
+            *EVENTS.lock().unwrap(),
+            [
+                "error Overflow",
+                "started 01",
+                "part 1",
+                "part 1: Err(Solve(ParseAt { line: 1, column: 5 }))",
+                "started 01",
+                "part 1",
+                "part 1: Ok(\"11\")",
+                "part 2",
+                "part 2: Ok(\"31\")",
+                "ended 01: 11 31",
+            ]
+        );
+
+        let request = std::format!("START INPUT DAY: 01\n{INPUT}\nEND INPUT\n");
+        assert_eq!(
+            RECEIVED.load(Ordering::Relaxed),
+            overflow.len() + "START INPUT DAY: 01\n3   x\nEND INPUT\n".len() + request.len()
+        );
+    }
+
+    #[cfg(all(feature = "streaming", feature = "day02"))]
+    #[test]
+    fn streaming() {
+        let mut board = board();
+
+        // twice the receive buffer, only a line at a time is kept
+        let input = "7 6 4 2 1\n".repeat(2 * BUFFER_SIZE / 10);
+        board.ask("02", &input);
+        assert_eq!(
+            board.line(),
+            std::format!("[02] part 1: {}", 2 * BUFFER_SIZE / 10)
+        );
+        assert_eq!(
+            board.line(),
+            std::format!("[02] part 2: {}", 2 * BUFFER_SIZE / 10)
+        );
+        assert!(board.line().starts_with("[02] elapsed: "));
+
+        board.ask("02", "7 6 4 2 1\n1 2 x");
+        assert_eq!(board.line(), "[02] part 1: error: parse error");
+    }
+
+    #[cfg(feature = "streaming")]
+    #[test]
+    fn streaming_lines() {
+        let mut board = board();
+
+        // a whole input, far longer than the receive buffer of a streaming only build
+        let copies = day01::LINES / INPUT.lines().count();
+        let input = INPUT.repeat(copies);
+        board.ask("01", &input);
+        assert_eq!(board.line(), std::format!("[01] part 1: {}", 11 * copies));
+        assert_eq!(
+            board.line(),
+            std::format!("[01] part 2: {}", 31 * copies * copies)
+        );
+        assert!(board.line().starts_with("[01] elapsed: "));
+    }
+
+    #[cfg(feature = "framed")]
+    #[test]
+    fn frames() {
+        use crate::protocol::{self, Request, Response};
+
+        let mut board = board();
+
+        let mut buffer = [0; 128];
+        let frame = protocol::encode(
+            9,
+            &Request::Solve {
+                day: 1,
+                input: INPUT,
+            },
+            &mut buffer,
+        )
+        .unwrap();
+        board.send(frame, 5);
+
+        let mut answer = Vec::new();
+        board.rx.read_until(0, &mut answer).unwrap();
+        assert_eq!(answer, [0]);
+        board.rx.read_until(0, &mut answer).unwrap();
+
+        let frame = answer.len() - 1;
+        assert!(matches!(
+            protocol::decode(&mut answer[1..frame]),
+            Ok((
+                9,
+                Response::Solved {
+                    day: 1,
+                    part_1: "11",
+                    part_2: "31",
+                    ..
+                }
+            ))
+        ));
+
+        // the text mode still works after a frame
+        board.ask("01", INPUT);
+        board.solved();
+    }
+
+    #[cfg(feature = "framed")]
+    #[test]
+    fn stray_delimiter() {
+        let mut board = board();
+
+        board.send(b"\0", 1);
+        board.ask("01", INPUT);
+        board.solved();
+
+        let request = std::format!("\0START INPUT DAY: 01\n{INPUT}\nEND INPUT\n");
+        board.send(request.as_bytes(), request.len());
+        board.solved();
+    }
+}
@@ -1,15 +1,22 @@
 #![no_std]
 
+#[cfg(feature = "std")]
+extern crate std;
+
 #[cfg(any(feature = "blocking", feature = "nonblocking"))]
 use core::fmt::Write as _;
 
-#[cfg(any(feature = "blocking", feature = "nonblocking"))]
 use core::fmt;
+#[cfg(any(feature = "blocking", feature = "nonblocking"))]
+use core::ops;
 
 use fugit::{Duration, Instant};
 
 use heapless::String as HLString;
 
+#[cfg(any(feature = "blocking", feature = "nonblocking"))]
+use solution::Solution;
+
 #[cfg(all(feature = "defmt", any(feature = "blocking", feature = "nonblocking")))]
 use defmt::{info, trace, warn};
 
@@ -26,9 +33,104 @@ mod nonblocking;
 #[cfg(feature = "nonblocking")]
 pub use nonblocking::run;
 
+#[cfg(feature = "framed")]
+pub mod protocol;
+
+#[cfg(all(feature = "framed", any(feature = "blocking", feature = "nonblocking")))]
+mod framed;
+
+#[cfg(all(
+    feature = "streaming",
+    any(feature = "blocking", feature = "nonblocking")
+))]
+mod stream;
+
+#[cfg(any(feature = "blocking", feature = "nonblocking"))]
+mod text;
+
+#[cfg(all(feature = "std", any(feature = "blocking", feature = "nonblocking")))]
+pub mod host;
+
 #[allow(dead_code)]
 type PartResult = HLString<64>;
 
+/// The size of the receive buffer of `run`, a line if the enabled days all
+/// stream their input (see `stream`) and no frame is kept, otherwise a whole
+/// input.
+#[cfg(any(feature = "blocking", feature = "nonblocking"))]
+const BUFFER_SIZE: usize = if cfg!(all(
+    feature = "streaming",
+    not(feature = "framed"),
+    not(any(
+        feature = "day03",
+        feature = "day04",
+        feature = "day05",
+        feature = "day06",
+        feature = "day07",
+        feature = "day08",
+        feature = "day09",
+        feature = "day10",
+        feature = "day11",
+        feature = "day12",
+        feature = "day13",
+        feature = "day14",
+        feature = "day15",
+        feature = "day16",
+        feature = "day17",
+        feature = "day18",
+        feature = "day19",
+        feature = "day20",
+        feature = "day21",
+        feature = "day22",
+        feature = "day23",
+        feature = "day24",
+        feature = "day25"
+    ))
+)) {
+    256
+} else {
+    25 * 1024
+};
+
+/// Why a part could not produce its answer.
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PartError {
+    /// The solver rejected the input.
+    Solve(solution::Error),
+    /// The answer does not fit a [`PartResult`].
+    Overflow,
+}
+
+impl From<solution::Error> for PartError {
+    fn from(err: solution::Error) -> Self {
+        PartError::Solve(err)
+    }
+}
+
+impl fmt::Display for PartError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            PartError::Solve(err) => write!(f, "{err}"),
+            PartError::Overflow => write!(f, "buffer overflow"),
+        }
+    }
+}
+
+/// Why a request has been dropped, see [`Handler::error`].
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorKind {
+    /// The request does not fit the receive buffer.
+    Overflow,
+    /// The input of a request is not valid UTF-8.
+    InvalidUtf8,
+    /// A framed request is corrupted or of another protocol version.
+    Frame,
+    /// Reading from the serial port failed.
+    Read,
+}
+
 #[cfg(any(feature = "blocking", feature = "nonblocking"))]
 const START_INPUT_TAG: &str = "START INPUT DAY: ";
 
@@ -92,242 +194,330 @@ pub enum Day {
 
 #[cfg(any(feature = "blocking", feature = "nonblocking"))]
 impl Day {
-    fn to_string(result: &mut PartResult, value: impl fmt::Display) -> Result<(), fmt::Error> {
-        write!(result, "{value}")
+    fn part_1<S: Solution>(result: &mut PartResult, input: &str) -> Result<(), PartError> {
+        let value = S::try_solve_1(input)?;
+        write!(result, "{value}").map_err(|_| PartError::Overflow)
     }
 
-    fn solve_1(self, result: &mut PartResult, input: &str) -> Result<(), fmt::Error> {
+    fn part_2<S: Solution>(result: &mut PartResult, input: &str) -> Result<(), PartError> {
+        let value = S::try_solve_2(input)?;
+        write!(result, "{value}").map_err(|_| PartError::Overflow)
+    }
+
+    fn solve_1(self, result: &mut PartResult, input: &str) -> Result<(), PartError> {
         match self {
             #[cfg(feature = "day01")]
-            Day::Day01 => Self::to_string(result, day01::solve_1(input)),
+            Day::Day01 => Self::part_1::<day01::Day>(result, input),
             #[cfg(feature = "day02")]
-            Day::Day02 => Self::to_string(result, day02::solve_1(input)),
+            Day::Day02 => Self::part_1::<day02::Day>(result, input),
             #[cfg(feature = "day03")]
-            Day::Day03 => Self::to_string(result, day03::solve_1(input)),
+            Day::Day03 => Self::part_1::<day03::Day>(result, input),
             #[cfg(feature = "day04")]
-            Day::Day04 => Self::to_string(result, day04::solve_1(input)),
+            Day::Day04 => Self::part_1::<day04::Day>(result, input),
             #[cfg(feature = "day05")]
-            Day::Day05 => Self::to_string(result, day05::solve_1(input)),
+            Day::Day05 => Self::part_1::<day05::Day>(result, input),
             #[cfg(feature = "day06")]
-            Day::Day06 => Self::to_string(result, day06::solve_1(input)),
+            Day::Day06 => Self::part_1::<day06::Day>(result, input),
             #[cfg(feature = "day07")]
-            Day::Day07 => Self::to_string(result, day07::solve_1(input)),
+            Day::Day07 => Self::part_1::<day07::Day>(result, input),
             #[cfg(feature = "day08")]
-            Day::Day08 => Self::to_string(result, day08::solve_1(input)),
+            Day::Day08 => Self::part_1::<day08::Day>(result, input),
             #[cfg(feature = "day09")]
-            Day::Day09 => Self::to_string(result, day09::solve_1(input)),
+            Day::Day09 => Self::part_1::<day09::Day>(result, input),
             #[cfg(feature = "day10")]
-            Day::Day10 => Self::to_string(result, day10::solve_1(input)),
+            Day::Day10 => Self::part_1::<day10::Day>(result, input),
             #[cfg(feature = "day11")]
-            Day::Day11 => Self::to_string(result, day11::solve_1(input)),
+            Day::Day11 => Self::part_1::<day11::Day>(result, input),
             #[cfg(feature = "day12")]
-            Day::Day12 => Self::to_string(result, day12::solve_1(input)),
+            Day::Day12 => Self::part_1::<day12::Day>(result, input),
             #[cfg(feature = "day13")]
-            Day::Day13 => Self::to_string(result, day13::solve_1(input)),
+            Day::Day13 => Self::part_1::<day13::Day>(result, input),
             #[cfg(feature = "day14")]
-            Day::Day14 => Self::to_string(
-                result,
-                day14::solve_1::<{ day14::WIDTH }, { day14::HEIGHT }>(input),
-            ),
+            Day::Day14 => Self::part_1::<day14::Day>(result, input),
             #[cfg(feature = "day15")]
-            Day::Day15 => Self::to_string(result, day15::solve_1(input)),
+            Day::Day15 => Self::part_1::<day15::Day>(result, input),
             #[cfg(feature = "day16")]
-            Day::Day16 => Self::to_string(result, day16::solve_1(input)),
+            Day::Day16 => Self::part_1::<day16::Day>(result, input),
             #[cfg(feature = "day17")]
-            Day::Day17 => Self::to_string(result, day17::solve_1(input)),
+            Day::Day17 => Self::part_1::<day17::Day>(result, input),
             #[cfg(feature = "day18")]
-            Day::Day18 => Self::to_string(result, day18::solve_1(input)),
+            Day::Day18 => Self::part_1::<day18::Day>(result, input),
             #[cfg(feature = "day19")]
-            Day::Day19 => Self::to_string(result, day19::solve_1(input)),
+            Day::Day19 => Self::part_1::<day19::Day>(result, input),
             #[cfg(feature = "day20")]
-            Day::Day20 => Self::to_string(result, day20::solve_1(input)),
+            Day::Day20 => Self::part_1::<day20::Day>(result, input),
             #[cfg(feature = "day21")]
-            Day::Day21 => Self::to_string(result, day21::solve_1(input)),
+            Day::Day21 => Self::part_1::<day21::Day>(result, input),
             #[cfg(feature = "day22")]
-            Day::Day22 => Self::to_string(result, day22::solve_1(input)),
+            Day::Day22 => Self::part_1::<day22::Day>(result, input),
             #[cfg(feature = "day23")]
-            Day::Day23 => Self::to_string(result, day23::solve_1(input)),
+            Day::Day23 => Self::part_1::<day23::Day>(result, input),
             #[cfg(feature = "day24")]
-            Day::Day24 => Self::to_string(result, day24::solve_1(input)),
+            Day::Day24 => Self::part_1::<day24::Day>(result, input),
             #[cfg(feature = "day25")]
-            Day::Day25 => Self::to_string(result, day25::solve_1(input)),
+            Day::Day25 => Self::part_1::<day25::Day>(result, input),
         }
     }
 
-    fn solve_2(self, result: &mut PartResult, input: &str) -> Result<(), fmt::Error> {
+    fn solve_2(self, result: &mut PartResult, input: &str) -> Result<(), PartError> {
         match self {
             #[cfg(feature = "day01")]
-            Day::Day01 => Self::to_string(result, day01::solve_2(input)),
+            Day::Day01 => Self::part_2::<day01::Day>(result, input),
             #[cfg(feature = "day02")]
-            Day::Day02 => Self::to_string(result, day02::solve_2(input)),
+            Day::Day02 => Self::part_2::<day02::Day>(result, input),
             #[cfg(feature = "day03")]
-            Day::Day03 => Self::to_string(result, day03::solve_2(input)),
+            Day::Day03 => Self::part_2::<day03::Day>(result, input),
             #[cfg(feature = "day04")]
-            Day::Day04 => Self::to_string(result, day04::solve_2(input)),
+            Day::Day04 => Self::part_2::<day04::Day>(result, input),
             #[cfg(feature = "day05")]
-            Day::Day05 => Self::to_string(result, day05::solve_2(input)),
+            Day::Day05 => Self::part_2::<day05::Day>(result, input),
             #[cfg(feature = "day06")]
-            Day::Day06 => Self::to_string(result, day06::solve_2(input)),
+            Day::Day06 => Self::part_2::<day06::Day>(result, input),
             #[cfg(feature = "day07")]
-            Day::Day07 => Self::to_string(result, day07::solve_2(input)),
+            Day::Day07 => Self::part_2::<day07::Day>(result, input),
             #[cfg(feature = "day08")]
-            Day::Day08 => Self::to_string(result, day08::solve_2(input)),
+            Day::Day08 => Self::part_2::<day08::Day>(result, input),
             #[cfg(feature = "day09")]
-            Day::Day09 => Self::to_string(result, day09::solve_2(input)),
+            Day::Day09 => Self::part_2::<day09::Day>(result, input),
             #[cfg(feature = "day10")]
-            Day::Day10 => Self::to_string(result, day10::solve_2(input)),
+            Day::Day10 => Self::part_2::<day10::Day>(result, input),
             #[cfg(feature = "day11")]
-            Day::Day11 => Self::to_string(result, day11::solve_2(input)),
+            Day::Day11 => Self::part_2::<day11::Day>(result, input),
             #[cfg(feature = "day12")]
-            Day::Day12 => Self::to_string(result, day12::solve_2(input)),
+            Day::Day12 => Self::part_2::<day12::Day>(result, input),
             #[cfg(feature = "day13")]
-            Day::Day13 => Self::to_string(result, day13::solve_2(input)),
+            Day::Day13 => Self::part_2::<day13::Day>(result, input),
             #[cfg(feature = "day14")]
-            Day::Day14 => Self::to_string(result, day14::solve_2(input)),
+            Day::Day14 => Self::part_2::<day14::Day>(result, input),
             #[cfg(feature = "day15")]
-            Day::Day15 => Self::to_string(result, day15::solve_2(input)),
+            Day::Day15 => Self::part_2::<day15::Day>(result, input),
             #[cfg(feature = "day16")]
-            Day::Day16 => Self::to_string(result, day16::solve_2(input)),
+            Day::Day16 => Self::part_2::<day16::Day>(result, input),
             #[cfg(feature = "day17")]
-            Day::Day17 => Self::to_string(result, day17::solve_2(input)),
+            Day::Day17 => Self::part_2::<day17::Day>(result, input),
             #[cfg(feature = "day18")]
-            Day::Day18 => Self::to_string(result, day18::solve_2(input)),
+            Day::Day18 => Self::part_2::<day18::Day>(result, input),
             #[cfg(feature = "day19")]
-            Day::Day19 => Self::to_string(result, day19::solve_2(input)),
+            Day::Day19 => Self::part_2::<day19::Day>(result, input),
             #[cfg(feature = "day20")]
-            Day::Day20 => Self::to_string(result, day20::solve_2(input)),
+            Day::Day20 => Self::part_2::<day20::Day>(result, input),
             #[cfg(feature = "day21")]
-            Day::Day21 => Self::to_string(result, day21::solve_2(input)),
+            Day::Day21 => Self::part_2::<day21::Day>(result, input),
             #[cfg(feature = "day22")]
-            Day::Day22 => Self::to_string(result, day22::solve_2(input)),
+            Day::Day22 => Self::part_2::<day22::Day>(result, input),
             #[cfg(feature = "day23")]
-            Day::Day23 => Self::to_string(result, day23::solve_2(input)),
+            Day::Day23 => Self::part_2::<day23::Day>(result, input),
             #[cfg(feature = "day24")]
-            Day::Day24 => Self::to_string(result, day24::solve_2(input)),
+            Day::Day24 => Self::part_2::<day24::Day>(result, input),
             #[cfg(feature = "day25")]
-            Day::Day25 => Self::to_string(result, day25::solve_2(input)),
+            Day::Day25 => Self::part_2::<day25::Day>(result, input),
         }
     }
+
+    /// Solves both parts of the day, see [`solve`].
+    // defmt does not support inlined format arguments
+    #[allow(clippy::uninlined_format_args)]
+    async fn solve<const NOM: u32, const DENOM: u32>(
+        self,
+        input: &str,
+        parts: (&mut PartResult, &mut PartResult),
+        timer: &impl Timer<u64, NOM, DENOM>,
+        handler: &mut impl AsyncHandler<u64, NOM, DENOM>,
+    ) -> Result<Duration<u64, NOM, DENOM>, (u8, PartError)>
+    where
+        Instant<u64, NOM, DENOM>: ops::Sub<Output = Duration<u64, NOM, DENOM>>,
+    {
+        info!("[{}] start working on {}", self, self);
+
+        let solve_part = |part, result: &mut PartResult| {
+            if part == 1 {
+                self.solve_1(result, input)
+            } else {
+                self.solve_2(result, input)
+            }
+        };
+
+        solve(self, solve_part, parts, timer, handler).await
+    }
 }
 
-impl core::str::FromStr for Day {
-    type Err = &'static str;
+/// Solves the two parts of `day` with `solve_part`, between
+/// [`Handler::started`] and [`Handler::ended`], returns the elapsed time or the
+/// failed part.
+///
+/// Each part is reported with [`Handler::part_started`] and
+/// [`Handler::part_finished`], the failed one too.
+#[cfg(any(feature = "blocking", feature = "nonblocking"))]
+async fn solve<const NOM: u32, const DENOM: u32>(
+    day: Day,
+    mut solve_part: impl FnMut(u8, &mut PartResult) -> Result<(), PartError>,
+    (part_1, part_2): (&mut PartResult, &mut PartResult),
+    timer: &impl Timer<u64, NOM, DENOM>,
+    handler: &mut impl AsyncHandler<u64, NOM, DENOM>,
+) -> Result<Duration<u64, NOM, DENOM>, (u8, PartError)>
+where
+    Instant<u64, NOM, DENOM>: ops::Sub<Output = Duration<u64, NOM, DENOM>>,
+{
+    let start = timer.now();
 
-    fn from_str(input: &str) -> Result<Self, Self::Err> {
-        match input.chars().take(2).try_fold(0, |acc, digit| match digit {
-            '0'..='9' => Some(acc * 10 + digit as u32 - '0' as u32),
-            _ => None,
-        }) {
+    handler.started(day, start).await;
+
+    for (part, result) in [(1, &mut *part_1), (2, &mut *part_2)] {
+        let part_start = timer.now();
+
+        handler.part_started(part, part_start).await;
+
+        let solved = solve_part(part, result);
+
+        let elapsed = timer.now() - part_start;
+
+        handler
+            .part_finished(part, elapsed, solved.map(|()| result.as_str()))
+            .await;
+
+        solved.map_err(|err| (part, err))?;
+    }
+
+    let elapsed = timer.now() - start;
+
+    handler
+        .ended(day, elapsed, part_1.as_str(), part_2.as_str())
+        .await;
+
+    Ok(elapsed)
+}
+
+impl Day {
+    /// The day `number`, if it is supported.
+    #[must_use]
+    pub fn from_number(number: u32) -> Option<Self> {
+        match number {
             #[cfg(feature = "day01")]
-            Some(1) => Ok(Day::Day01),
+            1 => Some(Day::Day01),
             #[cfg(feature = "day02")]
-            Some(2) => Ok(Day::Day02),
+            2 => Some(Day::Day02),
             #[cfg(feature = "day03")]
-            Some(3) => Ok(Day::Day03),
+            3 => Some(Day::Day03),
             #[cfg(feature = "day04")]
-            Some(4) => Ok(Day::Day04),
+            4 => Some(Day::Day04),
             #[cfg(feature = "day05")]
-            Some(5) => Ok(Day::Day05),
+            5 => Some(Day::Day05),
             #[cfg(feature = "day06")]
-            Some(6) => Ok(Day::Day06),
+            6 => Some(Day::Day06),
             #[cfg(feature = "day07")]
-            Some(7) => Ok(Day::Day07),
+            7 => Some(Day::Day07),
             #[cfg(feature = "day08")]
-            Some(8) => Ok(Day::Day08),
+            8 => Some(Day::Day08),
             #[cfg(feature = "day09")]
-            Some(9) => Ok(Day::Day09),
+            9 => Some(Day::Day09),
             #[cfg(feature = "day10")]
-            Some(10) => Ok(Day::Day10),
+            10 => Some(Day::Day10),
             #[cfg(feature = "day11")]
-            Some(11) => Ok(Day::Day11),
+            11 => Some(Day::Day11),
             #[cfg(feature = "day12")]
-            Some(12) => Ok(Day::Day12),
+            12 => Some(Day::Day12),
             #[cfg(feature = "day13")]
-            Some(13) => Ok(Day::Day13),
+            13 => Some(Day::Day13),
             #[cfg(feature = "day14")]
-            Some(14) => Ok(Day::Day14),
+            14 => Some(Day::Day14),
             #[cfg(feature = "day15")]
-            Some(15) => Ok(Day::Day15),
+            15 => Some(Day::Day15),
             #[cfg(feature = "day16")]
-            Some(16) => Ok(Day::Day16),
+            16 => Some(Day::Day16),
             #[cfg(feature = "day17")]
-            Some(17) => Ok(Day::Day17),
+            17 => Some(Day::Day17),
             #[cfg(feature = "day18")]
-            Some(18) => Ok(Day::Day18),
+            18 => Some(Day::Day18),
             #[cfg(feature = "day19")]
-            Some(19) => Ok(Day::Day19),
+            19 => Some(Day::Day19),
             #[cfg(feature = "day20")]
-            Some(20) => Ok(Day::Day20),
+            20 => Some(Day::Day20),
             #[cfg(feature = "day21")]
-            Some(21) => Ok(Day::Day21),
+            21 => Some(Day::Day21),
             #[cfg(feature = "day22")]
-            Some(22) => Ok(Day::Day22),
+            22 => Some(Day::Day22),
             #[cfg(feature = "day23")]
-            Some(23) => Ok(Day::Day23),
+            23 => Some(Day::Day23),
             #[cfg(feature = "day24")]
-            Some(24) => Ok(Day::Day24),
+            24 => Some(Day::Day24),
             #[cfg(feature = "day25")]
-            Some(25) => Ok(Day::Day25),
-            Some(_) => Err("invalid day"),
-            None => Err("invalid number"),
+            25 => Some(Day::Day25),
+            _ => None,
         }
     }
 }
 
+impl core::str::FromStr for Day {
+    type Err = &'static str;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let number = input
+            .chars()
+            .take(2)
+            .try_fold(0, |acc, digit| match digit {
+                '0'..='9' => Some(acc * 10 + digit as u32 - '0' as u32),
+                _ => None,
+            })
+            .ok_or("invalid number")?;
+
+        Day::from_number(number).ok_or("invalid day")
+    }
+}
+
 #[cfg(any(feature = "blocking", feature = "nonblocking"))]
 impl fmt::Display for Day {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         let day = match self {
             #[cfg(feature = "day01")]
-            Day::Day01 => 1,
+            Day::Day01 => <day01::Day>::DAY,
             #[cfg(feature = "day02")]
-            Day::Day02 => 2,
+            Day::Day02 => <day02::Day>::DAY,
             #[cfg(feature = "day03")]
-            Day::Day03 => 3,
+            Day::Day03 => <day03::Day>::DAY,
             #[cfg(feature = "day04")]
-            Day::Day04 => 4,
+            Day::Day04 => <day04::Day>::DAY,
             #[cfg(feature = "day05")]
-            Day::Day05 => 5,
+            Day::Day05 => <day05::Day>::DAY,
             #[cfg(feature = "day06")]
-            Day::Day06 => 6,
+            Day::Day06 => <day06::Day>::DAY,
             #[cfg(feature = "day07")]
-            Day::Day07 => 7,
+            Day::Day07 => <day07::Day>::DAY,
             #[cfg(feature = "day08")]
-            Day::Day08 => 8,
+            Day::Day08 => <day08::Day>::DAY,
             #[cfg(feature = "day09")]
-            Day::Day09 => 9,
+            Day::Day09 => <day09::Day>::DAY,
             #[cfg(feature = "day10")]
-            Day::Day10 => 10,
+            Day::Day10 => <day10::Day>::DAY,
             #[cfg(feature = "day11")]
-            Day::Day11 => 11,
+            Day::Day11 => <day11::Day>::DAY,
             #[cfg(feature = "day12")]
-            Day::Day12 => 12,
+            Day::Day12 => <day12::Day>::DAY,
             #[cfg(feature = "day13")]
-            Day::Day13 => 13,
+            Day::Day13 => <day13::Day>::DAY,
             #[cfg(feature = "day14")]
-            Day::Day14 => 14,
+            Day::Day14 => <day14::Day>::DAY,
             #[cfg(feature = "day15")]
-            Day::Day15 => 15,
+            Day::Day15 => <day15::Day>::DAY,
             #[cfg(feature = "day16")]
-            Day::Day16 => 16,
+            Day::Day16 => <day16::Day>::DAY,
             #[cfg(feature = "day17")]
-            Day::Day17 => 17,
+            Day::Day17 => <day17::Day>::DAY,
             #[cfg(feature = "day18")]
-            Day::Day18 => 18,
+            Day::Day18 => <day18::Day>::DAY,
             #[cfg(feature = "day19")]
-            Day::Day19 => 19,
+            Day::Day19 => <day19::Day>::DAY,
             #[cfg(feature = "day20")]
-            Day::Day20 => 20,
+            Day::Day20 => <day20::Day>::DAY,
             #[cfg(feature = "day21")]
-            Day::Day21 => 21,
+            Day::Day21 => <day21::Day>::DAY,
             #[cfg(feature = "day22")]
-            Day::Day22 => 22,
+            Day::Day22 => <day22::Day>::DAY,
             #[cfg(feature = "day23")]
-            Day::Day23 => 23,
+            Day::Day23 => <day23::Day>::DAY,
             #[cfg(feature = "day24")]
-            Day::Day24 => 24,
+            Day::Day24 => <day24::Day>::DAY,
             #[cfg(feature = "day25")]
-            Day::Day25 => 25,
+            Day::Day25 => <day25::Day>::DAY,
         };
 
         write!(f, "{day:02}")
@@ -338,8 +528,15 @@ pub trait Timer<T, const NOM: u32, const DENOM: u32> {
     fn now(&self) -> Instant<T, NOM, DENOM>;
 }
 
+/// The lifecycle of the requests answered by the runner, to show it on the
+/// board.
+///
+/// Every method does nothing by default.
 pub trait Handler<T, const NOM: u32, const DENOM: u32> {
+    /// A day is being solved.
     fn started(&mut self, _day: Day, _timestamp: Instant<T, NOM, DENOM>) {}
+
+    /// Both parts of the day have been solved.
     fn ended(
         &mut self,
         _day: Day,
@@ -348,8 +545,112 @@ pub trait Handler<T, const NOM: u32, const DENOM: u32> {
         _part_2: &str,
     ) {
     }
+
+    /// Part `part` of the day is being solved.
+    fn part_started(&mut self, _part: u8, _timestamp: Instant<T, NOM, DENOM>) {}
+
+    /// Part `part` has been solved with its answer, or it has failed.
+    fn part_finished(
+        &mut self,
+        _part: u8,
+        _elapsed: Duration<T, NOM, DENOM>,
+        _result: Result<&str, PartError>,
+    ) {
+    }
+
+    /// `bytes` bytes have been read, of a request in progress or not.
+    fn input_received(&mut self, _bytes: usize) {}
+
     fn unsupported_day(&mut self) {}
+
     fn invalid_input(&mut self) {}
+
+    /// A request has been dropped.
+    fn error(&mut self, _kind: ErrorKind) {}
+}
+
+/// Like [`Handler`], for the handlers of the nonblocking runner that wait.
+///
+/// Every [`Handler`] is an [`AsyncHandler`] that does not wait.
+// the executors of the boards are single threaded, the futures need not be
+// `Send`
+#[allow(async_fn_in_trait)]
+pub trait AsyncHandler<T, const NOM: u32, const DENOM: u32> {
+    async fn started(&mut self, _day: Day, _timestamp: Instant<T, NOM, DENOM>) {}
+
+    async fn ended(
+        &mut self,
+        _day: Day,
+        _elapsed: Duration<T, NOM, DENOM>,
+        _part_1: &str,
+        _part_2: &str,
+    ) {
+    }
+
+    async fn part_started(&mut self, _part: u8, _timestamp: Instant<T, NOM, DENOM>) {}
+
+    async fn part_finished(
+        &mut self,
+        _part: u8,
+        _elapsed: Duration<T, NOM, DENOM>,
+        _result: Result<&str, PartError>,
+    ) {
+    }
+
+    async fn input_received(&mut self, _bytes: usize) {}
+
+    async fn unsupported_day(&mut self) {}
+
+    async fn invalid_input(&mut self) {}
+
+    async fn error(&mut self, _kind: ErrorKind) {}
+}
+
+impl<T, const NOM: u32, const DENOM: u32, H: Handler<T, NOM, DENOM>> AsyncHandler<T, NOM, DENOM>
+    for H
+{
+    async fn started(&mut self, day: Day, timestamp: Instant<T, NOM, DENOM>) {
+        Handler::started(self, day, timestamp);
+    }
+
+    async fn ended(
+        &mut self,
+        day: Day,
+        elapsed: Duration<T, NOM, DENOM>,
+        part_1: &str,
+        part_2: &str,
+    ) {
+        Handler::ended(self, day, elapsed, part_1, part_2);
+    }
+
+    async fn part_started(&mut self, part: u8, timestamp: Instant<T, NOM, DENOM>) {
+        Handler::part_started(self, part, timestamp);
+    }
+
+    async fn part_finished(
+        &mut self,
+        part: u8,
+        elapsed: Duration<T, NOM, DENOM>,
+        result: Result<&str, PartError>,
+    ) {
+        Handler::part_finished(self, part, elapsed, result);
+    }
+
+    async fn input_received(&mut self, bytes: usize) {
+        Handler::input_received(self, bytes);
+    }
+
+    async fn unsupported_day(&mut self) {
+        Handler::unsupported_day(self);
+    }
+
+    async fn invalid_input(&mut self) {
+        Handler::invalid_input(self);
+    }
+
+    async fn error(&mut self, kind: ErrorKind) {
+        Handler::error(self, kind);
+    }
 }
 
 #[derive(Default)]
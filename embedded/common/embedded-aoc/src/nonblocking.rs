@@ -5,141 +5,224 @@ use embedded_io_async::{Read, Write};
 
 type Response = heapless::String<255>;
 
-const BUFFER_SIZE: usize = 25 * 1024;
-
+#[cfg(feature = "framed")]
+use crate::framed;
 use crate::{
-    info, trace, warn, Day, Duration, Handler, Instant, PartResult, Timer, END_INPUT_TAG,
-    START_INPUT_TAG,
+    info,
+    text::{Event, Receiver},
+    trace, warn, AsyncHandler, Duration, ErrorKind, Instant, PartResult, Timer, BUFFER_SIZE,
 };
 
 /// # Panics
 pub async fn run<const NOM: u32, const DENOM: u32>(
     (mut rx, mut tx): (impl Read, impl Write),
     timer: &impl Timer<u64, NOM, DENOM>,
-    mut handler: impl Handler<u64, NOM, DENOM>,
+    mut handler: impl AsyncHandler<u64, NOM, DENOM>,
 ) -> !
 where
     Instant<u64, NOM, DENOM>: ops::Sub<Output = Duration<u64, NOM, DENOM>>,
 {
     static RESPONSE: static_cell::StaticCell<Response> = static_cell::StaticCell::new();
     static BUFFER: static_cell::StaticCell<[u8; BUFFER_SIZE]> = static_cell::StaticCell::new();
-    
+    #[cfg(feature = "framed")]
+    static FRAME_RESPONSE: static_cell::StaticCell<[u8; framed::RESPONSE_SIZE]> =
+        static_cell::StaticCell::new();
+    static RECEIVER: static_cell::StaticCell<Receiver> = static_cell::StaticCell::new();
+
     trace!("run");
 
     let response = RESPONSE.init_with(|| Response::new());
 
     let buffer = BUFFER.init_with(|| [0; BUFFER_SIZE]);
-    loop {
-        let mut length = 0;
-        loop {
-            if length >= buffer.len() {
-                warn!("buffer overflow");
-                break;
-            }
-
-            match rx.read(&mut buffer[length..]).await {
-                Err(_err) => {
-                    #[cfg(feature = "log")]
-                    warn!("error reading: {_err:?}");
-                }
-                Ok(0) => {
-                    trace!("reading 0 bytes");
-                }
-                Ok(count) => {
-                    debug_assert!(length + count <= buffer.len(), "invalid count");
-
-                    length += count;
-
-                    if let Ok(input) = core::str::from_utf8(&buffer[..length]) {
-                        match (input.find(START_INPUT_TAG), input.find(END_INPUT_TAG)) {
-                            (Some(start_position), Some(end_position)) => {
-                                let Ok(day) =
-                                    input[start_position + START_INPUT_TAG.len()..].parse::<Day>()
-                                else {
-                                    warn!("unsupported day");
-
-                                    handler.unsupported_day();
 
-                                    tx.write_all(b"unsupported day\r\n").await.ok();
+    #[cfg(feature = "framed")]
+    let frame_response = FRAME_RESPONSE.init_with(|| [0; framed::RESPONSE_SIZE]);
 
-                                    break;
-                                };
+    let receiver = RECEIVER.init_with(Receiver::new);
 
-                                let input = input
-                                    [start_position + START_INPUT_TAG.len() + 2..end_position]
-                                    .trim();
-
-                                info!("[{}] start working on {}", day, day);
-
-                                let mut part_1 = PartResult::new();
-                                let mut part_2 = PartResult::new();
-
-                                let start = timer.now();
+    // the bytes of framed requests, kept while the receiver is idle
+    let mut length = 0;
+    loop {
+        #[cfg(feature = "framed")]
+        {
+            length =
+                answer_frames(buffer, length, frame_response, &mut tx, timer, &mut handler).await;
+        }
 
-                                handler.started(day, start);
+        let filled = length + receiver.len();
+        if filled >= buffer.len() {
+            warn!("buffer overflow");
 
-                                if day.solve_1(&mut part_1, input).is_err() {
-                                    warn!("part_1: buffer overflow");
-                                    break;
-                                }
+            handler.error(ErrorKind::Overflow).await;
 
-                                if day.solve_2(&mut part_2, input).is_err() {
-                                    warn!("part_2: buffer overflow");
-                                    break;
-                                }
+            #[cfg(feature = "framed")]
+            if length > 0 {
+                tx.write_all(framed::overflow(frame_response)).await.ok();
+            }
 
-                                let elapsed = timer.now() - start;
+            length = 0;
+            receiver.reset();
+            continue;
+        }
 
-                                handler.ended(day, elapsed, part_1.as_str(), part_2.as_str());
+        match rx.read(&mut buffer[filled..]).await {
+            Err(_err) => {
+                #[cfg(feature = "log")]
+                warn!("error reading: {_err:?}");
 
-                                info!("[{}] part 1: {}", day, part_1.as_str());
+                handler.error(ErrorKind::Read).await;
+            }
+            Ok(0) => {
+                trace!("reading 0 bytes");
+            }
+            Ok(count) => {
+                debug_assert!(filled + count <= buffer.len(), "invalid count");
 
-                                response.clear();
-                                write!(response, "[{day}] part 1: {part_1}\r\n").ok();
-                                tx.write_all(response.as_bytes()).await.ok();
+                handler.input_received(count).await;
 
-                                info!("[{}] part 2: {}", day, part_2.as_str());
+                #[cfg(feature = "framed")]
+                let count = if receiver.is_idle() && buffer[0] == 0 {
+                    length += count;
 
-                                response.clear();
-                                write!(response, "[{day}] part 2: {part_2}\r\n").ok();
-                                tx.write_all(response.as_bytes()).await.ok();
+                    // a framed request, answered when it is complete
+                    let Some(stray) = framed::stray(&buffer[..length]) else {
+                        continue;
+                    };
+
+                    // a stray `0x00`, dropped, the rest is text
+                    buffer.copy_within(stray..length, 0);
+                    core::mem::take(&mut length) - stray
+                } else {
+                    count
+                };
+
+                // the text after a framed request is scanned with the new bytes
+                let count = core::mem::take(&mut length) + count;
+                if let Some(event) = receiver.received(buffer, count) {
+                    answer_text(event, response, &mut tx, timer, &mut handler).await;
+                }
+            }
+        }
+    }
+}
 
-                                info!(
-                                    "[{}] elapsed: {}ms ({}us)",
-                                    day,
-                                    elapsed.to_millis(),
-                                    elapsed.to_micros()
-                                );
+/// Answers a request of the text mode.
+// defmt does not support inlined format arguments
+#[allow(clippy::uninlined_format_args)]
+// the stream of a streamed request is consumed
+#[cfg_attr(not(feature = "streaming"), allow(clippy::needless_pass_by_value))]
+async fn answer_text<const NOM: u32, const DENOM: u32>(
+    event: Event<'_>,
+    response: &mut Response,
+    tx: &mut impl Write,
+    timer: &impl Timer<u64, NOM, DENOM>,
+    handler: &mut impl AsyncHandler<u64, NOM, DENOM>,
+) where
+    Instant<u64, NOM, DENOM>: ops::Sub<Output = Duration<u64, NOM, DENOM>>,
+{
+    let mut part_1 = PartResult::new();
+    let mut part_2 = PartResult::new();
+
+    let (day, solved) = match event {
+        Event::Request(day, input) => (
+            day,
+            day.solve(input, (&mut part_1, &mut part_2), timer, handler)
+                .await,
+        ),
+        #[cfg(feature = "streaming")]
+        Event::Streamed(stream) => (
+            stream.day(),
+            stream
+                .solve((&mut part_1, &mut part_2), timer, handler)
+                .await,
+        ),
+        Event::UnsupportedDay => {
+            warn!("unsupported day");
+
+            handler.unsupported_day().await;
+
+            tx.write_all(b"unsupported day\r\n").await.ok();
+            return;
+        }
+        Event::InvalidInput => {
+            warn!("invalid input");
 
-                                response.clear();
-                                write!(
-                                    response,
-                                    "[{day}] elapsed: {}ms ({}us)\r\n",
-                                    elapsed.to_millis(),
-                                    elapsed.to_micros()
-                                )
-                                .ok();
-                                tx.write_all(response.as_bytes()).await.ok();
+            handler.invalid_input().await;
 
-                                break;
-                            }
-                            (None, Some(_)) => {
-                                warn!("invalid input");
+            tx.write_all(b"invalid input\r\n").await.ok();
+            return;
+        }
+        Event::InvalidUtf8 => {
+            warn!("invalid utf8 data");
 
-                                handler.invalid_input();
+            handler.error(ErrorKind::InvalidUtf8).await;
+            return;
+        }
+    };
+
+    let elapsed = match solved {
+        Ok(elapsed) => elapsed,
+        Err((part, err)) => {
+            warn!("[{}] part {}: {}", day, part, err);
+            response.clear();
+            write!(response, "[{day}] part {part}: error: {err}\r\n").ok();
+            tx.write_all(response.as_bytes()).await.ok();
+            return;
+        }
+    };
+
+    info!("[{}] part 1: {}", day, part_1.as_str());
+
+    response.clear();
+    write!(response, "[{day}] part 1: {part_1}\r\n").ok();
+    tx.write_all(response.as_bytes()).await.ok();
+
+    info!("[{}] part 2: {}", day, part_2.as_str());
+
+    response.clear();
+    write!(response, "[{day}] part 2: {part_2}\r\n").ok();
+    tx.write_all(response.as_bytes()).await.ok();
+
+    info!(
+        "[{}] elapsed: {}ms ({}us)",
+        day,
+        elapsed.to_millis(),
+        elapsed.to_micros()
+    );
+
+    response.clear();
+    write!(
+        response,
+        "[{day}] elapsed: {}ms ({}us)\r\n",
+        elapsed.to_millis(),
+        elapsed.to_micros()
+    )
+    .ok();
+    tx.write_all(response.as_bytes()).await.ok();
+}
 
-                                tx.write_all(b"invalid input\r\n").await.ok();
+/// Answers the complete frames at the start of `buffer[..length]`, returns
+/// the length of the rest.
+#[cfg(feature = "framed")]
+async fn answer_frames<const NOM: u32, const DENOM: u32>(
+    buffer: &mut [u8],
+    mut length: usize,
+    response: &mut [u8; framed::RESPONSE_SIZE],
+    tx: &mut impl Write,
+    timer: &impl Timer<u64, NOM, DENOM>,
+    handler: &mut impl AsyncHandler<u64, NOM, DENOM>,
+) -> usize
+where
+    Instant<u64, NOM, DENOM>: ops::Sub<Output = Duration<u64, NOM, DENOM>>,
+{
+    while let Some((frame, end)) = framed::find(&buffer[..length]) {
+        let answer =
+            framed::answer(&mut buffer[frame], BUFFER_SIZE, response, timer, handler).await;
+        tx.write_all(answer).await.ok();
 
-                                break;
-                            }
-                            _ => {}
-                        }
-                    } else {
-                        warn!("invalid utf8 data");
-                        break;
-                    }
-                }
-            }
-        }
+        buffer.copy_within(end..length, 0);
+        length -= end;
     }
+
+    length
 }
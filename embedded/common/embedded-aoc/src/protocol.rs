@@ -0,0 +1,245 @@
+//! The framed protocol of [`run`](crate::run), for the hosts that drive a
+//! board.
+//!
+//! A frame is `0x00`, [`MAGIC`], the [COBS] encoding of a message followed by
+//! its CRC-16/IBM-SDLC (little endian), and `0x00`. The message is the
+//! postcard encoding of [`VERSION`], the request id (echoed by the response)
+//! and a [`Request`] or a [`Response`].
+//!
+//! The leading `0x00` and [`MAGIC`] switch `run` from the text mode to the
+//! framed mode, so a terminal can still send `START INPUT DAY: NN` …
+//! `END INPUT`, even after a stray `0x00`.
+//!
+//! [COBS]: https://en.wikipedia.org/wiki/Consistent_Overhead_Byte_Stuffing
+
+use core::fmt;
+
+use crc::{Crc, CRC_16_IBM_SDLC};
+
+use postcard::ser_flavors::{crc::CrcModifier, Cobs, Slice};
+
+use serde::{Deserialize, Serialize};
+
+/// The version of the protocol, a frame with another version is answered with
+/// [`ErrorCode::Version`].
+pub const VERSION: u8 = 1;
+
+/// The byte after the leading `0x00` of a frame, not ASCII so it is not typed
+/// on a terminal.
+pub const MAGIC: u8 = 0xa5;
+
+static CRC: Crc<u16> = Crc::<u16>::new(&CRC_16_IBM_SDLC);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Request<'a> {
+    /// Answered by [`Response::Pong`].
+    Ping,
+    /// Answered by [`Response::Info`].
+    Info,
+    /// Answered by [`Response::Days`].
+    ListDays,
+    /// Solves both parts of `day`, answered by [`Response::Solved`].
+    Solve { day: u8, input: &'a str },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Response<'a> {
+    Pong,
+    Info {
+        version: u8,
+        /// The largest frame the board can receive.
+        capacity: u32,
+    },
+    /// The supported days, bit `n` for day `n`.
+    Days(u32),
+    Solved {
+        day: u8,
+        part_1: &'a str,
+        part_2: &'a str,
+        elapsed_us: u64,
+    },
+    Error(ErrorCode),
+}
+
+/// Why a request failed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ErrorCode {
+    /// The frame is corrupted, the id of the response is 0.
+    Frame,
+    /// The frame has another [`VERSION`], the id of the response is 0.
+    Version,
+    /// The message is not a [`Request`], the id of the response is 0.
+    Request,
+    /// The frame does not fit the buffer of the board.
+    Overflow,
+    UnsupportedDay,
+    Solve {
+        part: u8,
+        error: SolveError,
+    },
+}
+
+/// Why a part could not produce its answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SolveError {
+    Parse,
+    Capacity,
+    Unsolvable,
+    /// The answer does not fit the response.
+    Answer,
+}
+
+impl From<solution::Error> for SolveError {
+    fn from(err: solution::Error) -> Self {
+        match err {
+            solution::Error::Parse | solution::Error::ParseAt { .. } => SolveError::Parse,
+            solution::Error::Capacity => SolveError::Capacity,
+            solution::Error::Unsolvable => SolveError::Unsolvable,
+        }
+    }
+}
+
+#[cfg_attr(feature = "defmt", derive(defmt::Format))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The frame does not start with [`MAGIC`].
+    Magic,
+    /// The COBS encoding is invalid.
+    Cobs,
+    /// The CRC does not match.
+    Crc,
+    /// The frame has another [`VERSION`].
+    Version(u8),
+    /// The message cannot be encoded or decoded.
+    Message,
+    /// The frame does not fit the buffer.
+    Overflow,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self {
+            Error::Magic => write!(f, "missing magic byte"),
+            Error::Cobs => write!(f, "invalid cobs encoding"),
+            Error::Crc => write!(f, "crc mismatch"),
+            Error::Version(version) => write!(f, "unsupported version {version}"),
+            Error::Message => write!(f, "invalid message"),
+            Error::Overflow => write!(f, "buffer overflow"),
+        }
+    }
+}
+
+impl core::error::Error for Error {}
+
+/// Encodes `message` with `id` in a frame, delimiters included.
+///
+/// # Errors
+/// [`Error::Overflow`] if the frame does not fit `buffer`.
+pub fn encode<'a, T: Serialize>(
+    id: u16,
+    message: &T,
+    buffer: &'a mut [u8],
+) -> Result<&'a [u8], Error> {
+    let ([delimiter, magic], rest) = buffer.split_first_chunk_mut().ok_or(Error::Overflow)?;
+    *delimiter = 0;
+    *magic = MAGIC;
+
+    let cobs = Cobs::try_new(Slice::new(rest)).map_err(|_| Error::Overflow)?;
+    let length = postcard::serialize_with_flavor(
+        &(VERSION, id, message),
+        CrcModifier::new(cobs, CRC.digest()),
+    )
+    .map_err(|err| match err {
+        postcard::Error::SerializeBufferFull => Error::Overflow,
+        _ => Error::Message,
+    })?
+    .len();
+
+    Ok(&buffer[..length + 2])
+}
+
+/// Decodes the id and the message of a frame, `frame` is the data between the
+/// delimiters, [`MAGIC`] included, and it is decoded in place.
+///
+/// # Errors
+/// See [`Error`].
+pub fn decode<'a, T: Deserialize<'a>>(frame: &'a mut [u8]) -> Result<(u16, T), Error> {
+    let (&mut magic, frame) = frame.split_first_mut().ok_or(Error::Magic)?;
+    if magic != MAGIC {
+        return Err(Error::Magic);
+    }
+
+    let length = cobs::decode_in_place(frame).map_err(|_| Error::Cobs)?;
+    let frame: &'a [u8] = frame;
+
+    let (data, crc) = frame[..length].split_last_chunk::<2>().ok_or(Error::Crc)?;
+    if CRC.checksum(data) != u16::from_le_bytes(*crc) {
+        return Err(Error::Crc);
+    }
+
+    let (version, data) = postcard::take_from_bytes::<u8>(data).map_err(|_| Error::Message)?;
+    if version != VERSION {
+        return Err(Error::Version(version));
+    }
+
+    let (id, data) = postcard::take_from_bytes::<u16>(data).map_err(|_| Error::Message)?;
+    let message = postcard::from_bytes(data).map_err(|_| Error::Message)?;
+
+    Ok((id, message))
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use std::vec::Vec;
+
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let mut buffer = [0; 64];
+        let frame = encode(
+            7,
+            &Request::Solve {
+                day: 1,
+                input: "3   4\n4   3",
+            },
+            &mut buffer,
+        )
+        .unwrap();
+        assert_eq!(frame.first(), Some(&0));
+        assert_eq!(frame.last(), Some(&0));
+        assert!(!frame[1..frame.len() - 1].contains(&0));
+
+        let mut frame = Vec::from(&frame[1..frame.len() - 1]);
+        assert_eq!(
+            decode(&mut frame),
+            Ok((
+                7,
+                Request::Solve {
+                    day: 1,
+                    input: "3   4\n4   3"
+                }
+            ))
+        );
+    }
+
+    #[test]
+    fn corrupted() {
+        let mut buffer = [0; 64];
+        let length = encode(1, &Response::Pong, &mut buffer).unwrap().len();
+
+        let mut frame = Vec::from(&buffer[1..length - 1]);
+        frame[2] ^= 0x10;
+        assert_eq!(decode::<Response>(&mut frame), Err(Error::Crc));
+
+        frame[0] = b'S';
+        assert_eq!(decode::<Response>(&mut frame), Err(Error::Magic));
+
+        assert_eq!(
+            encode(1, &Response::Days(u32::MAX), &mut [0; 4]),
+            Err(Error::Overflow)
+        );
+    }
+}
@@ -0,0 +1,140 @@
+// without a day that streams the states are uninhabited
+#![cfg_attr(
+    not(any(feature = "day01", feature = "day02")),
+    allow(unreachable_code, unused_variables, dead_code)
+)]
+
+use core::fmt::Write as _;
+use core::ops;
+
+use solution::StreamingSolution;
+
+use crate::{info, AsyncHandler, Day, Duration, Instant, PartError, PartResult, Timer};
+
+/// A day solved while its input is received, a line at a time, see
+/// [`StreamingSolution`].
+///
+/// The elapsed time covers only the answers, the lines are parsed as they
+/// arrive.
+pub(crate) struct Stream {
+    state: State,
+    /// The first error of the lines pushed so far.
+    error: Option<solution::Error>,
+}
+
+// a single stream at a time, kept by the receiver of the text mode
+#[allow(clippy::large_enum_variant)]
+enum State {
+    #[cfg(feature = "day01")]
+    Day01(<day01::Day as StreamingSolution>::State),
+    #[cfg(feature = "day02")]
+    Day02(<day02::Day as StreamingSolution>::State),
+}
+
+impl Day {
+    /// Starts the stream of the day in `stream`, if it consumes its input a
+    /// line at a time.
+    pub(crate) fn start_stream(self, stream: &mut Option<Stream>) -> bool {
+        match self {
+            #[cfg(feature = "day01")]
+            Day::Day01 => Stream::start::<day01::Day>(stream, State::Day01),
+            #[cfg(feature = "day02")]
+            Day::Day02 => Stream::start::<day02::Day>(stream, State::Day02),
+            #[allow(unreachable_patterns)]
+            _ => return false,
+        }
+
+        true
+    }
+}
+
+impl Stream {
+    fn start<S: StreamingSolution>(stream: &mut Option<Stream>, state: fn(S::State) -> State) {
+        *stream = Some(Stream {
+            state: state(S::State::default()),
+            error: None,
+        });
+    }
+
+    fn finish<S: StreamingSolution>(
+        state: &mut S::State,
+        part: u8,
+        result: &mut PartResult,
+    ) -> Result<(), PartError> {
+        if part == 1 {
+            let value = S::try_finish_1(state)?;
+            write!(result, "{value}").map_err(|_| PartError::Overflow)
+        } else {
+            let value = S::try_finish_2(state)?;
+            write!(result, "{value}").map_err(|_| PartError::Overflow)
+        }
+    }
+
+    pub(crate) fn day(&self) -> Day {
+        match self.state {
+            #[cfg(feature = "day01")]
+            State::Day01(_) => Day::Day01,
+            #[cfg(feature = "day02")]
+            State::Day02(_) => Day::Day02,
+        }
+    }
+
+    /// Pushes a line of the input, the empty lines are skipped and an invalid
+    /// line fails part 1.
+    pub(crate) fn push_line(&mut self, line: &[u8]) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let Ok(line) = core::str::from_utf8(line) else {
+            self.error = Some(solution::Error::Parse);
+            return;
+        };
+
+        let line = line.trim_end();
+        if line.is_empty() {
+            return;
+        }
+
+        let pushed: Result<(), solution::Error> = match self.state {
+            #[cfg(feature = "day01")]
+            State::Day01(ref mut state) => day01::Day::push_line(state, line),
+            #[cfg(feature = "day02")]
+            State::Day02(ref mut state) => day02::Day::push_line(state, line),
+        };
+
+        self.error = pushed.err();
+    }
+
+    /// Like [`Day::solve`], with the lines pushed so far.
+    // defmt does not support inlined format arguments
+    #[allow(clippy::uninlined_format_args)]
+    pub(crate) async fn solve<const NOM: u32, const DENOM: u32>(
+        &mut self,
+        parts: (&mut PartResult, &mut PartResult),
+        timer: &impl Timer<u64, NOM, DENOM>,
+        handler: &mut impl AsyncHandler<u64, NOM, DENOM>,
+    ) -> Result<Duration<u64, NOM, DENOM>, (u8, PartError)>
+    where
+        Instant<u64, NOM, DENOM>: ops::Sub<Output = Duration<u64, NOM, DENOM>>,
+    {
+        let day = self.day();
+
+        info!("[{}] finish working on {}", day, day);
+
+        let solve_part = |part, result: &mut PartResult| {
+            if let Some(err) = self.error {
+                return Err(err.into());
+            }
+
+            match self.state {
+                #[cfg(feature = "day01")]
+                State::Day01(ref mut state) => Self::finish::<day01::Day>(state, part, result),
+                #[cfg(feature = "day02")]
+                State::Day02(ref mut state) => Self::finish::<day02::Day>(state, part, result),
+            }
+        };
+
+        crate::solve(day, solve_part, parts, timer, handler).await
+    }
+}
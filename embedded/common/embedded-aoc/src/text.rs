@@ -0,0 +1,240 @@
+//! The text mode of [`run`](crate::run): `START INPUT DAY: NN`, the input and
+//! `END INPUT`.
+//!
+//! The tags are matched a byte at a time as the bytes are read, so a read
+//! costs as much as its own bytes whatever the size of the input received so
+//! far.
+
+use core::mem;
+
+#[cfg(feature = "streaming")]
+use crate::stream::Stream;
+use crate::{Day, END_INPUT_TAG, START_INPUT_TAG};
+
+/// Matches a tag a byte at a time.
+///
+/// A mismatch restarts from the current byte, that is enough because the
+/// first byte of the tags does not occur again in them.
+struct Tag {
+    tag: &'static [u8],
+    matched: usize,
+}
+
+impl Tag {
+    const fn new(tag: &'static str) -> Self {
+        Self {
+            tag: tag.as_bytes(),
+            matched: 0,
+        }
+    }
+
+    /// Whether `byte` completes the tag.
+    fn matches(&mut self, byte: u8) -> bool {
+        if self.tag[self.matched] == byte {
+            self.matched += 1;
+        } else {
+            self.matched = usize::from(self.tag[0] == byte);
+        }
+
+        if self.matched == self.tag.len() {
+            self.matched = 0;
+            true
+        } else {
+            false
+        }
+    }
+}
+
+enum State {
+    /// Waiting for [`START_INPUT_TAG`].
+    Idle,
+    /// Reading the two digits of the day.
+    Day([u8; 2], usize),
+    /// Reading the input of the day, `None` if it is not supported.
+    Input(Option<Day>),
+    /// Pushing the lines of the input to the stream of the day.
+    #[cfg(feature = "streaming")]
+    Stream,
+}
+
+/// A complete request, or the reason it has been dropped.
+pub(crate) enum Event<'a> {
+    Request(Day, &'a str),
+    /// The lines have been pushed to the stream, that is ready to be solved.
+    #[cfg(feature = "streaming")]
+    Streamed(&'a mut Stream),
+    UnsupportedDay,
+    /// [`END_INPUT_TAG`] without [`START_INPUT_TAG`].
+    InvalidInput,
+    InvalidUtf8,
+}
+
+/// Scans the bytes read in a buffer, keeping in it only the input of the
+/// current request.
+pub(crate) struct Receiver {
+    state: State,
+    start: Tag,
+    end: Tag,
+    /// The bytes of the input at the start of the buffer.
+    length: usize,
+    #[cfg(feature = "streaming")]
+    stream: Option<Stream>,
+}
+
+impl Receiver {
+    pub(crate) const fn new() -> Self {
+        Self {
+            state: State::Idle,
+            start: Tag::new(START_INPUT_TAG),
+            end: Tag::new(END_INPUT_TAG),
+            length: 0,
+            #[cfg(feature = "streaming")]
+            stream: None,
+        }
+    }
+
+    /// The bytes kept at the start of the buffer, the next read goes after
+    /// them.
+    pub(crate) fn len(&self) -> usize {
+        self.length
+    }
+
+    /// Whether no request is in progress, the buffer is empty.
+    #[cfg(feature = "framed")]
+    pub(crate) fn is_idle(&self) -> bool {
+        matches!(self.state, State::Idle)
+    }
+
+    /// Drops the current request.
+    pub(crate) fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Scans the `count` bytes read at `buffer[self.len()..]`, the bytes after
+    /// a complete request are dropped.
+    pub(crate) fn received<'a>(
+        &'a mut self,
+        buffer: &'a mut [u8],
+        count: usize,
+    ) -> Option<Event<'a>> {
+        let (mut length, end) = (self.length, self.length + count);
+        for position in self.length..end {
+            let byte = buffer[position];
+
+            if self.start.matches(byte) {
+                self.state = State::Day([0; 2], 0);
+                self.end.matched = 0;
+                length = 0;
+                continue;
+            }
+
+            match &mut self.state {
+                State::Idle => {
+                    if self.end.matches(byte) {
+                        self.reset();
+                        return Some(Event::InvalidInput);
+                    }
+                }
+                State::Day(digits, count) => {
+                    digits[*count] = byte;
+                    *count += 1;
+                    if *count == digits.len() {
+                        let day = core::str::from_utf8(digits)
+                            .ok()
+                            .and_then(|day| day.parse::<Day>().ok());
+
+                        self.state = State::Input(day);
+
+                        #[cfg(feature = "streaming")]
+                        if day.is_some_and(|day| day.start_stream(&mut self.stream)) {
+                            self.state = State::Stream;
+                        }
+                    }
+                }
+                State::Input(day) => {
+                    buffer[length] = byte;
+                    length += 1;
+
+                    if self.end.matches(byte) {
+                        let day = mem::replace(&mut self.state, State::Idle);
+                        self.length = 0;
+
+                        let State::Input(Some(day)) = day else {
+                            return Some(Event::UnsupportedDay);
+                        };
+
+                        let input = &buffer[..length - END_INPUT_TAG.len()];
+                        return Some(match core::str::from_utf8(input) {
+                            Ok(input) => Event::Request(day, input.trim()),
+                            Err(_) => Event::InvalidUtf8,
+                        });
+                    }
+
+                    // the input of an unsupported day is not kept
+                    if day.is_none() {
+                        length = 0;
+                    }
+                }
+                #[cfg(feature = "streaming")]
+                State::Stream => {
+                    buffer[length] = byte;
+                    length += 1;
+
+                    let ended = self.end.matches(byte);
+                    if byte == b'\n' || ended {
+                        let line = &buffer[..length - if ended { END_INPUT_TAG.len() } else { 1 }];
+                        if let Some(stream) = self.stream.as_mut() {
+                            stream.push_line(line);
+                        }
+
+                        length = 0;
+                    }
+
+                    if ended {
+                        self.state = State::Idle;
+                        self.length = 0;
+                        return self.stream.as_mut().map(Event::Streamed);
+                    }
+                }
+            }
+        }
+
+        self.length = length;
+
+        None
+    }
+}
+
+#[cfg(all(test, feature = "day01"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags() {
+        let mut tag = Tag::new(END_INPUT_TAG);
+        assert!(!b"EEND INPU".iter().any(|&byte| tag.matches(byte)));
+        assert!(tag.matches(b'T'));
+        assert_eq!(tag.matched, 0);
+    }
+
+    #[cfg(not(feature = "streaming"))]
+    #[test]
+    fn fragments() {
+        let request = b"junk START INPUT DAY: 01\n3   4\n4   3\nEND INPUT\nSTART";
+
+        let mut buffer = [0; 64];
+        let mut receiver = Receiver::new();
+        for (position, &byte) in request.iter().enumerate() {
+            buffer[receiver.len()] = byte;
+            match receiver.received(&mut buffer, 1) {
+                Some(Event::Request(_, input)) => {
+                    assert_eq!(input, "3   4\n4   3");
+                    assert_eq!(position, request.len() - 7);
+                }
+                None => {}
+                _ => panic!("invalid event"),
+            }
+        }
+        assert_eq!(receiver.len(), 0);
+    }
+}